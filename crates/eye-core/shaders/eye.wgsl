@@ -9,10 +9,11 @@ struct Uniforms {
     sclera_color: vec3f,
     squash_stretch: f32,
 
-    // Highlight (16 bytes)
-    highlight_offset: vec2f,
-    highlight_radius: f32,
-    highlight_intensity: f32,
+    // Highlights (16 bytes)
+    highlight_count: u32,
+    _pad_highlight_a: u32,
+    _pad_highlight_b: u32,
+    _pad_highlight_c: u32,
 
     // Global (32 bytes)
     bg_color: vec3f,
@@ -44,16 +45,37 @@ struct Uniforms {
 
     // Head orientation (16 bytes)
     head_pitch: f32,
-    highlight_blur: f32,
-    _pad_head_b: f32,
-    _pad_head_c: f32,
-
-    // Bezier outline: open state (128 bytes)
-    // 4 segments × 2 vec4f. Each vec4f packs 2 vec2f control points.
-    outline_open: array<vec4f, 8>,
-
-    // Bezier outline: closed state (128 bytes)
-    outline_closed: array<vec4f, 8>,
+    _pad_head: f32,
+    bg_alpha: f32,
+    visibility: u32,
+
+    // Outline segment counts (16 bytes)
+    outline_segments: u32,
+    upper_segments: u32,
+    iris_segments: u32,
+    pupil_segments: u32,
+
+    // Lids (16 bytes)
+    upper_lid_close: f32,
+    lower_lid_raise: f32,
+    crease_thickness: f32,
+    crease_offset: f32,
+
+    // Lid skin (32 bytes)
+    lid_color: vec3f,
+    _pad_lid_color: f32,
+    crease_color: vec3f,
+    _pad_crease: f32,
+
+    // Bezier outline: open state (384 bytes)
+    // Up to 12 segments × 2 vec4f. Each vec4f packs 2 vec2f control points.
+    outline_open: array<vec4f, 24>,
+
+    // Bezier outline: upper lid closed target (384 bytes)
+    outline_closed: array<vec4f, 24>,
+
+    // Bezier outline: lower lid closed target (384 bytes)
+    outline_lower_closed: array<vec4f, 24>,
 
     // Eyebrow (224 bytes)
     eyebrow_color: vec3f,
@@ -72,11 +94,33 @@ struct Uniforms {
     pupil_color: vec3f,
     pupil_radius: f32,
 
-    // Iris Bezier outline (128 bytes)
-    iris_outline: array<vec4f, 8>,
+    // Iris Bezier outline (384 bytes)
+    iris_outline: array<vec4f, 24>,
 
-    // Pupil Bezier outline (128 bytes)
-    pupil_outline: array<vec4f, 8>,
+    // Pupil Bezier outline (384 bytes)
+    pupil_outline: array<vec4f, 24>,
+
+    // Highlights (4 × 448 bytes)
+    highlights: array<Highlight, 4>,
+}
+
+// One specular highlight (448 bytes)
+struct Highlight {
+    offset: vec2f,
+    radius: f32,
+    aspect: f32,
+    color: vec3f,
+    intensity: f32,
+    blur: f32,
+    parallax: f32,
+    rotation: f32,
+    shape: u32,
+    segments: u32,
+    _pad_a: u32,
+    _pad_b: u32,
+    _pad_c: u32,
+    // Same layout as outline_open, in units of radius
+    outline: array<vec4f, 24>,
 }
 
 struct EyePair {
@@ -101,6 +145,23 @@ struct VertexOutput {
 
 const SUBDIV: u32 = 16u;
 
+// Feature bits of `visibility` (mirrors EyeUniforms::SHOW_*)
+const SHOW_EYEBROW: u32 = 1u;
+const SHOW_EYELASH: u32 = 2u;
+const SHOW_HIGHLIGHT: u32 = 4u;
+const SHOW_IRIS: u32 = 8u;
+const SHOW_PUPIL: u32 = 16u;
+const SHOW_LID_FILL: u32 = 32u;
+const SHOW_CREASE: u32 = 64u;
+
+// Highlight shapes (mirrors HighlightShape::to_uniform)
+const HIGHLIGHT_ELLIPSE: u32 = 1u;
+const HIGHLIGHT_BEZIER: u32 = 2u;
+
+fn visible(feature: u32) -> bool {
+    return (u.visibility & feature) != 0u;
+}
+
 // ============================================================
 // Vertex shader: fullscreen triangle
 // ============================================================
@@ -164,20 +225,46 @@ fn point_segment_test(p: vec2f, a: vec2f, b: vec2f) -> vec2f {
 }
 
 // ============================================================
-// Evaluate eye outline: returns signed distance
+// Lid closure: eyelid_close drives both lids, the per-lid
+// values close them further on top.
+// ============================================================
+
+fn upper_lid() -> f32 {
+    return 1.0 - (1.0 - u.eyelid_close) * (1.0 - u.upper_lid_close);
+}
+
+fn lower_lid() -> f32 {
+    return 1.0 - (1.0 - u.eyelid_close) * (1.0 - u.lower_lid_raise);
+}
+
+// Control point `idx` (owned by anchor `k`) of the outline with one lid
+// closed by `close_t`. The upper lid moves anchors 0..=upper_segments
+// toward outline_closed, the lower lid the rest plus both corners toward
+// outline_lower_closed.
+fn lid_control_point(idx: u32, k: u32, lower: bool, close_t: f32) -> vec4f {
+    let on_upper = k <= u.upper_segments;
+    let on_lower = k == 0u || k >= u.upper_segments;
+    let t = select(0.0, close_t, select(on_upper, on_lower, lower));
+    let closed = select(u.outline_closed[idx], u.outline_lower_closed[idx], lower);
+    return mix(u.outline_open[idx], closed, t);
+}
+
+// ============================================================
+// Evaluate the region left open by one lid: returns signed distance
 // (negative = inside, positive = outside)
 // ============================================================
 
-fn eval_outline(p: vec2f, close_t: f32) -> f32 {
+fn eval_lid_region(p: vec2f, lower: bool, close_t: f32) -> f32 {
     var min_d2 = 1e10;
     var winding = 0.0;
 
-    for (var seg = 0u; seg < 4u; seg++) {
+    for (var seg = 0u; seg < u.outline_segments; seg++) {
         let idx = seg * 2u;
+        let next = (seg + 1u) % u.outline_segments;
 
         // Interpolate between open and closed control points
-        let cp0 = mix(u.outline_open[idx], u.outline_closed[idx], close_t);
-        let cp1 = mix(u.outline_open[idx + 1u], u.outline_closed[idx + 1u], close_t);
+        let cp0 = lid_control_point(idx, seg, lower, close_t);
+        let cp1 = lid_control_point(idx + 1u, next, lower, close_t);
 
         let P0 = cp0.xy;
         let P1 = cp0.zw;
@@ -201,6 +288,21 @@ fn eval_outline(p: vec2f, close_t: f32) -> f32 {
     return dist * sign_val;
 }
 
+// ============================================================
+// Evaluate eye outline: the sclera clipped by both lid curves.
+// Returns signed distance (negative = inside, positive = outside)
+// ============================================================
+
+fn eval_outline(p: vec2f) -> f32 {
+    var d = eval_lid_region(p, false, upper_lid());
+    // An open lower lid leaves the open outline, which the upper region is inside of
+    let lower_t = lower_lid();
+    if lower_t > 0.0 {
+        d = max(d, eval_lid_region(p, true, lower_t));
+    }
+    return d;
+}
+
 // ============================================================
 // Evaluate eyebrow outline: returns signed distance
 // The eyebrow is shifted vertically based on eyelid state.
@@ -248,7 +350,7 @@ fn eval_iris_outline(p: vec2f) -> f32 {
     var min_d2 = 1e10;
     var winding = 0.0;
 
-    for (var seg = 0u; seg < 4u; seg++) {
+    for (var seg = 0u; seg < u.iris_segments; seg++) {
         let idx = seg * 2u;
         let cp0 = u.iris_outline[idx];
         let cp1 = u.iris_outline[idx + 1u];
@@ -274,6 +376,67 @@ fn eval_iris_outline(p: vec2f) -> f32 {
     return dist * sign_val;
 }
 
+// ============================================================
+// Evaluate highlight outline: returns signed distance
+// (negative = inside, positive = outside)
+// ============================================================
+
+fn eval_highlight_outline(h: u32, p: vec2f) -> f32 {
+    var min_d2 = 1e10;
+    var winding = 0.0;
+
+    for (var seg = 0u; seg < u.highlights[h].segments; seg++) {
+        let idx = seg * 2u;
+        let cp0 = u.highlights[h].outline[idx];
+        let cp1 = u.highlights[h].outline[idx + 1u];
+
+        let P0 = cp0.xy;
+        let P1 = cp0.zw;
+        let P2 = cp1.xy;
+        let P3 = cp1.zw;
+
+        var prev = P0;
+        for (var i = 1u; i <= SUBDIV; i++) {
+            let t = f32(i) / f32(SUBDIV);
+            let curr = cubic_bezier(t, P0, P1, P2, P3);
+            let result = point_segment_test(p, prev, curr);
+            min_d2 = min(min_d2, result.x);
+            winding += result.y;
+            prev = curr;
+        }
+    }
+
+    let dist = sqrt(min_d2);
+    let sign_val = select(1.0, -1.0, winding != 0.0);
+    return dist * sign_val;
+}
+
+// Signed distance to highlight `h`, with `p` relative to its center.
+fn eval_highlight(h: u32, p: vec2f) -> f32 {
+    let hl = u.highlights[h];
+    if hl.shape != HIGHLIGHT_ELLIPSE && hl.shape != HIGHLIGHT_BEZIER {
+        return sd_circle(p, hl.radius);
+    }
+
+    // Undo the rotation, then scale to a unit shape
+    let c = cos(hl.rotation);
+    let s = sin(hl.rotation);
+    let local = vec2f(c * p.x + s * p.y, -s * p.x + c * p.y);
+    let size = max(vec2f(hl.radius, hl.radius * hl.aspect), vec2f(1e-4));
+    if hl.shape == HIGHLIGHT_BEZIER {
+        // Scaled distance is exact for uniform scale, close enough otherwise
+        return eval_highlight_outline(h, local / size) * min(size.x, size.y);
+    }
+
+    // Ellipse distance approximation (exact on the boundary)
+    let k0 = length(local / size);
+    let k1 = length(local / (size * size));
+    if k1 < 1e-6 {
+        return -min(size.x, size.y);
+    }
+    return k0 * (k0 - 1.0) / k1;
+}
+
 // ============================================================
 // Evaluate pupil outline: returns signed distance
 // (negative = inside, positive = outside)
@@ -283,7 +446,7 @@ fn eval_pupil_outline(p: vec2f) -> f32 {
     var min_d2 = 1e10;
     var winding = 0.0;
 
-    for (var seg = 0u; seg < 4u; seg++) {
+    for (var seg = 0u; seg < u.pupil_segments; seg++) {
         let idx = seg * 2u;
         let cp0 = u.pupil_outline[idx];
         let cp1 = u.pupil_outline[idx + 1u];
@@ -315,12 +478,15 @@ fn eval_pupil_outline(p: vec2f) -> f32 {
 // ============================================================
 
 fn render_eyebrow(p: vec2f, mirror: f32, h_scale: f32, v_scale: f32, rest_h_scale: f32) -> vec4f {
+    if !visible(SHOW_EYEBROW) {
+        return vec4f(0.0, 0.0, 0.0, 0.0);
+    }
     let foreshortened = vec2f(p.x / h_scale, p.y / v_scale);
     let local_p = vec2f(foreshortened.x * mirror, foreshortened.y);
 
     // Apply rest_h_scale to cancel rest-position foreshortening (WYSIWYG at yaw=0)
     let corrected_p = vec2f(local_p.x * rest_h_scale, local_p.y);
-    let d_brow = eval_eyebrow_outline(corrected_p, u.eyelid_close);
+    let d_brow = eval_eyebrow_outline(corrected_p, upper_lid());
     let aa = fwidth(d_brow) * 0.5;
     let brow_mask = 1.0 - smoothstep(-aa, aa, d_brow);
 
@@ -333,18 +499,18 @@ fn render_eyebrow(p: vec2f, mirror: f32, h_scale: f32, v_scale: f32, rest_h_scal
 
 // ============================================================
 // Evaluate unsigned distance to the upper eye outline
-// (segments 0: Left→Top, 1: Top→Right only).
+// (the first `upper_segments` segments, Left corner → Right corner).
 // Returns vec2f(distance, t_along) where t_along is 0..1
-// parametric position along the upper arc (0=Left, 0.5=Top, 1=Right).
+// parametric position along the upper arc (0=Left, 1=Right).
 // ============================================================
 
 fn eval_upper_outline_dist(p: vec2f, close_t: f32) -> vec2f {
     var min_d2 = 1e10;
     var best_t = 0.5;
-    let total_steps = f32(2u * SUBDIV);
+    let total_steps = f32(max(u.upper_segments, 1u) * SUBDIV);
 
-    // Only upper 2 segments (Left→Top, Top→Right)
-    for (var seg = 0u; seg < 2u; seg++) {
+    // Only the upper segments (Left → Right over the top)
+    for (var seg = 0u; seg < u.upper_segments; seg++) {
         let idx = seg * 2u;
         let cp0 = mix(u.outline_open[idx], u.outline_closed[idx], close_t);
         let cp1 = mix(u.outline_open[idx + 1u], u.outline_closed[idx + 1u], close_t);
@@ -381,6 +547,9 @@ fn eval_upper_outline_dist(p: vec2f, close_t: f32) -> vec2f {
 // ============================================================
 
 fn render_eyelash(p: vec2f, mirror: f32, h_scale: f32, v_scale: f32, rest_h_scale: f32) -> vec4f {
+    if !visible(SHOW_EYELASH) {
+        return vec4f(0.0, 0.0, 0.0, 0.0);
+    }
     let foreshortened = vec2f(p.x / h_scale, p.y / v_scale);
     let local_p = vec2f(foreshortened.x * mirror, foreshortened.y);
 
@@ -390,7 +559,7 @@ fn render_eyelash(p: vec2f, mirror: f32, h_scale: f32, v_scale: f32, rest_h_scal
 
     // Apply rest_h_scale to match outline correction
     let corrected_p = vec2f(sq_p.x * rest_h_scale, sq_p.y);
-    let result = eval_upper_outline_dist(corrected_p, u.eyelid_close);
+    let result = eval_upper_outline_dist(corrected_p, upper_lid());
     let dist = result.x;
     let t_along = result.y;  // 0=Left tip, 0.5=Top center, 1=Right tip
 
@@ -410,6 +579,38 @@ fn render_eyelash(p: vec2f, mirror: f32, h_scale: f32, v_scale: f32, rest_h_scal
     return vec4f(u.eyelash_color, lash_mask);
 }
 
+// ============================================================
+// Render the lid crease as a stroke following the open upper
+// outline, lifted by crease_offset. Fades out toward both tips.
+// ============================================================
+
+fn render_crease(p: vec2f, mirror: f32, h_scale: f32, v_scale: f32, rest_h_scale: f32) -> vec4f {
+    if !visible(SHOW_CREASE) {
+        return vec4f(0.0, 0.0, 0.0, 0.0);
+    }
+    let foreshortened = vec2f(p.x / h_scale, p.y / v_scale);
+    let local_p = vec2f(foreshortened.x * mirror, foreshortened.y);
+
+    // Apply same squash/stretch as the eye
+    let ss_scale = 1.0 + u.squash_stretch;
+    let sq_p = vec2f(local_p.x / ss_scale, local_p.y * ss_scale);
+
+    let corrected_p = vec2f(sq_p.x * rest_h_scale, sq_p.y - u.crease_offset);
+    let result = eval_upper_outline_dist(corrected_p, 0.0);
+    let dist = result.x;
+    let taper = sin(result.y * 3.14159265);
+    let thickness = u.crease_thickness * taper;
+
+    let aa = fwidth(dist) * 0.5;
+    let crease_mask = 1.0 - smoothstep(thickness - aa, thickness + aa, dist);
+
+    if crease_mask < 0.001 {
+        return vec4f(0.0, 0.0, 0.0, 0.0);
+    }
+
+    return vec4f(u.crease_color, crease_mask);
+}
+
 // ============================================================
 // Render a single eye at local coordinates.
 // `mirror` is 1.0 for left eye, -1.0 for right eye.
@@ -427,12 +628,20 @@ fn render_eye(p: vec2f, mirror: f32, h_scale: f32, v_scale: f32, rest_h_scale: f
     // --- Outline (replaces sclera ellipse + eyelid clipping) ---
     // Apply rest_h_scale to cancel rest-position foreshortening (WYSIWYG at yaw=0)
     let outline_p = vec2f(sq_p.x * rest_h_scale, sq_p.y);
-    let d_outline = eval_outline(outline_p, u.eyelid_close);
+    let d_outline = eval_outline(outline_p);
     let aa = fwidth(d_outline) * 0.5;
     let outline_mask = 1.0 - smoothstep(-aa, aa, d_outline);
 
+    // --- Lid skin: the open outline where the lids cover it ---
+    var lid_mask = 0.0;
+    if visible(SHOW_LID_FILL) {
+        let d_open = eval_lid_region(outline_p, false, 0.0);
+        let aa_o = fwidth(d_open) * 0.5;
+        lid_mask = (1.0 - smoothstep(-aa_o, aa_o, d_open)) * (1.0 - outline_mask);
+    }
+
     if outline_mask < 0.001 {
-        return vec4f(0.0, 0.0, 0.0, 0.0);
+        return vec4f(u.lid_color, lid_mask);
     }
 
     // --- Compose eye content ---
@@ -447,26 +656,47 @@ fn render_eye(p: vec2f, mirror: f32, h_scale: f32, v_scale: f32, rest_h_scale: f
     // Correct iris/pupil query coords: multiply x by rest_h_scale to cancel
     // the rest-position foreshortening, so editor shapes = screen shapes at yaw=0.
     let iris_p_shape = vec2f(iris_p.x * rest_h_scale, iris_p.y);
-    let d_iris = eval_iris_outline(iris_p_shape);
-    let aa_i = fwidth(d_iris) * 0.5;
-    let iris_mask = 1.0 - smoothstep(-aa_i, aa_i, d_iris);
-    eye_color = mix(eye_color, u.iris_color, iris_mask);
+    if visible(SHOW_IRIS) {
+        let d_iris = eval_iris_outline(iris_p_shape);
+        let aa_i = fwidth(d_iris) * 0.5;
+        let iris_mask = 1.0 - smoothstep(-aa_i, aa_i, d_iris);
+        eye_color = mix(eye_color, u.iris_color, iris_mask);
+    }
 
     // --- Pupil (center of iris) ---
-    let d_pupil = eval_pupil_outline(iris_p_shape);
-    let aa_p = fwidth(d_pupil) * 0.5;
-    let pupil_mask = 1.0 - smoothstep(-aa_p, aa_p, d_pupil);
-    eye_color = mix(eye_color, u.pupil_color, pupil_mask);
-
-    // --- Highlight (additive, over everything) ---
-    let look_shift = vec2f(u.look_x * 0.05, u.look_y * 0.05);
-    let hl_p = outline_p - u.highlight_offset - look_shift;
-    let d_hl = sd_circle(hl_p, u.highlight_radius);
-    let blur_h = max(fwidth(d_hl) * 0.5, u.highlight_blur);
-    let hl_mask = 1.0 - smoothstep(-blur_h, blur_h, d_hl);
-    eye_color = eye_color + vec3f(u.highlight_intensity * hl_mask);
-
-    return vec4f(eye_color, outline_mask);
+    if visible(SHOW_PUPIL) {
+        let d_pupil = eval_pupil_outline(iris_p_shape);
+        let aa_p = fwidth(d_pupil) * 0.5;
+        let pupil_mask = 1.0 - smoothstep(-aa_p, aa_p, d_pupil);
+        eye_color = mix(eye_color, u.pupil_color, pupil_mask);
+    }
+
+    // --- Highlights (additive, over everything) ---
+    if visible(SHOW_HIGHLIGHT) {
+        let look = vec2f(u.look_x, u.look_y);
+        for (var h = 0u; h < u.highlight_count; h++) {
+            let hl = u.highlights[h];
+            let hl_p = outline_p - hl.offset - look * hl.parallax;
+            let d_hl = eval_highlight(h, hl_p);
+            let blur_h = max(fwidth(d_hl) * 0.5, hl.blur);
+            let hl_mask = 1.0 - smoothstep(-blur_h, blur_h, d_hl);
+            eye_color = eye_color + hl.color * (hl.intensity * hl_mask);
+        }
+    }
+
+    // Eye opening over lid skin (straight alpha)
+    let alpha = outline_mask + lid_mask;
+    return vec4f((eye_color * outline_mask + u.lid_color * lid_mask) / alpha, alpha);
+}
+
+// ============================================================
+// Compositing
+// ============================================================
+
+// Porter-Duff "over": straight-alpha layer onto a premultiplied accumulator.
+// With an opaque accumulator this is identical to mix(dst.rgb, src, a).
+fn over(dst: vec4f, src: vec3f, a: f32) -> vec4f {
+    return vec4f(src * a, a) + dst * (1.0 - a);
 }
 
 // ============================================================
@@ -504,7 +734,8 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4f {
     // --- Nod sink: vertical displacement during nod ---
     p.y += g.nod_sink;
 
-    var color = g.bg_color;
+    // Premultiplied RGBA accumulator; bg_alpha = 0 leaves only eye coverage as alpha
+    var color = vec4f(g.bg_color * g.bg_alpha, g.bg_alpha);
 
     // --- Sphere projection model ---
     // Eyes are decals on a virtual sphere. Rotation causes foreshortening.
@@ -557,11 +788,13 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4f {
             let fade = (1.0 - smoothstep(BBOX_HX - BBOX_FADE, BBOX_HX, left_lx))
                      * (1.0 - smoothstep(BBOX_HY - BBOX_FADE, BBOX_HY, left_ly));
             let left_brow = render_eyebrow(left_p, 1.0, left_h_scale, v_scale, rest_h_scale);
-            color = mix(color, left_brow.xyz, left_brow.w * fade);
+            color = over(color, left_brow.xyz, left_brow.w * fade);
             let left = render_eye(left_p, 1.0, left_h_scale, v_scale, rest_h_scale);
-            color = mix(color, left.xyz, left.w * fade);
+            color = over(color, left.xyz, left.w * fade);
+            let left_crease = render_crease(left_p, 1.0, left_h_scale, v_scale, rest_h_scale);
+            color = over(color, left_crease.xyz, left_crease.w * fade);
             let left_lash = render_eyelash(left_p, 1.0, left_h_scale, v_scale, rest_h_scale);
-            color = mix(color, left_lash.xyz, left_lash.w * fade);
+            color = over(color, left_lash.xyz, left_lash.w * fade);
         }
 
         u = pair.right;
@@ -572,11 +805,13 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4f {
             let fade = (1.0 - smoothstep(BBOX_HX - BBOX_FADE, BBOX_HX, right_lx))
                      * (1.0 - smoothstep(BBOX_HY - BBOX_FADE, BBOX_HY, right_ly));
             let right_brow = render_eyebrow(right_p, -1.0, right_h_scale, v_scale, rest_h_scale);
-            color = mix(color, right_brow.xyz, right_brow.w * fade);
+            color = over(color, right_brow.xyz, right_brow.w * fade);
             let right = render_eye(right_p, -1.0, right_h_scale, v_scale, rest_h_scale);
-            color = mix(color, right.xyz, right.w * fade);
+            color = over(color, right.xyz, right.w * fade);
+            let right_crease = render_crease(right_p, -1.0, right_h_scale, v_scale, rest_h_scale);
+            color = over(color, right_crease.xyz, right_crease.w * fade);
             let right_lash = render_eyelash(right_p, -1.0, right_h_scale, v_scale, rest_h_scale);
-            color = mix(color, right_lash.xyz, right_lash.w * fade);
+            color = over(color, right_lash.xyz, right_lash.w * fade);
         }
     } else {
         u = pair.right;
@@ -587,11 +822,13 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4f {
             let fade = (1.0 - smoothstep(BBOX_HX - BBOX_FADE, BBOX_HX, right_lx))
                      * (1.0 - smoothstep(BBOX_HY - BBOX_FADE, BBOX_HY, right_ly));
             let right_brow = render_eyebrow(right_p, -1.0, right_h_scale, v_scale, rest_h_scale);
-            color = mix(color, right_brow.xyz, right_brow.w * fade);
+            color = over(color, right_brow.xyz, right_brow.w * fade);
             let right = render_eye(right_p, -1.0, right_h_scale, v_scale, rest_h_scale);
-            color = mix(color, right.xyz, right.w * fade);
+            color = over(color, right.xyz, right.w * fade);
+            let right_crease = render_crease(right_p, -1.0, right_h_scale, v_scale, rest_h_scale);
+            color = over(color, right_crease.xyz, right_crease.w * fade);
             let right_lash = render_eyelash(right_p, -1.0, right_h_scale, v_scale, rest_h_scale);
-            color = mix(color, right_lash.xyz, right_lash.w * fade);
+            color = over(color, right_lash.xyz, right_lash.w * fade);
         }
 
         u = pair.left;
@@ -602,13 +839,15 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4f {
             let fade = (1.0 - smoothstep(BBOX_HX - BBOX_FADE, BBOX_HX, left_lx))
                      * (1.0 - smoothstep(BBOX_HY - BBOX_FADE, BBOX_HY, left_ly));
             let left_brow = render_eyebrow(left_p, 1.0, left_h_scale, v_scale, rest_h_scale);
-            color = mix(color, left_brow.xyz, left_brow.w * fade);
+            color = over(color, left_brow.xyz, left_brow.w * fade);
             let left = render_eye(left_p, 1.0, left_h_scale, v_scale, rest_h_scale);
-            color = mix(color, left.xyz, left.w * fade);
+            color = over(color, left.xyz, left.w * fade);
+            let left_crease = render_crease(left_p, 1.0, left_h_scale, v_scale, rest_h_scale);
+            color = over(color, left_crease.xyz, left_crease.w * fade);
             let left_lash = render_eyelash(left_p, 1.0, left_h_scale, v_scale, rest_h_scale);
-            color = mix(color, left_lash.xyz, left_lash.w * fade);
+            color = over(color, left_lash.xyz, left_lash.w * fade);
         }
     }
 
-    return vec4f(color, 1.0);
+    return color;
 }
@@ -1,3 +1,5 @@
+use serde::{Deserialize, Serialize};
+
 #[derive(Clone, Copy)]
 #[allow(dead_code)]
 pub enum Easing {
@@ -8,9 +10,23 @@ pub enum Easing {
     BounceOut,
     ElasticOut,
     BackOut,
+    /// CSS `cubic-bezier(x1, y1, x2, y2)`: curve from (0, 0) to (1, 1) with
+    /// control points (x1, y1) and (x2, y2). x1 and x2 are clamped to [0, 1].
+    CubicBezier { x1: f32, y1: f32, x2: f32, y2: f32 },
+    /// CSS `steps(count, jump-start | jump-end)`.
+    Steps { count: u32, jump_start: bool },
+    /// Unit-mass damped spring released from 0 toward 1. Evaluated in
+    /// seconds (see [`apply_easing_for`]) and snapped to 1 at the end.
+    Spring { stiffness: f32, damping: f32 },
 }
 
 pub fn apply_easing(t: f32, easing: Easing) -> f32 {
+    apply_easing_for(t, easing, 1.0)
+}
+
+/// [`apply_easing`] for a transition lasting `duration` seconds. Only the
+/// spring depends on real time; every other curve is duration-independent.
+pub fn apply_easing_for(t: f32, easing: Easing, duration: f32) -> f32 {
     match easing {
         Easing::Linear => t,
         Easing::EaseIn => t * t,
@@ -25,6 +41,79 @@ pub fn apply_easing(t: f32, easing: Easing) -> f32 {
         Easing::BounceOut => bounce_out(t),
         Easing::ElasticOut => elastic_out(t),
         Easing::BackOut => back_out(t),
+        Easing::CubicBezier { x1, y1, x2, y2 } => cubic_bezier_easing(t, x1, y1, x2, y2),
+        Easing::Steps { count, jump_start } => steps_easing(t, count, jump_start),
+        Easing::Spring { stiffness, damping } => spring_easing(t * duration, stiffness, damping, t),
+    }
+}
+
+fn cubic_bezier_easing(t: f32, x1: f32, y1: f32, x2: f32, y2: f32) -> f32 {
+    if t <= 0.0 {
+        return 0.0;
+    }
+    if t >= 1.0 {
+        return 1.0;
+    }
+    let (x1, x2) = (x1.clamp(0.0, 1.0), x2.clamp(0.0, 1.0));
+    // Bernstein form with fixed end points (0, 0) and (1, 1)
+    let coord = |s: f32, a: f32, b: f32| {
+        let u = 1.0 - s;
+        3.0 * u * u * s * a + 3.0 * u * s * s * b + s * s * s
+    };
+
+    // x(s) is monotonic for x1, x2 in [0, 1]: bisect for the s with x(s) = t
+    let (mut lo, mut hi) = (0.0_f32, 1.0_f32);
+    let mut s = t;
+    for _ in 0..24 {
+        let x = coord(s, x1, x2);
+        if (x - t).abs() < 1e-5 {
+            break;
+        }
+        if x < t {
+            lo = s;
+        } else {
+            hi = s;
+        }
+        s = 0.5 * (lo + hi);
+    }
+    coord(s, y1, y2)
+}
+
+fn steps_easing(t: f32, count: u32, jump_start: bool) -> f32 {
+    if t >= 1.0 {
+        return 1.0;
+    }
+    let n = count.max(1) as f32;
+    let step = if jump_start {
+        (t * n).floor() + 1.0
+    } else {
+        (t * n).floor()
+    };
+    (step / n).clamp(0.0, 1.0)
+}
+
+/// Displacement of a unit-mass spring at `time` seconds (0 → 1).
+/// `t` is the normalized progress, used to land exactly on 1.
+fn spring_easing(time: f32, stiffness: f32, damping: f32, t: f32) -> f32 {
+    if t <= 0.0 {
+        return 0.0;
+    }
+    if t >= 1.0 {
+        return 1.0;
+    }
+    let omega = stiffness.max(1e-3).sqrt();
+    let zeta = damping.max(0.0) / (2.0 * omega);
+    if zeta < 1.0 {
+        let omega_d = omega * (1.0 - zeta * zeta).sqrt();
+        let decay = (-zeta * omega * time).exp();
+        1.0 - decay * ((omega_d * time).cos() + zeta * omega / omega_d * (omega_d * time).sin())
+    } else if zeta - 1.0 < 1e-4 {
+        1.0 - (-omega * time).exp() * (1.0 + omega * time)
+    } else {
+        let root = (zeta * zeta - 1.0).sqrt();
+        let r1 = -omega * (zeta - root);
+        let r2 = -omega * (zeta + root);
+        1.0 + (r2 * (r1 * time).exp() - r1 * (r2 * time).exp()) / (r1 - r2)
     }
 }
 
@@ -95,17 +184,47 @@ impl Xorshift32 {
     }
 }
 
+/// Longest run of blinks in one cluster (a triple blink).
+const MAX_CLUSTER: u32 = 3;
+/// How long a wink keeps the lid shut unless told otherwise.
+pub const WINK_HOLD: f32 = 0.2;
+
+/// The eye a wink closes; the other one stays open.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WinkEye {
+    Left,
+    Right,
+}
+
+impl WinkEye {
+    pub const ALL: [Self; 2] = [Self::Left, Self::Right];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Left => "Left",
+            Self::Right => "Right",
+        }
+    }
+}
+
 /// A single blink event with asymmetric timing.
 struct BlinkEvent {
     start_time: f32,
     close_duration: f32,
+    /// Time held at the deepest point between closing and opening.
+    hold_duration: f32,
     open_duration: f32,
+    /// Share of the way from rest to fully shut (1.0 = full blink).
+    depth: f32,
+    /// Only this eye closes; `None` blinks both.
+    wink: Option<WinkEye>,
 }
 
 impl BlinkEvent {
     /// Total duration of this blink.
     fn total_duration(&self) -> f32 {
-        self.close_duration + self.open_duration
+        self.close_duration + self.hold_duration + self.open_duration
     }
 
     fn end_time(&self) -> f32 {
@@ -117,11 +236,42 @@ impl BlinkEvent {
 ///
 /// Generates randomised blink events with asymmetric close/open timing
 /// (close ≈ 30%, open ≈ 70%) and variable inter-blink intervals (4–10 s).
+/// Some blinks come in quick double/triple clusters and some only close
+/// part way. Winks close a single eye. Large gaze shifts reported through
+/// [`BlinkAnimation::notify_gaze_shift`] may set off a blink, and the rest
+/// lid follows the vertical gaze set with [`BlinkAnimation::set_look_y`].
+/// The ranges are public so each character can have its own temperament.
 pub struct BlinkAnimation {
     rest_value: f32,
+    /// Total blink duration range in seconds.
+    pub duration_min: f32,
+    pub duration_max: f32,
+    /// Range of the closing phase's share of the total duration.
+    pub close_ratio_min: f32,
+    pub close_ratio_max: f32,
+    /// Range of the wait between blinks in seconds.
+    pub interval_min: f32,
+    pub interval_max: f32,
+    /// Chance that another blink quickly follows (up to a triple blink).
+    pub cluster_chance: f32,
+    /// Chance that an idle blink only closes part way.
+    pub partial_chance: f32,
+    /// Depth range of partial blinks (share of the way to fully shut).
+    pub partial_depth_min: f32,
+    pub partial_depth_max: f32,
+    /// Gaze shift (look units) from which a saccade may set off a blink.
+    pub saccade_blink_threshold: f32,
+    /// Blink chance of a saccade at the threshold, growing with amplitude.
+    pub saccade_blink_chance: f32,
+    /// How far the rest lid follows the vertical gaze: looking down lowers
+    /// it, looking up raises it (eyelid units per look unit).
+    pub lid_follow: f32,
+    look_y: f32,
     rng: Xorshift32,
     current_blink: Option<BlinkEvent>,
     next_blink_time: f32,
+    /// Follow-up blinks left in the current cluster.
+    cluster_left: u32,
     last_t: f32,
 }
 
@@ -132,9 +282,25 @@ impl BlinkAnimation {
         let next_blink_time = rng.range(1.0, 3.0); // first blink comes a bit sooner
         Self {
             rest_value,
+            // Paper: 7–9 frames @ 30fps, 9 best
+            duration_min: 0.230,
+            duration_max: 0.350,
+            close_ratio_min: 0.28,
+            close_ratio_max: 0.38,
+            interval_min: 4.0,
+            interval_max: 10.0,
+            cluster_chance: 0.12,
+            partial_chance: 0.15,
+            partial_depth_min: 0.45,
+            partial_depth_max: 0.8,
+            saccade_blink_threshold: 0.6,
+            saccade_blink_chance: 0.4,
+            lid_follow: 0.25,
+            look_y: 0.0,
             rng,
             current_blink: None,
             next_blink_time,
+            cluster_left: 0,
             last_t: 0.0,
         }
     }
@@ -144,27 +310,93 @@ impl BlinkAnimation {
         Self::new(42, 0.20)
     }
 
+    /// Eyelid value between blinks.
+    pub fn rest_value(&self) -> f32 {
+        self.rest_value
+    }
+
+    pub fn set_rest_value(&mut self, rest_value: f32) {
+        self.rest_value = rest_value.clamp(0.0, 1.0);
+    }
+
+    /// Current vertical gaze, used for the lid follow.
+    pub fn set_look_y(&mut self, look_y: f32) {
+        self.look_y = look_y;
+    }
+
+    /// Report a gaze shift of `amplitude` look units starting at `t`. Shifts
+    /// past `saccade_blink_threshold` may start a blink right away; returns
+    /// whether one was started.
+    pub fn notify_gaze_shift(&mut self, t: f32, amplitude: f32) -> bool {
+        let threshold = self.saccade_blink_threshold.max(1e-3);
+        if amplitude < threshold || self.is_blinking(t) {
+            return false;
+        }
+        let chance = (self.saccade_blink_chance * amplitude / threshold).min(1.0);
+        if self.rng.next_f32() >= chance {
+            return false;
+        }
+        let blink = self.generate_blink(t);
+        let total = blink.total_duration();
+        self.current_blink = Some(blink);
+        self.cluster_left = 0;
+        self.next_blink_time = t + total + self.next_interval();
+        true
+    }
+
     /// Trigger an immediate blink at time `t`.
     pub fn trigger(&mut self, t: f32) {
-        self.current_blink = Some(self.generate_blink(t));
-        let total = self.current_blink.as_ref().unwrap().total_duration();
-        self.next_blink_time = t + total + self.rng.range(4.0, 10.0);
+        self.trigger_with(t, None, 0.0, None);
+    }
+
+    /// Trigger a full blink with custom timing. `None` durations keep the
+    /// natural randomised value; `hold_duration` keeps the lid shut between phases.
+    pub fn trigger_with(
+        &mut self,
+        t: f32,
+        close_duration: Option<f32>,
+        hold_duration: f32,
+        open_duration: Option<f32>,
+    ) {
+        self.start_explicit(t, None, close_duration, hold_duration, open_duration);
     }
 
-    /// Advance internal state and return the current `eyelid_close` value.
+    /// Close only `eye` at time `t`, held shut briefly.
+    pub fn wink(&mut self, t: f32, eye: WinkEye) {
+        self.wink_with(t, eye, None, WINK_HOLD, None);
+    }
+
+    /// Wink with custom timing, as [`BlinkAnimation::trigger_with`].
+    pub fn wink_with(
+        &mut self,
+        t: f32,
+        eye: WinkEye,
+        close_duration: Option<f32>,
+        hold_duration: f32,
+        open_duration: Option<f32>,
+    ) {
+        self.start_explicit(t, Some(eye), close_duration, hold_duration, open_duration);
+    }
+
+    /// Advance internal state and return the current `eyelid_close` values
+    /// as `[left, right]`.
     ///
     /// Must be called with monotonically increasing `t` (seconds since start).
-    pub fn evaluate(&mut self, t: f32) -> f32 {
+    pub fn evaluate(&mut self, t: f32) -> [f32; 2] {
         self.advance(t);
-        self.compute_value(t)
+        self.peek_value(t)
     }
 
-    /// Return the `eyelid_close` value at time `t` **without** mutating state.
+    /// Return the `[left, right]` `eyelid_close` values at time `t`
+    /// **without** mutating state.
     ///
     /// Useful for computing velocity from a past sample without disturbing the
     /// scheduling timeline.
-    pub fn peek_value(&self, t: f32) -> f32 {
-        self.compute_value(t)
+    pub fn peek_value(&self, t: f32) -> [f32; 2] {
+        [
+            self.compute_value(t, WinkEye::Left),
+            self.compute_value(t, WinkEye::Right),
+        ]
     }
 
     /// Returns `true` if a blink is currently in progress at time `t`.
@@ -182,6 +414,7 @@ impl BlinkAnimation {
         // Detect large time jumps (e.g. toggle off→on): reset scheduling.
         if t < self.last_t - 0.5 {
             self.current_blink = None;
+            self.cluster_left = 0;
             self.next_blink_time = t + self.rng.range(1.0, 3.0);
         }
         self.last_t = t;
@@ -195,52 +428,119 @@ impl BlinkAnimation {
 
         // If no blink is active and it's time for the next one, start it.
         if self.current_blink.is_none() && t >= self.next_blink_time {
-            self.current_blink = Some(self.generate_blink(t));
-            // Schedule the *next* blink 4–10 seconds from now.
-            let total = self.current_blink.as_ref().unwrap().total_duration();
-            self.next_blink_time = t + total + self.rng.range(4.0, 10.0);
+            let mut blink = self.generate_blink(t);
+            if self.rng.next_f32() < self.partial_chance {
+                let min = self.partial_depth_min.clamp(0.0, 1.0);
+                blink.depth = self.rng.range(min, self.partial_depth_max.clamp(min, 1.0));
+            }
+            let total = blink.total_duration();
+            self.current_blink = Some(blink);
+
+            if self.cluster_left > 0 {
+                self.cluster_left -= 1;
+            } else {
+                // A new cluster: each extra blink follows with `cluster_chance`
+                while self.cluster_left < MAX_CLUSTER - 1 && self.rng.next_f32() < self.cluster_chance {
+                    self.cluster_left += 1;
+                }
+            }
+            // Schedule the *next* blink: quickly within a cluster, otherwise
+            // after a random interval.
+            let wait = if self.cluster_left > 0 {
+                self.rng.range(0.05, 0.15)
+            } else {
+                self.next_interval()
+            };
+            self.next_blink_time = t + total + wait;
         }
     }
 
+    /// Start a triggered blink or wink, replacing any blink in progress.
+    fn start_explicit(
+        &mut self,
+        t: f32,
+        wink: Option<WinkEye>,
+        close_duration: Option<f32>,
+        hold_duration: f32,
+        open_duration: Option<f32>,
+    ) {
+        let mut blink = self.generate_blink(t);
+        if let Some(close) = close_duration {
+            blink.close_duration = close.max(1e-3);
+        }
+        if let Some(open) = open_duration {
+            blink.open_duration = open.max(1e-3);
+        }
+        blink.hold_duration = hold_duration.max(0.0);
+        blink.wink = wink;
+        let total = blink.total_duration();
+        self.current_blink = Some(blink);
+        self.cluster_left = 0;
+        self.next_blink_time = t + total + self.next_interval();
+    }
+
+    /// Random wait between the end of one blink and the next.
+    fn next_interval(&mut self) -> f32 {
+        let min = self.interval_min.max(0.0);
+        self.rng.range(min, self.interval_max.max(min))
+    }
+
     /// Generate a single blink event starting at time `t`.
     fn generate_blink(&mut self, t: f32) -> BlinkEvent {
-        // Total blink duration: 230–350 ms (paper: 7–9 frames @ 30fps, 9 best)
-        let total = self.rng.range(0.230, 0.350);
-        // Close/open ratio: close 28–38% of total
-        let close_ratio = self.rng.range(0.28, 0.38);
+        let min = self.duration_min.max(0.01);
+        let total = self.rng.range(min, self.duration_max.max(min));
+        // Close/open ratio: share of the total spent closing
+        let min = self.close_ratio_min.clamp(0.05, 0.95);
+        let close_ratio = self.rng.range(min, self.close_ratio_max.clamp(min, 0.95));
         BlinkEvent {
             start_time: t,
             close_duration: total * close_ratio,
+            hold_duration: 0.0,
             open_duration: total * (1.0 - close_ratio),
+            depth: 1.0,
+            wink: None,
         }
     }
 
-    /// Pure computation of eyelid_close at time `t` given current blink state.
-    fn compute_value(&self, t: f32) -> f32 {
+    /// Rest lid shifted by the vertical gaze.
+    fn follow_rest(&self) -> f32 {
+        (self.rest_value - self.look_y * self.lid_follow).clamp(0.0, 1.0)
+    }
+
+    /// Pure computation of `eye`'s eyelid_close at time `t` given current blink state.
+    fn compute_value(&self, t: f32, eye: WinkEye) -> f32 {
+        let rest = self.follow_rest();
         let Some(ref blink) = self.current_blink else {
-            return self.rest_value;
+            return rest;
         };
+        if blink.wink.is_some_and(|w| w != eye) {
+            return rest;
+        }
+        let peak = rest + (1.0 - rest) * blink.depth;
 
         let elapsed = t - blink.start_time;
 
         if elapsed < 0.0 {
             // Before blink starts (peek into the past)
-            return self.rest_value;
+            return rest;
         }
 
         if elapsed < blink.close_duration {
-            // Closing phase: rest → 1.0 with EaseIn (accelerating shut)
+            // Closing phase: rest → peak with EaseIn (accelerating shut)
             let p = elapsed / blink.close_duration;
             let eased = apply_easing(p, Easing::EaseIn);
-            self.rest_value + (1.0 - self.rest_value) * eased
+            rest + (peak - rest) * eased
+        } else if elapsed < blink.close_duration + blink.hold_duration {
+            // Held shut
+            peak
         } else if elapsed < blink.total_duration() {
-            // Opening phase: 1.0 → rest with EaseOut (decelerating open)
-            let p = (elapsed - blink.close_duration) / blink.open_duration;
+            // Opening phase: peak → rest with EaseOut (decelerating open)
+            let p = (elapsed - blink.close_duration - blink.hold_duration) / blink.open_duration;
             let eased = apply_easing(p, Easing::EaseOut);
-            1.0 - (1.0 - self.rest_value) * eased
+            peak - (peak - rest) * eased
         } else {
             // After blink ends
-            self.rest_value
+            rest
         }
     }
 }
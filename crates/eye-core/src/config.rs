@@ -1,13 +1,19 @@
 use serde::{Deserialize, Serialize};
 
+use crate::animation::BlinkAnimation;
+use crate::listening::ListeningNod;
+use crate::microsaccade::MicrosaccadeAnimation;
 use crate::nod::{NodAnimation, NodCurve};
 use crate::outline::{
     BezierAnchor, BezierOutline, EyeShape, EyebrowGuide, EyebrowOutline, EyebrowShape,
-    EyelashShape, IrisShape, PupilShape,
+    EyelashShape, EyelidShape, Highlight, HighlightShape, IrisShape, PupilShape,
 };
+use crate::pupil::PupilDynamics;
+use crate::renderer::EyeUniforms;
 
+use crate::controller::{EyeController, EyeSideState};
 #[cfg(feature = "gui")]
-use crate::gui::{EyeSideState, SectionLink, Side};
+use crate::gui::{SectionLink, Side};
 
 // ============================================================
 // Serializable config types
@@ -31,16 +37,15 @@ pub struct EyeSideConfig {
 
     // Scalar parameters (from uniforms)
     pub eyelid_close: f32,
+    #[serde(default)]
+    pub upper_lid_close: f32,
+    #[serde(default)]
+    pub lower_lid_raise: f32,
     pub iris_radius: f32,
     pub iris_follow: f32,
     #[serde(default = "default_iris_offset_y")]
     pub iris_offset_y: f32,
     pub pupil_radius: f32,
-    pub highlight_offset: [f32; 2],
-    pub highlight_radius: f32,
-    pub highlight_intensity: f32,
-    #[serde(default)]
-    pub highlight_blur: f32,
     pub look_x: f32,
     pub look_y: f32,
 
@@ -48,8 +53,36 @@ pub struct EyeSideConfig {
     pub eye_shape: EyeShapeConfig,
     pub eyebrow_shape: EyebrowShapeConfig,
     pub eyelash_shape: EyelashShapeConfig,
+    #[serde(default)]
+    pub eyelid_shape: EyelidShapeConfig,
     pub iris_shape: BezierOutlineConfig,
     pub pupil_shape: BezierOutlineConfig,
+    /// Highlights in drawing order.
+    #[serde(default = "default_highlights")]
+    pub highlights: Vec<HighlightConfig>,
+    /// Features drawn on this eye; the global `show_*` toggles hide them
+    /// on both eyes on top.
+    #[serde(default = "default_true")]
+    pub show_eyebrow: bool,
+    #[serde(default = "default_true")]
+    pub show_eyelash: bool,
+    #[serde(default = "default_true")]
+    pub show_highlight: bool,
+    #[serde(default = "default_true")]
+    pub show_iris: bool,
+    #[serde(default = "default_true")]
+    pub show_pupil: bool,
+
+    // Single circular highlight of older files, read only
+    // (see `migrate_legacy_highlight` and `LEGACY_HIGHLIGHT_FIELDS`)
+    #[serde(default, skip_serializing)]
+    highlight_offset: Option<[f32; 2]>,
+    #[serde(default, skip_serializing)]
+    highlight_radius: Option<f32>,
+    #[serde(default, skip_serializing)]
+    highlight_intensity: Option<f32>,
+    #[serde(default, skip_serializing)]
+    highlight_blur: Option<f32>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -57,14 +90,19 @@ pub struct EyeShapeConfig {
     pub open: BezierOutlineConfig,
     pub closed: BezierOutlineConfig,
     pub close_arch: f32,
+    /// Lower lid target; older files without it use `closed`.
+    #[serde(default)]
+    pub lower_closed: Option<BezierOutlineConfig>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct BezierOutlineConfig {
-    pub anchors: [BezierAnchorConfig; 4],
+    /// Anchors in path order. Older files always have 4; more than
+    /// `BezierOutline::MAX_ANCHORS` are dropped on load.
+    pub anchors: Vec<BezierAnchorConfig>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct BezierAnchorConfig {
     pub position: [f32; 2],
     pub handle_in: [f32; 2],
@@ -83,6 +121,20 @@ pub struct EyebrowShapeConfig {
     pub color: [f32; 3],
 }
 
+/// Field names of the single circular highlight that `highlights` replaced.
+/// Still accepted on load (also as timeline track properties) and by the
+/// protocol's `set_eye`, never written.
+pub(crate) const LEGACY_HIGHLIGHT_FIELDS: [&str; 4] = [
+    "highlight_offset",
+    "highlight_radius",
+    "highlight_intensity",
+    "highlight_blur",
+];
+
+fn default_highlights() -> Vec<HighlightConfig> {
+    vec![HighlightConfig::default()]
+}
+
 fn default_iris_offset_y() -> f32 {
     -0.02
 }
@@ -106,12 +158,51 @@ pub struct EyelashShapeConfig {
     pub thickness: f32,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EyelidShapeConfig {
+    pub fill: bool,
+    pub color: [f32; 3],
+    pub crease: bool,
+    pub crease_color: [f32; 3],
+    pub crease_thickness: f32,
+    pub crease_offset: f32,
+}
+
+impl Default for EyelidShapeConfig {
+    fn default() -> Self {
+        Self::from(&EyelidShape::default())
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HighlightConfig {
+    pub shape: HighlightShape,
+    pub offset: [f32; 2],
+    pub radius: f32,
+    pub aspect: f32,
+    pub rotation: f32,
+    pub outline: BezierOutlineConfig,
+    pub color: [f32; 3],
+    pub intensity: f32,
+    pub blur: f32,
+    pub parallax: f32,
+}
+
+impl Default for HighlightConfig {
+    fn default() -> Self {
+        Self::from(&Highlight::default())
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct NodCurveConfig {
     pub anchors: [BezierAnchorConfig; 3],
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
 pub struct NodConfig {
     pub curve: NodCurveConfig,
     pub amount: f32,
@@ -198,6 +289,198 @@ impl NodConfig {
     }
 }
 
+/// Blink timing, see [`BlinkAnimation`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BlinkConfig {
+    /// Eyelid value between blinks.
+    pub rest_value: f32,
+    pub duration_min: f32,
+    pub duration_max: f32,
+    pub close_ratio_min: f32,
+    pub close_ratio_max: f32,
+    pub interval_min: f32,
+    pub interval_max: f32,
+    pub cluster_chance: f32,
+    pub partial_chance: f32,
+    pub partial_depth_min: f32,
+    pub partial_depth_max: f32,
+    pub saccade_blink_threshold: f32,
+    pub saccade_blink_chance: f32,
+    pub lid_follow: f32,
+}
+
+impl Default for BlinkConfig {
+    fn default() -> Self {
+        Self::from(&BlinkAnimation::sample())
+    }
+}
+
+impl From<&BlinkAnimation> for BlinkConfig {
+    fn from(a: &BlinkAnimation) -> Self {
+        Self {
+            rest_value: a.rest_value(),
+            duration_min: a.duration_min,
+            duration_max: a.duration_max,
+            close_ratio_min: a.close_ratio_min,
+            close_ratio_max: a.close_ratio_max,
+            interval_min: a.interval_min,
+            interval_max: a.interval_max,
+            cluster_chance: a.cluster_chance,
+            partial_chance: a.partial_chance,
+            partial_depth_min: a.partial_depth_min,
+            partial_depth_max: a.partial_depth_max,
+            saccade_blink_threshold: a.saccade_blink_threshold,
+            saccade_blink_chance: a.saccade_blink_chance,
+            lid_follow: a.lid_follow,
+        }
+    }
+}
+
+impl BlinkConfig {
+    pub fn apply_to(&self, a: &mut BlinkAnimation) {
+        a.set_rest_value(self.rest_value);
+        a.duration_min = self.duration_min;
+        a.duration_max = self.duration_max;
+        a.close_ratio_min = self.close_ratio_min;
+        a.close_ratio_max = self.close_ratio_max;
+        a.interval_min = self.interval_min;
+        a.interval_max = self.interval_max;
+        a.cluster_chance = self.cluster_chance;
+        a.partial_chance = self.partial_chance;
+        a.partial_depth_min = self.partial_depth_min;
+        a.partial_depth_max = self.partial_depth_max;
+        a.saccade_blink_threshold = self.saccade_blink_threshold;
+        a.saccade_blink_chance = self.saccade_blink_chance;
+        a.lid_follow = self.lid_follow;
+    }
+}
+
+/// Idle microsaccade parameters, see [`MicrosaccadeAnimation`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MicrosaccadeConfig {
+    pub rate: f32,
+    pub amplitude_min: f32,
+    pub amplitude_max: f32,
+    pub duration: f32,
+    pub center_bias: f32,
+    pub drift_rate: f32,
+}
+
+impl Default for MicrosaccadeConfig {
+    fn default() -> Self {
+        Self::from(&MicrosaccadeAnimation::new(0))
+    }
+}
+
+impl From<&MicrosaccadeAnimation> for MicrosaccadeConfig {
+    fn from(a: &MicrosaccadeAnimation) -> Self {
+        Self {
+            rate: a.rate,
+            amplitude_min: a.amplitude_min,
+            amplitude_max: a.amplitude_max,
+            duration: a.duration,
+            center_bias: a.center_bias,
+            drift_rate: a.drift_rate,
+        }
+    }
+}
+
+impl MicrosaccadeConfig {
+    pub fn apply_to(&self, a: &mut MicrosaccadeAnimation) {
+        a.rate = self.rate;
+        a.amplitude_min = self.amplitude_min;
+        a.amplitude_max = self.amplitude_max;
+        a.duration = self.duration;
+        a.center_bias = self.center_bias;
+        a.drift_rate = self.drift_rate;
+    }
+}
+
+/// Pupil size response, see [`PupilDynamics`]. The light and arousal
+/// inputs are runtime state and not saved.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PupilConfig {
+    pub enabled: bool,
+    pub min_scale: f32,
+    pub max_scale: f32,
+    pub arousal_gain: f32,
+    pub constriction_time: f32,
+    pub dilation_time: f32,
+    pub hippus_amplitude: f32,
+    pub hippus_frequency: f32,
+}
+
+impl Default for PupilConfig {
+    fn default() -> Self {
+        Self::from(&PupilDynamics::default())
+    }
+}
+
+impl From<&PupilDynamics> for PupilConfig {
+    fn from(p: &PupilDynamics) -> Self {
+        Self {
+            enabled: p.enabled,
+            min_scale: p.min_scale,
+            max_scale: p.max_scale,
+            arousal_gain: p.arousal_gain,
+            constriction_time: p.constriction_time,
+            dilation_time: p.dilation_time,
+            hippus_amplitude: p.hippus_amplitude,
+            hippus_frequency: p.hippus_frequency,
+        }
+    }
+}
+
+impl PupilConfig {
+    pub fn apply_to(&self, p: &mut PupilDynamics) {
+        p.enabled = self.enabled;
+        p.min_scale = self.min_scale;
+        p.max_scale = self.max_scale;
+        p.arousal_gain = self.arousal_gain;
+        p.constriction_time = self.constriction_time;
+        p.dilation_time = self.dilation_time;
+        p.hippus_amplitude = self.hippus_amplitude;
+        p.hippus_frequency = self.hippus_frequency;
+    }
+}
+
+/// Speech-pause detection of the listening nod, see [`ListeningNod`].
+/// Whether it is enabled stays a runtime choice.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ListeningConfig {
+    pub speech_threshold: f32,
+    pub pause_delay: f32,
+    pub cooldown: f32,
+}
+
+impl Default for ListeningConfig {
+    fn default() -> Self {
+        Self::from(&ListeningNod::default())
+    }
+}
+
+impl From<&ListeningNod> for ListeningConfig {
+    fn from(l: &ListeningNod) -> Self {
+        Self {
+            speech_threshold: l.speech_threshold,
+            pause_delay: l.pause_delay,
+            cooldown: l.cooldown,
+        }
+    }
+}
+
+impl ListeningConfig {
+    pub fn apply_to(&self, l: &mut ListeningNod) {
+        l.speech_threshold = self.speech_threshold;
+        l.pause_delay = self.pause_delay;
+        l.cooldown = self.cooldown;
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct GlobalConfig {
     pub bg_color: [f32; 3],
@@ -210,8 +493,40 @@ pub struct GlobalConfig {
     pub show_highlight: bool,
     pub show_eyebrow: bool,
     pub show_eyelash: bool,
+    #[serde(default = "default_true")]
+    pub show_iris: bool,
+    #[serde(default = "default_true")]
+    pub show_pupil: bool,
     #[serde(default)]
     pub nod: NodConfig,
+    #[serde(default = "default_true")]
+    pub auto_microsaccade: bool,
+    #[serde(default)]
+    pub microsaccade: MicrosaccadeConfig,
+    #[serde(default)]
+    pub blink: BlinkConfig,
+    #[serde(default)]
+    pub listening: ListeningConfig,
+    #[serde(default)]
+    pub pupil: PupilConfig,
+    /// Output a transparent background (premultiplied alpha) for compositing.
+    #[serde(default)]
+    pub transparent_background: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl GlobalConfig {
+    /// Background opacity written to the uniforms.
+    pub fn bg_alpha(&self) -> f32 {
+        if self.transparent_background {
+            0.0
+        } else {
+            1.0
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -261,15 +576,29 @@ impl From<&BezierAnchorConfig> for BezierAnchor {
     }
 }
 
+impl BezierOutlineConfig {
+    /// Subdivide to at least `count` anchors (capped at `MAX_ANCHORS`)
+    /// without changing the shape. See [`BezierOutline::upsampled`].
+    pub fn upsample(&mut self, count: usize) {
+        let count = count.min(BezierOutline::MAX_ANCHORS);
+        if self.anchors.len() < count {
+            *self = Self::from(&BezierOutline::from(&*self).upsampled(count));
+        }
+    }
+
+    /// Redistribute to exactly `count` anchors (within the outline limits).
+    /// See [`BezierOutline::resampled`].
+    pub fn resample(&mut self, count: usize) {
+        if self.anchors.len() != count {
+            *self = Self::from(&BezierOutline::from(&*self).resampled(count));
+        }
+    }
+}
+
 impl From<&BezierOutline> for BezierOutlineConfig {
     fn from(o: &BezierOutline) -> Self {
         Self {
-            anchors: [
-                BezierAnchorConfig::from(&o.anchors[0]),
-                BezierAnchorConfig::from(&o.anchors[1]),
-                BezierAnchorConfig::from(&o.anchors[2]),
-                BezierAnchorConfig::from(&o.anchors[3]),
-            ],
+            anchors: o.anchors.iter().map(BezierAnchorConfig::from).collect(),
         }
     }
 }
@@ -277,12 +606,12 @@ impl From<&BezierOutline> for BezierOutlineConfig {
 impl From<&BezierOutlineConfig> for BezierOutline {
     fn from(c: &BezierOutlineConfig) -> Self {
         Self {
-            anchors: [
-                BezierAnchor::from(&c.anchors[0]),
-                BezierAnchor::from(&c.anchors[1]),
-                BezierAnchor::from(&c.anchors[2]),
-                BezierAnchor::from(&c.anchors[3]),
-            ],
+            anchors: c
+                .anchors
+                .iter()
+                .take(BezierOutline::MAX_ANCHORS)
+                .map(BezierAnchor::from)
+                .collect(),
         }
     }
 }
@@ -323,6 +652,7 @@ impl From<&EyeShape> for EyeShapeConfig {
             open: BezierOutlineConfig::from(&s.open),
             closed: BezierOutlineConfig::from(&s.closed),
             close_arch: s.close_arch,
+            lower_closed: Some(BezierOutlineConfig::from(&s.lower_closed)),
         }
     }
 }
@@ -333,6 +663,7 @@ impl From<&EyeShapeConfig> for EyeShape {
             open: BezierOutline::from(&c.open),
             closed: BezierOutline::from(&c.closed),
             close_arch: c.close_arch,
+            lower_closed: BezierOutline::from(c.lower_closed.as_ref().unwrap_or(&c.closed)),
         }
     }
 }
@@ -384,6 +715,66 @@ impl From<&EyelashShapeConfig> for EyelashShape {
     }
 }
 
+impl From<&EyelidShape> for EyelidShapeConfig {
+    fn from(s: &EyelidShape) -> Self {
+        Self {
+            fill: s.fill,
+            color: s.color,
+            crease: s.crease,
+            crease_color: s.crease_color,
+            crease_thickness: s.crease_thickness,
+            crease_offset: s.crease_offset,
+        }
+    }
+}
+
+impl From<&EyelidShapeConfig> for EyelidShape {
+    fn from(c: &EyelidShapeConfig) -> Self {
+        Self {
+            fill: c.fill,
+            color: c.color,
+            crease: c.crease,
+            crease_color: c.crease_color,
+            crease_thickness: c.crease_thickness,
+            crease_offset: c.crease_offset,
+        }
+    }
+}
+
+impl From<&Highlight> for HighlightConfig {
+    fn from(h: &Highlight) -> Self {
+        Self {
+            shape: h.shape,
+            offset: h.offset,
+            radius: h.radius,
+            aspect: h.aspect,
+            rotation: h.rotation,
+            outline: BezierOutlineConfig::from(&h.outline),
+            color: h.color,
+            intensity: h.intensity,
+            blur: h.blur,
+            parallax: h.parallax,
+        }
+    }
+}
+
+impl From<&HighlightConfig> for Highlight {
+    fn from(c: &HighlightConfig) -> Self {
+        Self {
+            shape: c.shape,
+            offset: c.offset,
+            radius: c.radius,
+            aspect: c.aspect,
+            rotation: c.rotation,
+            outline: BezierOutline::from(&c.outline),
+            color: c.color,
+            intensity: c.intensity,
+            blur: c.blur,
+            parallax: c.parallax,
+        }
+    }
+}
+
 impl From<&IrisShape> for BezierOutlineConfig {
     fn from(s: &IrisShape) -> Self {
         BezierOutlineConfig::from(&s.outline)
@@ -427,7 +818,6 @@ impl SectionLinkConfig {
 // EyeSideConfig: per-eye state extraction
 // ============================================================
 
-#[cfg(feature = "gui")]
 impl From<&EyeSideState> for EyeSideConfig {
     fn from(s: &EyeSideState) -> Self {
         Self {
@@ -435,51 +825,86 @@ impl From<&EyeSideState> for EyeSideConfig {
             iris_color: s.uniforms.iris_color,
             pupil_color: s.uniforms.pupil_color,
             eyelid_close: s.uniforms.eyelid_close,
+            upper_lid_close: s.uniforms.upper_lid_close,
+            lower_lid_raise: s.uniforms.lower_lid_raise,
             iris_radius: s.uniforms.iris_radius,
             iris_follow: s.uniforms.iris_follow,
             iris_offset_y: s.uniforms.iris_offset_y,
             pupil_radius: s.uniforms.pupil_radius,
-            highlight_offset: s.uniforms.highlight_offset,
-            highlight_radius: s.uniforms.highlight_radius,
-            highlight_intensity: s.uniforms.highlight_intensity,
-            highlight_blur: s.uniforms.highlight_blur,
             look_x: s.uniforms.look_x,
             look_y: s.uniforms.look_y,
             eye_shape: EyeShapeConfig::from(&s.eye_shape),
             eyebrow_shape: EyebrowShapeConfig::from(&s.eyebrow_shape),
             eyelash_shape: EyelashShapeConfig::from(&s.eyelash_shape),
+            eyelid_shape: EyelidShapeConfig::from(&s.eyelid_shape),
             iris_shape: BezierOutlineConfig::from(&s.iris_shape),
             pupil_shape: BezierOutlineConfig::from(&s.pupil_shape),
+            highlights: s.highlights.iter().map(HighlightConfig::from).collect(),
+            show_eyebrow: s.is_visible(EyeUniforms::SHOW_EYEBROW),
+            show_eyelash: s.is_visible(EyeUniforms::SHOW_EYELASH),
+            show_highlight: s.is_visible(EyeUniforms::SHOW_HIGHLIGHT),
+            show_iris: s.is_visible(EyeUniforms::SHOW_IRIS),
+            show_pupil: s.is_visible(EyeUniforms::SHOW_PUPIL),
+            highlight_offset: None,
+            highlight_radius: None,
+            highlight_intensity: None,
+            highlight_blur: None,
         }
     }
 }
 
-#[cfg(feature = "gui")]
 impl EyeSideConfig {
     pub fn apply_to(&self, s: &mut EyeSideState) {
         s.uniforms.sclera_color = self.sclera_color;
         s.uniforms.iris_color = self.iris_color;
         s.uniforms.pupil_color = self.pupil_color;
         s.uniforms.eyelid_close = self.eyelid_close;
+        s.uniforms.upper_lid_close = self.upper_lid_close;
+        s.uniforms.lower_lid_raise = self.lower_lid_raise;
         s.uniforms.iris_radius = self.iris_radius;
         s.uniforms.iris_follow = self.iris_follow;
         s.uniforms.iris_offset_y = self.iris_offset_y;
         s.uniforms.pupil_radius = self.pupil_radius;
-        s.uniforms.highlight_offset = self.highlight_offset;
-        s.uniforms.highlight_radius = self.highlight_radius;
-        s.uniforms.highlight_intensity = self.highlight_intensity;
-        s.uniforms.highlight_blur = self.highlight_blur;
         s.uniforms.look_x = self.look_x;
         s.uniforms.look_y = self.look_y;
         s.eye_shape = EyeShape::from(&self.eye_shape);
         s.eyebrow_shape = EyebrowShape::from(&self.eyebrow_shape);
         s.eyelash_shape = EyelashShape::from(&self.eyelash_shape);
+        s.eyelid_shape = EyelidShape::from(&self.eyelid_shape);
         s.iris_shape = IrisShape {
             outline: BezierOutline::from(&self.iris_shape),
         };
         s.pupil_shape = PupilShape {
             outline: BezierOutline::from(&self.pupil_shape),
         };
+        s.highlights = self.highlights.iter().map(Highlight::from).collect();
+        s.set_visible(EyeUniforms::SHOW_EYEBROW, self.show_eyebrow);
+        s.set_visible(EyeUniforms::SHOW_EYELASH, self.show_eyelash);
+        s.set_visible(EyeUniforms::SHOW_HIGHLIGHT, self.show_highlight);
+        s.set_visible(EyeUniforms::SHOW_IRIS, self.show_iris);
+        s.set_visible(EyeUniforms::SHOW_PUPIL, self.show_pupil);
+    }
+
+    /// Fold the single circular highlight of an older file into the first
+    /// highlight, keeping its other settings. Loading through
+    /// [`EyeConfig::from_json`], a legacy timeline or a protocol `set_eye`
+    /// does this already.
+    pub fn migrate_legacy_highlight(&mut self) {
+        let offset = self.highlight_offset.take();
+        let radius = self.highlight_radius.take();
+        let intensity = self.highlight_intensity.take();
+        let blur = self.highlight_blur.take();
+        if offset.is_none() && radius.is_none() && intensity.is_none() && blur.is_none() {
+            return;
+        }
+        if self.highlights.is_empty() {
+            self.highlights.push(HighlightConfig::default());
+        }
+        let h = &mut self.highlights[0];
+        h.offset = offset.unwrap_or(h.offset);
+        h.radius = radius.unwrap_or(h.radius);
+        h.intensity = intensity.unwrap_or(h.intensity);
+        h.blur = blur.unwrap_or(h.blur);
     }
 }
 
@@ -494,13 +919,52 @@ impl EyeConfig {
         serde_json::to_string_pretty(self)
     }
 
+    /// Parse a config; the single highlight of older files is migrated on load.
     pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
-        serde_json::from_str(json)
+        let mut config: Self = serde_json::from_str(json)?;
+        config.left.migrate_legacy_highlight();
+        config.right.migrate_legacy_highlight();
+        Ok(config)
+    }
+
+    /// Apply shapes, globals and toggles to a controller (GUI link state is ignored).
+    pub fn apply_to_controller(&self, c: &mut EyeController) {
+        let aspect = c.left.uniforms.aspect_ratio;
+        let time = c.left.uniforms.time;
+
+        self.left.apply_to(&mut c.left);
+        self.right.apply_to(&mut c.right);
+
+        for side in [&mut c.left, &mut c.right] {
+            side.uniforms.aspect_ratio = aspect;
+            side.uniforms.time = time;
+            side.uniforms.bg_color = self.global.bg_color;
+            side.uniforms.eye_separation = self.global.eye_separation;
+            side.uniforms.max_angle = self.global.max_angle;
+            side.uniforms.eye_angle = self.global.eye_angle;
+            side.uniforms.bg_alpha = self.global.bg_alpha();
+        }
+
+        c.auto_blink = self.global.auto_blink;
+        c.follow_mouse = self.global.follow_mouse;
+        c.show_highlight = self.global.show_highlight;
+        c.show_eyebrow = self.global.show_eyebrow;
+        c.show_eyelash = self.global.show_eyelash;
+        c.show_iris = self.global.show_iris;
+        c.show_pupil = self.global.show_pupil;
+        c.focus_distance = self.global.focus_distance;
+        self.global.nod.apply_to(&mut c.nod_animation);
+        c.auto_microsaccade = self.global.auto_microsaccade;
+        self.global.microsaccade.apply_to(&mut c.microsaccade_animation);
+        self.global.blink.apply_to(&mut c.blink_animation);
+        self.global.listening.apply_to(&mut c.listening_nod);
+        self.global.pupil.apply_to(&mut c.pupil_dynamics);
     }
 }
 
 #[cfg(feature = "gui")]
 impl EyeConfig {
+    #[allow(clippy::too_many_arguments)]
     pub fn from_state(
         left: &EyeSideState,
         right: &EyeSideState,
@@ -514,8 +978,15 @@ impl EyeConfig {
         show_highlight: bool,
         show_eyebrow: bool,
         show_eyelash: bool,
+        show_iris: bool,
+        show_pupil: bool,
         focus_distance: f32,
         nod_animation: &NodAnimation,
+        auto_microsaccade: bool,
+        microsaccade_animation: &MicrosaccadeAnimation,
+        blink_animation: &BlinkAnimation,
+        listening_nod: &ListeningNod,
+        pupil_dynamics: &PupilDynamics,
     ) -> Self {
         Self {
             version: Self::CURRENT_VERSION,
@@ -532,7 +1003,15 @@ impl EyeConfig {
                 show_highlight,
                 show_eyebrow,
                 show_eyelash,
+                show_iris,
+                show_pupil,
                 nod: NodConfig::from(nod_animation),
+                auto_microsaccade,
+                microsaccade: MicrosaccadeConfig::from(microsaccade_animation),
+                blink: BlinkConfig::from(blink_animation),
+                listening: ListeningConfig::from(listening_nod),
+                pupil: PupilConfig::from(pupil_dynamics),
+                transparent_background: left.uniforms.bg_alpha < 0.5,
             },
             links: LinkConfig {
                 shape: SectionLinkConfig::from(link_shape),
@@ -544,6 +1023,7 @@ impl EyeConfig {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn apply_to_state(
         &self,
         left: &mut EyeSideState,
@@ -558,8 +1038,15 @@ impl EyeConfig {
         show_highlight: &mut bool,
         show_eyebrow: &mut bool,
         show_eyelash: &mut bool,
+        show_iris: &mut bool,
+        show_pupil: &mut bool,
         focus_distance: &mut f32,
         nod_animation: &mut NodAnimation,
+        auto_microsaccade: &mut bool,
+        microsaccade_animation: &mut MicrosaccadeAnimation,
+        blink_animation: &mut BlinkAnimation,
+        listening_nod: &mut ListeningNod,
+        pupil_dynamics: &mut PupilDynamics,
     ) {
         // Preserve runtime-only fields
         let aspect = left.uniforms.aspect_ratio;
@@ -579,21 +1066,34 @@ impl EyeConfig {
         left.uniforms.eye_separation = self.global.eye_separation;
         left.uniforms.max_angle = self.global.max_angle;
         left.uniforms.eye_angle = self.global.eye_angle;
+        left.uniforms.bg_alpha = self.global.bg_alpha();
         right.uniforms.bg_color = self.global.bg_color;
         right.uniforms.eye_separation = self.global.eye_separation;
         right.uniforms.max_angle = self.global.max_angle;
         right.uniforms.eye_angle = self.global.eye_angle;
+        right.uniforms.bg_alpha = self.global.bg_alpha();
 
         *auto_blink = self.global.auto_blink;
         *follow_mouse = self.global.follow_mouse;
         *show_highlight = self.global.show_highlight;
         *show_eyebrow = self.global.show_eyebrow;
         *show_eyelash = self.global.show_eyelash;
+        *show_iris = self.global.show_iris;
+        *show_pupil = self.global.show_pupil;
         *focus_distance = self.global.focus_distance;
 
         // Nod
         self.global.nod.apply_to(nod_animation);
 
+        // Microsaccades
+        *auto_microsaccade = self.global.auto_microsaccade;
+        self.global.microsaccade.apply_to(microsaccade_animation);
+
+        // Blink timing and listening thresholds
+        self.global.blink.apply_to(blink_animation);
+        self.global.listening.apply_to(listening_nod);
+        self.global.pupil.apply_to(pupil_dynamics);
+
         // Links
         *link_shape = self.links.shape.to_section_link();
         *link_iris = self.links.iris.to_section_link();
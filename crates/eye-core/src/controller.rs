@@ -0,0 +1,813 @@
+use serde::{Deserialize, Serialize};
+
+use crate::animation::{BlinkAnimation, WinkEye};
+use crate::expression::ExpressionMixer;
+use crate::gaze::GazeFilter;
+use crate::head::{HeadEyeCoordination, HeadEyePose};
+use crate::listening::ListeningNod;
+use crate::microsaccade::MicrosaccadeAnimation;
+use crate::nod::NodAnimation;
+use crate::outline::{
+    EyeShape, EyebrowShape, EyelashShape, EyelidShape, Highlight, IrisShape, PupilShape,
+};
+use crate::pupil::PupilDynamics;
+use crate::renderer::{EyePairUniforms, EyeUniforms};
+use crate::timeline::{
+    AnimationChannel, Timeline, TimelineAction, TimelineFrame, TimelinePlayer, TimelinePose,
+    TrackProperty,
+};
+
+/// Squash & stretch gain applied to eyelid velocity (units/s).
+const SQUASH_STRENGTH: f32 = 0.08;
+/// Maximum absolute squash & stretch factor.
+const MAX_SQUASH: f32 = 0.045;
+/// Fixed frame step used to estimate eyelid velocity.
+const VELOCITY_DT: f32 = 1.0 / 60.0;
+
+// ============================================================
+// Per-eye state
+// ============================================================
+
+/// All parameters for one eye.
+#[derive(Clone, Debug)]
+pub struct EyeSideState {
+    pub uniforms: EyeUniforms,
+    pub eye_shape: EyeShape,
+    pub eyebrow_shape: EyebrowShape,
+    pub eyelash_shape: EyelashShape,
+    pub eyelid_shape: EyelidShape,
+    pub iris_shape: IrisShape,
+    pub pupil_shape: PupilShape,
+    /// Drawn in order; only the first `EyeUniforms::MAX_HIGHLIGHTS` are uploaded.
+    pub highlights: Vec<Highlight>,
+    /// Features drawn on this eye (`EyeUniforms::SHOW_FEATURES` bits). The
+    /// controller's `show_*` toggles hide them on both eyes on top.
+    pub visibility: u32,
+}
+
+impl Default for EyeSideState {
+    fn default() -> Self {
+        Self {
+            uniforms: EyeUniforms::default(),
+            eye_shape: EyeShape::default(),
+            eyebrow_shape: EyebrowShape::default(),
+            eyelash_shape: EyelashShape::default(),
+            eyelid_shape: EyelidShape::default(),
+            iris_shape: IrisShape::default(),
+            pupil_shape: PupilShape::default(),
+            highlights: vec![Highlight::default()],
+            visibility: EyeUniforms::SHOW_FEATURES,
+        }
+    }
+}
+
+impl EyeSideState {
+    /// Whether every feature in `features` (`EyeUniforms::SHOW_*` bits) is
+    /// enabled for this eye.
+    pub fn is_visible(&self, features: u32) -> bool {
+        self.visibility & features == features
+    }
+
+    /// Show or hide `features` (`EyeUniforms::SHOW_*` bits) on this eye.
+    pub fn set_visible(&mut self, features: u32, visible: bool) {
+        if visible {
+            self.visibility |= features;
+        } else {
+            self.visibility &= !features;
+        }
+    }
+
+    /// Copy the shape descriptions (outlines, eyebrow, eyelash, lids,
+    /// highlights) into the uniforms.
+    pub fn sync_shapes(&mut self) {
+        self.uniforms.set_eye_outline(
+            &self.eye_shape.open,
+            &self.eye_shape.closed,
+            &self.eye_shape.lower_closed,
+        );
+
+        self.uniforms.eyebrow_color = self.eyebrow_shape.color;
+        self.uniforms.eyebrow_base_y = self.eyebrow_shape.base_y;
+        self.uniforms.eyebrow_follow = self.eyebrow_shape.follow;
+        self.uniforms.eyebrow_outline = self.eyebrow_shape.outline.to_uniform_array();
+
+        self.uniforms.eyelash_color = self.eyelash_shape.color;
+        self.uniforms.eyelash_thickness = self.eyelash_shape.thickness;
+
+        let lid = &self.eyelid_shape;
+        self.uniforms.lid_color = lid.color;
+        self.uniforms.crease_color = lid.crease_color;
+        self.uniforms.crease_thickness = lid.crease_thickness;
+        self.uniforms.crease_offset = lid.crease_offset;
+        self.uniforms.set_visible(EyeUniforms::SHOW_LID_FILL, lid.fill);
+        self.uniforms.set_visible(EyeUniforms::SHOW_CREASE, lid.crease);
+
+        self.uniforms.set_iris_outline(&self.iris_shape.outline);
+        self.uniforms.set_pupil_outline(&self.pupil_shape.outline);
+        self.uniforms.set_highlights(&self.highlights);
+    }
+}
+
+// ============================================================
+// Per-frame inputs
+// ============================================================
+
+/// Gaze parameters supplied by an external source (e.g. a WebSocket client).
+#[derive(Clone, Debug)]
+pub struct RemoteGaze {
+    pub look_x: f32,
+    pub look_y: f32,
+    pub focus_distance: f32,
+    /// Overrides the eyelid state when set.
+    pub eyelid_close: Option<f32>,
+}
+
+/// Host-provided inputs for a single frame.
+#[derive(Clone, Debug)]
+pub struct EyeInputs {
+    /// Viewport width / height.
+    pub aspect_ratio: f32,
+    /// Viewport height in logical pixels (drives the convergence model).
+    pub logical_height: f32,
+    /// Pointer position mapped to look coordinates, see [`pointer_to_look`].
+    pub mouse_look: Option<[f32; 2]>,
+    /// External gaze. Takes priority over `mouse_look` when present.
+    pub remote_gaze: Option<RemoteGaze>,
+    /// Smoothed microphone RMS level for the listening nod.
+    pub audio_rms: f32,
+}
+
+impl Default for EyeInputs {
+    fn default() -> Self {
+        Self {
+            aspect_ratio: 16.0 / 9.0,
+            logical_height: 480.0,
+            mouse_look: None,
+            remote_gaze: None,
+            audio_rms: 0.0,
+        }
+    }
+}
+
+/// Map a pointer position (physical pixels) to `[look_x, look_y]` in [-1, 1].
+pub fn pointer_to_look(x: f64, y: f64, width: f64, height: f64) -> [f32; 2] {
+    let cx = width / 2.0;
+    let cy = height / 2.0;
+    [
+        ((x - cx) / cx).clamp(-1.0, 1.0) as f32,
+        -((y - cy) / cy).clamp(-1.0, 1.0) as f32,
+    ]
+}
+
+// ============================================================
+// Events & snapshots
+// ============================================================
+
+/// Notable state change raised during [`EyeController::update`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum EyeEvent {
+    BlinkStarted,
+    BlinkFinished,
+    NodStarted,
+    NodFinished,
+    /// The listening nod detected a pause in speech.
+    SpeechPauseDetected,
+    TimelineStarted,
+    TimelineMarkerReached { index: usize, label: String },
+    /// Playback ended or was stopped.
+    TimelineFinished,
+}
+
+/// Point-in-time view of the animated state.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EyeSnapshot {
+    pub time: f32,
+    pub look_x: f32,
+    pub look_y: f32,
+    pub head_yaw: f32,
+    pub head_pitch: f32,
+    pub eyelid_close: f32,
+    pub blinking: bool,
+    pub nodding: bool,
+    pub timeline_playing: bool,
+    /// Seconds into the timeline.
+    pub timeline_time: f32,
+    pub remote_gaze_active: bool,
+}
+
+// ============================================================
+// Controller
+// ============================================================
+
+/// Owns both eyes and every animation driving them.
+///
+/// Call [`EyeController::update`] once per frame to run the full pipeline
+/// (timeline → blink → gaze filter → head/eye split → microsaccade → convergence → nod → pupil) and obtain
+/// the uniforms ready for [`crate::EyeRenderer`].
+pub struct EyeController {
+    pub left: EyeSideState,
+    pub right: EyeSideState,
+
+    pub blink_animation: BlinkAnimation,
+    pub nod_animation: NodAnimation,
+    pub microsaccade_animation: MicrosaccadeAnimation,
+    pub listening_nod: ListeningNod,
+    /// Animates the pupil size from light level and arousal.
+    pub pupil_dynamics: PupilDynamics,
+    pub timeline_player: TimelinePlayer,
+    /// Smooths mouse-follow and remote gaze targets.
+    pub gaze_filter: GazeFilter,
+    /// Splits the filtered gaze between head orientation and eye rotation.
+    pub head_eye: HeadEyeCoordination,
+    /// Expression presets layered over both eyes at output time.
+    pub expressions: ExpressionMixer,
+
+    pub auto_blink: bool,
+    /// Schedule idle microsaccades on their own.
+    pub auto_microsaccade: bool,
+    pub follow_mouse: bool,
+    pub show_highlight: bool,
+    pub show_eyebrow: bool,
+    pub show_eyelash: bool,
+    pub show_iris: bool,
+    pub show_pupil: bool,
+    /// Freezes blink, microsaccade, nod and pupil updates.
+    pub pause_animation: bool,
+    pub focus_distance: f32,
+    /// Latched external gaze (e.g. from protocol `gaze` commands).
+    /// Used when a frame's [`EyeInputs::remote_gaze`] is `None`.
+    pub remote_gaze: Option<RemoteGaze>,
+    /// Latched `[head_yaw, head_pitch]` target (e.g. from protocol `head` commands).
+    /// Overrides the head's own follow; the eyes keep the gaze by counter-rotating.
+    pub head_target: Option<[f32; 2]>,
+
+    prev_timeline_eyelid: f32,
+    /// Pose when playback started; layered tracks blend over it, and the
+    /// animated properties return to it once playback ends.
+    timeline_base: Option<TimelinePose>,
+    remote_gaze_active: bool,
+
+    /// Events raised by the last `update`.
+    events: Vec<EyeEvent>,
+    was_blinking: bool,
+    was_nodding: bool,
+    was_timeline_playing: bool,
+}
+
+impl Default for EyeController {
+    fn default() -> Self {
+        Self {
+            left: EyeSideState::default(),
+            right: EyeSideState::default(),
+            blink_animation: BlinkAnimation::sample(),
+            nod_animation: NodAnimation::default(),
+            microsaccade_animation: MicrosaccadeAnimation::new(7),
+            listening_nod: ListeningNod::default(),
+            pupil_dynamics: PupilDynamics::default(),
+            timeline_player: TimelinePlayer::new(),
+            gaze_filter: GazeFilter::default(),
+            head_eye: HeadEyeCoordination::default(),
+            expressions: ExpressionMixer::default(),
+            auto_blink: true,
+            auto_microsaccade: true,
+            follow_mouse: true,
+            show_highlight: true,
+            show_eyebrow: true,
+            show_eyelash: true,
+            show_iris: true,
+            show_pupil: true,
+            pause_animation: false,
+            focus_distance: 1.5,
+            remote_gaze: None,
+            head_target: None,
+            prev_timeline_eyelid: 0.0,
+            timeline_base: None,
+            remote_gaze_active: false,
+            events: Vec::new(),
+            was_blinking: false,
+            was_nodding: false,
+            was_timeline_playing: false,
+        }
+    }
+}
+
+impl EyeController {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Trigger an immediate blink.
+    pub fn trigger_blink(&mut self, time: f32) {
+        self.blink_animation.trigger(time);
+    }
+
+    /// Close only `eye` briefly.
+    pub fn trigger_wink(&mut self, time: f32, eye: WinkEye) {
+        self.blink_animation.wink(time, eye);
+    }
+
+    /// Start a nod from the current eyelid state.
+    pub fn trigger_nod(&mut self, time: f32) {
+        let current_eyelid = self.left.uniforms.eyelid_close;
+        self.nod_animation.trigger(time, current_eyelid);
+    }
+
+    /// Move expression `name` to `weight` over `duration` seconds.
+    pub fn set_expression(
+        &mut self,
+        name: &str,
+        weight: f32,
+        duration: f32,
+        time: f32,
+    ) -> Result<(), String> {
+        self.expressions.set(name, weight, duration, time)
+    }
+
+    /// Start a microsaccade biased toward the current gaze center.
+    pub fn trigger_microsaccade(&mut self, time: f32) {
+        let look_x = self.left.uniforms.look_x;
+        let look_y = self.left.uniforms.look_y;
+        self.microsaccade_animation.trigger(time, look_x, look_y);
+    }
+
+    /// Stop playback and replace the timeline. The eyes go back to their
+    /// pose from before playback.
+    pub fn load_timeline(&mut self, timeline: Timeline) {
+        self.timeline_player.stop();
+        self.restore_timeline_base();
+        let player = &mut self.timeline_player;
+        player.timeline = timeline;
+        player.timeline.sort();
+        player.selected_track = None;
+    }
+
+    /// Whether the last `update` applied remote gaze input.
+    pub fn remote_gaze_active(&self) -> bool {
+        self.remote_gaze_active
+    }
+
+    /// Whether the host should keep requesting frames.
+    pub fn is_animating(&self) -> bool {
+        !self.gaze_filter.is_settled()
+            || !self.head_eye.is_settled()
+            || self.expressions.is_transitioning()
+            || !self.pause_animation
+                && (self.auto_blink
+                    || self.auto_microsaccade
+                    || self.remote_gaze_active
+                    || self.nod_animation.is_active()
+                    || self.listening_nod.enabled
+                    || self.pupil_dynamics.enabled
+                    || self.timeline_player.is_playing())
+    }
+
+    /// Events raised by the last [`EyeController::update`], in order.
+    pub fn events(&self) -> &[EyeEvent] {
+        &self.events
+    }
+
+    /// Current look/eyelid values and animation flags.
+    pub fn snapshot(&self) -> EyeSnapshot {
+        let u = &self.left.uniforms;
+        EyeSnapshot {
+            time: u.time,
+            look_x: u.look_x,
+            look_y: u.look_y,
+            head_yaw: u.head_yaw,
+            head_pitch: u.head_pitch,
+            eyelid_close: u.eyelid_close,
+            blinking: self.was_blinking,
+            nodding: self.was_nodding,
+            timeline_playing: self.timeline_player.is_playing(),
+            timeline_time: self.timeline_player.current_time(),
+            remote_gaze_active: self.remote_gaze_active,
+        }
+    }
+
+    /// Run the per-frame animation pipeline and return the uniforms to render.
+    pub fn update(&mut self, time: f32, inputs: &EyeInputs) -> EyePairUniforms {
+        self.events.clear();
+        let timeline_active = self.timeline_player.is_active();
+        if !timeline_active || !self.was_timeline_playing {
+            // Playback ended after the last update (and may have restarted)
+            self.restore_timeline_base();
+        }
+        self.set_runtime_fields(inputs.aspect_ratio, time);
+        self.expressions.update(time);
+
+        // --- Timeline playback ---
+        if timeline_active && !self.was_timeline_playing {
+            self.events.push(EyeEvent::TimelineStarted);
+            self.timeline_base = Some(TimelinePose::from_state(
+                &self.left,
+                &self.right,
+                self.focus_distance,
+            ));
+        }
+        let frame = self.timeline_player.evaluate(time);
+        if let Some(frame) = &frame {
+            self.fire_timeline_events(&frame.fired_events, time);
+        }
+
+        if timeline_active && self.timeline_player.timeline.is_layered() {
+            // Realtime animation fills the channels the layers leave open,
+            // then the layers blend over it
+            if let (Some(frame), Some(base)) = (&frame, &self.timeline_base) {
+                let mut pose = TimelinePose::from_state(&self.left, &self.right, self.focus_distance);
+                frame.restore(&mut pose, base);
+                pose.apply_to_state(&mut self.left, &mut self.right, &mut self.focus_distance);
+                self.set_runtime_fields(inputs.aspect_ratio, time);
+            }
+            self.update_realtime(time, inputs);
+            if let Some(frame) = &frame {
+                self.apply_timeline_frame(frame, time, inputs.aspect_ratio);
+            }
+            self.finish_timeline_frame(time);
+        } else if timeline_active {
+            // Unlayered timelines take priority over all other animations
+            if let Some(frame) = &frame {
+                self.apply_timeline_frame(frame, time, inputs.aspect_ratio);
+            }
+            // Gestures fired by the event track play over the timeline
+            if !self.pause_animation {
+                self.apply_microsaccade(time);
+                self.apply_nod(time);
+            }
+            self.finish_timeline_frame(time);
+        } else {
+            self.update_realtime(time, inputs);
+        }
+
+        if !self.pause_animation {
+            self.pupil_dynamics.update(time, self.left.uniforms.bg_color);
+        }
+
+        self.left.sync_shapes();
+        self.right.sync_shapes();
+        self.sync_globals();
+        self.detect_transitions(time);
+
+        self.uniforms()
+    }
+
+    /// Build the paired uniforms with expressions and visibility overrides applied.
+    pub fn uniforms(&self) -> EyePairUniforms {
+        let mut left = self.left.uniforms;
+        let mut right = self.right.uniforms;
+
+        let rest_eyelid = self.blink_animation.rest_value();
+        self.expressions.apply(&self.left, false, rest_eyelid, &mut left);
+        self.expressions.apply(&self.right, true, rest_eyelid, &mut right);
+
+        // Pupil dynamics scale whatever size the expressions settled on
+        let pupil_scale = self.pupil_dynamics.scale();
+        if (pupil_scale - 1.0).abs() > 1e-6 {
+            for u in [&mut left, &mut right] {
+                u.pupil_radius *= pupil_scale;
+                for v in u.pupil_outline.iter_mut().flatten() {
+                    *v *= pupil_scale;
+                }
+            }
+        }
+
+        // Each eye's own toggles, then the global ones on top
+        let globals = [
+            (self.show_highlight, EyeUniforms::SHOW_HIGHLIGHT),
+            (self.show_eyebrow, EyeUniforms::SHOW_EYEBROW),
+            (self.show_eyelash, EyeUniforms::SHOW_EYELASH),
+            (self.show_iris, EyeUniforms::SHOW_IRIS),
+            (self.show_pupil, EyeUniforms::SHOW_PUPIL),
+        ];
+        for (u, side) in [(&mut left, &self.left), (&mut right, &self.right)] {
+            u.set_visible(EyeUniforms::SHOW_FEATURES & !side.visibility, false);
+            for (shown, feature) in globals {
+                if !shown {
+                    u.set_visible(feature, false);
+                }
+            }
+        }
+
+        EyePairUniforms { left, right }
+    }
+
+    fn set_runtime_fields(&mut self, aspect_ratio: f32, time: f32) {
+        self.left.uniforms.aspect_ratio = aspect_ratio;
+        self.left.uniforms.time = time;
+        self.right.uniforms.aspect_ratio = aspect_ratio;
+        self.right.uniforms.time = time;
+    }
+
+    /// Start the gestures of the event track entries the playhead just reached.
+    fn fire_timeline_events(&mut self, fired: &[usize], time: f32) {
+        for &index in fired {
+            match self.timeline_player.timeline.events[index].action.clone() {
+                TimelineAction::Marker { label } => {
+                    self.events.push(EyeEvent::TimelineMarkerReached { index, label });
+                }
+                TimelineAction::Blink {
+                    close_duration,
+                    hold_duration,
+                    open_duration,
+                } => {
+                    self.blink_animation
+                        .trigger_with(time, close_duration, hold_duration, open_duration);
+                }
+                TimelineAction::Wink {
+                    eye,
+                    close_duration,
+                    hold_duration,
+                    open_duration,
+                } => {
+                    self.blink_animation.wink_with(
+                        time,
+                        eye,
+                        close_duration,
+                        hold_duration,
+                        open_duration,
+                    );
+                }
+                TimelineAction::Nod { overrides } => {
+                    let current_eyelid = self.left.uniforms.eyelid_close;
+                    self.nod_animation.trigger_with(time, current_eyelid, overrides);
+                }
+                TimelineAction::Microsaccade => self.trigger_microsaccade(time),
+            }
+        }
+    }
+
+    /// Put the properties the timeline animates back to their values from
+    /// when playback started, so played values don't stick (or get saved).
+    fn restore_timeline_base(&mut self) {
+        let Some(base) = self.timeline_base.take() else {
+            return;
+        };
+        let mut pose = TimelinePose::from_state(&self.left, &self.right, self.focus_distance);
+        self.timeline_player.sample(0.0).restore(&mut pose, &base);
+        pose.apply_to_state(&mut self.left, &mut self.right, &mut self.focus_distance);
+    }
+
+    /// Whether the playing timeline replaces realtime animation on `channel`.
+    fn timeline_drives(&self, channel: AnimationChannel) -> bool {
+        self.timeline_player.is_active() && self.timeline_player.timeline.drives(channel)
+    }
+
+    fn apply_timeline_frame(&mut self, frame: &TimelineFrame, time: f32, aspect_ratio: f32) {
+        // Un-keyed eyelids rest open; the blink overlay below is not a base value
+        if self.timeline_drives(AnimationChannel::Eyelids) {
+            let rest = self.blink_animation.rest_value();
+            if !frame.animates(TrackProperty::EyelidClose, false) {
+                self.left.uniforms.eyelid_close = rest;
+            }
+            if !frame.animates(TrackProperty::EyelidClose, true) {
+                self.right.uniforms.eyelid_close = rest;
+            }
+        }
+
+        // Tracks affect only the properties they animate
+        let mut pose = TimelinePose::from_state(&self.left, &self.right, self.focus_distance);
+        frame.apply_to(&mut pose);
+        pose.apply_to_state(&mut self.left, &mut self.right, &mut self.focus_distance);
+        // Restore runtime-only fields
+        self.set_runtime_fields(aspect_ratio, time);
+
+        // Overlay blink on top of timeline eyelid_close
+        if self.blink_animation.is_blinking(time) {
+            let [left, right] = self.blink_animation.peek_value(time);
+            self.left.uniforms.eyelid_close = self.left.uniforms.eyelid_close.max(left);
+            self.right.uniforms.eyelid_close = self.right.uniforms.eyelid_close.max(right);
+        }
+    }
+
+    /// Squash and gaze tracking for the channels the timeline drives.
+    fn finish_timeline_frame(&mut self, time: f32) {
+        if self.timeline_drives(AnimationChannel::Eyelids) {
+            // Squash & stretch driven by eyelid velocity during timeline playback
+            let eyelid_now = self.left.uniforms.eyelid_close;
+            let velocity = (eyelid_now - self.prev_timeline_eyelid) / VELOCITY_DT;
+            self.set_squash(squash_from_velocity(velocity));
+            self.prev_timeline_eyelid = eyelid_now;
+        }
+        if self.timeline_drives(AnimationChannel::Gaze) {
+            self.remote_gaze_active = false;
+            self.reset_gaze_tracking(time);
+        }
+    }
+
+    /// Realtime animation, skipping the channels a playing timeline drives.
+    fn update_realtime(&mut self, time: f32, inputs: &EyeInputs) {
+        let drive_eyelids = !self.timeline_drives(AnimationChannel::Eyelids);
+        let drive_gaze = !self.timeline_drives(AnimationChannel::Gaze);
+
+        // Auto-blink: applies to both eyes (skip when paused). A timeline
+        // driving the eyelids derives squash from its own eyelid motion.
+        if drive_eyelids {
+            if self.auto_blink && !self.pause_animation {
+                // Rest lid follows last frame's vertical gaze
+                self.blink_animation.set_look_y(self.left.uniforms.look_y);
+                let eyelid_now = self.blink_animation.evaluate(time);
+                let eyelid_prev = self.blink_animation.peek_value(time - VELOCITY_DT);
+                // Per eye, so winks only squash the winking eye
+                for (i, side) in [&mut self.left, &mut self.right].into_iter().enumerate() {
+                    let velocity = (eyelid_now[i] - eyelid_prev[i]) / VELOCITY_DT;
+                    side.uniforms.squash_stretch = squash_from_velocity(velocity);
+                    side.uniforms.eyelid_close = eyelid_now[i];
+                }
+            } else {
+                self.set_squash(0.0);
+            }
+        }
+
+        // Gaze input: remote gaze takes priority over mouse follow
+        let remote_gaze = inputs.remote_gaze.clone().or_else(|| self.remote_gaze.clone());
+        let remote_gaze = remote_gaze.filter(|_| drive_gaze);
+        self.remote_gaze_active = remote_gaze.is_some();
+        let mut gaze_target = None;
+        if let Some(gaze) = &remote_gaze {
+            gaze_target = Some([gaze.look_x, gaze.look_y]);
+            self.focus_distance = gaze.focus_distance;
+            if let Some(ec) = gaze.eyelid_close.filter(|_| drive_eyelids) {
+                self.set_eyelid(ec);
+            }
+        } else if self.follow_mouse {
+            gaze_target = inputs.mouse_look;
+        }
+        // A timeline driving the gaze is tracked after it is applied
+        if drive_gaze {
+            if gaze_target.is_some() || self.head_target.is_some() {
+                let gaze = match gaze_target {
+                    Some(target) => {
+                        let max_angle = self.left.uniforms.max_angle;
+                        let gaze = self.gaze_filter.update(time, target, max_angle);
+                        // Large saccades may set off a blink
+                        if let Some(amplitude) = self.gaze_filter.saccade_started(time) {
+                            if drive_eyelids && self.auto_blink && !self.pause_animation {
+                                self.blink_animation.notify_gaze_shift(time, amplitude);
+                            }
+                        }
+                        gaze
+                    }
+                    None => {
+                        // Only the head is driven: hold the gaze so the eyes counter-rotate
+                        let gaze = self.head_eye.gaze();
+                        self.gaze_filter.reset(time, gaze);
+                        gaze
+                    }
+                };
+                let pose = self.head_eye.update(time, gaze, self.head_target);
+                self.set_pose(pose);
+            } else {
+                // Nothing drives the gaze: track the displayed pose (GUI edits)
+                self.reset_gaze_tracking(time);
+            }
+        }
+
+        if !self.pause_animation {
+            // Idle microsaccades belong to the gaze channel
+            if self.auto_microsaccade && drive_gaze {
+                let u = &self.left.uniforms;
+                let (look_x, look_y) = (u.look_x, u.look_y);
+                let blinking = self.blink_animation.is_blinking(time);
+                self.microsaccade_animation.update(time, look_x, look_y, blinking);
+            }
+            self.apply_microsaccade(time);
+        }
+
+        // Focus distance → convergence offset (global)
+        // Physical eye separation on screen scales with window size.
+        // Model: each eye rotates inward to converge at the viewer.
+        let half_ipd_lp = self.left.uniforms.eye_separation * inputs.logical_height * 0.25;
+        let viewer_dist_lp = self.focus_distance * 800.0;
+        let conv_angle = (half_ipd_lp / viewer_dist_lp).atan();
+        let iris_follow = self.left.uniforms.iris_follow;
+        let max_angle = self.left.uniforms.max_angle;
+        let convergence = if max_angle > 0.001 {
+            (conv_angle * iris_follow / max_angle).clamp(0.0, iris_follow * 0.8)
+        } else {
+            0.0
+        };
+        self.left.uniforms.convergence = convergence;
+        self.right.uniforms.convergence = convergence;
+
+        if self.pause_animation {
+            return;
+        }
+
+        // Listening nod: trigger nod on detected speech pauses
+        if drive_gaze
+            && self.listening_nod.enabled
+            && self.listening_nod.update(time, inputs.audio_rms)
+        {
+            self.events.push(EyeEvent::SpeechPauseDetected);
+            if !self.nod_animation.is_active() {
+                self.trigger_nod(time);
+            }
+        }
+
+        self.apply_nod(time);
+    }
+
+    /// Microsaccade: iris-only offset (both eyes same direction).
+    fn apply_microsaccade(&mut self, time: f32) {
+        let (ms_x, ms_y) = self.microsaccade_animation.evaluate(time);
+        for side in [&mut self.left, &mut self.right] {
+            side.uniforms.microsaccade_x = ms_x;
+            side.uniforms.microsaccade_y = ms_y;
+        }
+    }
+
+    /// Nod animation: sets nod_pitch uniform and overrides eyelid_close.
+    fn apply_nod(&mut self, time: f32) {
+        if let Some(nod_out) = self.nod_animation.evaluate(time) {
+            for side in [&mut self.left, &mut self.right] {
+                side.uniforms.nod_pitch = nod_out.nod_pitch;
+                side.uniforms.nod_pivot_y = nod_out.pivot_y;
+                side.uniforms.nod_sink = nod_out.nod_sink;
+                side.uniforms.eyelid_close = nod_out.eyelid_close;
+            }
+        } else {
+            for side in [&mut self.left, &mut self.right] {
+                side.uniforms.nod_pitch = 0.0;
+                side.uniforms.nod_sink = 0.0;
+            }
+        }
+    }
+
+    /// Raise start/finish events by comparing against the previous update.
+    fn detect_transitions(&mut self, time: f32) {
+        let blinking = self.blink_animation.is_blinking(time);
+        if blinking != self.was_blinking {
+            self.events.push(if blinking {
+                EyeEvent::BlinkStarted
+            } else {
+                EyeEvent::BlinkFinished
+            });
+            self.was_blinking = blinking;
+        }
+
+        let nodding = self.nod_animation.is_active();
+        if nodding != self.was_nodding {
+            self.events.push(if nodding {
+                EyeEvent::NodStarted
+            } else {
+                EyeEvent::NodFinished
+            });
+            self.was_nodding = nodding;
+        }
+
+        let playing = self.timeline_player.is_active();
+        if self.was_timeline_playing && !playing {
+            self.events.push(EyeEvent::TimelineFinished);
+        }
+        self.was_timeline_playing = playing;
+    }
+
+    /// Set eye rotation and head orientation for both eyes.
+    fn set_pose(&mut self, pose: HeadEyePose) {
+        for side in [&mut self.left, &mut self.right] {
+            side.uniforms.look_x = pose.eye[0];
+            side.uniforms.look_y = pose.eye[1];
+            side.uniforms.head_yaw = pose.head[0];
+            side.uniforms.head_pitch = pose.head[1];
+        }
+    }
+
+    /// Seed head/eye coordination and the gaze filter from the displayed pose,
+    /// so the next target starts from there.
+    fn reset_gaze_tracking(&mut self, time: f32) {
+        let u = &self.left.uniforms;
+        let pose = HeadEyePose {
+            head: [u.head_yaw, u.head_pitch],
+            eye: [u.look_x, u.look_y],
+        };
+        self.head_eye.reset(time, pose);
+        self.gaze_filter.reset(time, self.head_eye.gaze());
+    }
+
+    fn set_eyelid(&mut self, eyelid_close: f32) {
+        self.left.uniforms.eyelid_close = eyelid_close;
+        self.right.uniforms.eyelid_close = eyelid_close;
+    }
+
+    fn set_squash(&mut self, squash: f32) {
+        self.left.uniforms.squash_stretch = squash;
+        self.right.uniforms.squash_stretch = squash;
+    }
+
+    /// Sync global params left → right.
+    fn sync_globals(&mut self) {
+        let l = &self.left.uniforms;
+        let r = &mut self.right.uniforms;
+        r.bg_color = l.bg_color;
+        r.bg_alpha = l.bg_alpha;
+        r.eye_separation = l.eye_separation;
+        r.max_angle = l.max_angle;
+        r.eye_angle = l.eye_angle;
+        r.head_yaw = l.head_yaw;
+        r.head_pitch = l.head_pitch;
+    }
+}
+
+fn squash_from_velocity(velocity: f32) -> f32 {
+    (velocity * SQUASH_STRENGTH).clamp(-MAX_SQUASH, MAX_SQUASH)
+}
@@ -0,0 +1,222 @@
+use std::fmt;
+
+use crate::config::EyeConfig;
+use crate::controller::{EyeController, EyeInputs};
+use crate::offscreen::{OffscreenError, RgbaImage};
+use crate::renderer::EyeRenderer;
+use crate::timeline::Timeline;
+
+// ============================================================
+// Settings
+// ============================================================
+
+/// Fixed-rate offline export settings.
+#[derive(Clone, Debug)]
+pub struct ExportSettings {
+    /// Frames per second of the exported sequence.
+    pub fps: u32,
+    /// Output width in pixels.
+    pub width: u32,
+    /// Output height in pixels.
+    pub height: u32,
+    /// Seconds to export. `None` = the timeline's total duration. Frames
+    /// past the end hold the timeline's last frame.
+    pub duration: Option<f32>,
+}
+
+impl Default for ExportSettings {
+    fn default() -> Self {
+        Self {
+            fps: 30,
+            width: 640,
+            height: 360,
+            duration: None,
+        }
+    }
+}
+
+impl ExportSettings {
+    /// Number of frames covering `duration` (both endpoints included).
+    pub fn frame_count(&self, duration: f32) -> u32 {
+        (duration.max(0.0) * self.fps as f32).ceil() as u32 + 1
+    }
+}
+
+// ============================================================
+// Errors
+// ============================================================
+
+#[derive(Debug)]
+pub enum ExportError {
+    /// The timeline has no keys or markers.
+    EmptyTimeline,
+    /// `fps` was zero.
+    ZeroFps,
+    Render(OffscreenError),
+    Io(std::io::Error),
+    #[cfg(feature = "png")]
+    Png(png::EncodingError),
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptyTimeline => write!(f, "timeline is empty"),
+            Self::ZeroFps => write!(f, "fps must be greater than zero"),
+            Self::Render(e) => write!(f, "render failed: {e}"),
+            Self::Io(e) => write!(f, "I/O error: {e}"),
+            #[cfg(feature = "png")]
+            Self::Png(e) => write!(f, "PNG encoding failed: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for ExportError {}
+
+impl From<OffscreenError> for ExportError {
+    fn from(e: OffscreenError) -> Self {
+        Self::Render(e)
+    }
+}
+
+impl From<std::io::Error> for ExportError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+#[cfg(feature = "png")]
+impl From<png::EncodingError> for ExportError {
+    fn from(e: png::EncodingError) -> Self {
+        Self::Png(e)
+    }
+}
+
+// ============================================================
+// Frame stepping
+// ============================================================
+
+/// Validate the inputs and compute how many frames the export will produce.
+fn frame_count(timeline: &Timeline, settings: &ExportSettings) -> Result<u32, ExportError> {
+    if timeline.is_empty() {
+        return Err(ExportError::EmptyTimeline);
+    }
+    if settings.fps == 0 {
+        return Err(ExportError::ZeroFps);
+    }
+    let duration = settings
+        .duration
+        .unwrap_or_else(|| timeline.total_duration());
+    Ok(settings.frame_count(duration))
+}
+
+/// Step `timeline` at a fixed frame rate and render every frame offscreen,
+/// handing each one to `on_frame` in order.
+///
+/// Each call drives a fresh controller set up from `config` (`None` = the
+/// default look), so every animation starts from the same state. With
+/// synthetic time (`frame / fps`) the result is identical on every run
+/// regardless of how long rendering takes. Blink markers trigger the blink
+/// animation exactly as in real-time playback. Past the end the timeline is
+/// held on its last frame rather than handing back to realtime animation.
+/// Returns the number of frames rendered.
+pub fn render_timeline<F>(
+    config: Option<&EyeConfig>,
+    timeline: &Timeline,
+    renderer: &EyeRenderer,
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    settings: &ExportSettings,
+    mut on_frame: F,
+) -> Result<u32, ExportError>
+where
+    F: FnMut(u32, RgbaImage) -> Result<(), ExportError>,
+{
+    let frames = frame_count(timeline, settings)?;
+    let mut timeline = timeline.clone();
+    timeline.sort();
+
+    let mut controller = EyeController::new();
+    if let Some(config) = config {
+        config.apply_to_controller(&mut controller);
+    }
+
+    let end = timeline.total_duration();
+    let player = &mut controller.timeline_player;
+    player.timeline = timeline;
+    player.looping = false;
+    player.ping_pong = false;
+    player.set_speed(1.0, 0.0);
+    player.play(0.0);
+
+    let inputs = EyeInputs {
+        aspect_ratio: settings.width as f32 / settings.height.max(1) as f32,
+        logical_height: settings.height as f32,
+        ..Default::default()
+    };
+
+    for i in 0..frames {
+        let time = i as f32 / settings.fps as f32;
+        if time > end && controller.timeline_player.is_playing() {
+            // Land on the end so its events fire, then hold it
+            controller.update(end, &inputs);
+            controller.timeline_player.pause();
+        }
+        let pair = controller.update(time, &inputs);
+        let image = renderer.render_to_image(device, queue, settings.width, settings.height, &pair)?;
+        on_frame(i, image)?;
+    }
+    Ok(frames)
+}
+
+/// Export `timeline` as numbered PNG files (`{prefix}_00000.png`, ...) in `dir`.
+/// Returns the number of frames written.
+#[cfg(feature = "png")]
+#[allow(clippy::too_many_arguments)]
+pub fn export_png_sequence(
+    config: Option<&EyeConfig>,
+    timeline: &Timeline,
+    renderer: &EyeRenderer,
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    settings: &ExportSettings,
+    dir: impl AsRef<std::path::Path>,
+    prefix: &str,
+) -> Result<u32, ExportError> {
+    let dir = dir.as_ref();
+    std::fs::create_dir_all(dir)?;
+    render_timeline(config, timeline, renderer, device, queue, settings, |i, image| {
+        image.write_png(dir.join(format!("{prefix}_{i:05}.png")))?;
+        Ok(())
+    })
+}
+
+/// Export `timeline` as a single animated PNG that plays once.
+/// Returns the number of frames written.
+#[cfg(feature = "png")]
+pub fn export_apng(
+    config: Option<&EyeConfig>,
+    timeline: &Timeline,
+    renderer: &EyeRenderer,
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    settings: &ExportSettings,
+    path: impl AsRef<std::path::Path>,
+) -> Result<u32, ExportError> {
+    let frames = frame_count(timeline, settings)?;
+
+    let file = std::io::BufWriter::new(std::fs::File::create(path)?);
+    let mut encoder = png::Encoder::new(file, settings.width, settings.height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_animated(frames, 1)?;
+    encoder.set_frame_delay(1, settings.fps.min(u16::MAX as u32) as u16)?;
+    let mut writer = encoder.write_header()?;
+
+    render_timeline(config, timeline, renderer, device, queue, settings, |_, image| {
+        writer.write_image_data(&image.pixels)?;
+        Ok(())
+    })?;
+    writer.finish()?;
+    Ok(frames)
+}
@@ -0,0 +1,483 @@
+use serde::{Deserialize, Serialize};
+
+use crate::animation::{apply_easing, Easing};
+use crate::config::{BezierOutlineConfig, EyebrowOutlineConfig};
+use crate::controller::EyeSideState;
+use crate::outline::{BezierAnchor, BezierOutline, EyebrowOutline, EyebrowShape};
+use crate::renderer::EyeUniforms;
+
+// ============================================================
+// Expression definitions
+// ============================================================
+
+/// Partial override of an eye's config. `None` fields are left to the base look.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExpressionOverride {
+    /// Open eye outline.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eye_shape: Option<BezierOutlineConfig>,
+    /// Arch of the closed lid; regenerates the closed outline.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub close_arch: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eyebrow_outline: Option<EyebrowOutlineConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eyebrow_base_y: Option<f32>,
+    /// Resting lid. Blinks still close fully from here.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eyelid_close: Option<f32>,
+    /// Upper lid alone (sleepy, suspicious).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upper_lid_close: Option<f32>,
+    /// Lower lid alone (squint).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lower_lid_raise: Option<f32>,
+    /// Pupil size; the pupil outline is scaled to match.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pupil_radius: Option<f32>,
+}
+
+impl ExpressionOverride {
+    /// Field-wise `self`, falling back to `base` where unset.
+    fn or(&self, base: &Self) -> Self {
+        Self {
+            eye_shape: self.eye_shape.clone().or_else(|| base.eye_shape.clone()),
+            close_arch: self.close_arch.or(base.close_arch),
+            eyebrow_outline: self
+                .eyebrow_outline
+                .clone()
+                .or_else(|| base.eyebrow_outline.clone()),
+            eyebrow_base_y: self.eyebrow_base_y.or(base.eyebrow_base_y),
+            eyelid_close: self.eyelid_close.or(base.eyelid_close),
+            upper_lid_close: self.upper_lid_close.or(base.upper_lid_close),
+            lower_lid_raise: self.lower_lid_raise.or(base.lower_lid_raise),
+            pupil_radius: self.pupil_radius.or(base.pupil_radius),
+        }
+    }
+}
+
+/// How an expression combines with the others that are active.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExpressionBlend {
+    /// Weighted average: when blend weights sum past 1 they are normalized,
+    /// so two full expressions meet halfway.
+    #[default]
+    Blend,
+    /// Offset from the base is added on top at its weight, unnormalized.
+    Additive,
+}
+
+/// A named expression preset.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Expression {
+    pub name: String,
+    #[serde(default)]
+    pub blend: ExpressionBlend,
+    /// Overrides for both eyes (the renderer mirrors shapes for the right eye).
+    #[serde(default)]
+    pub eyes: ExpressionOverride,
+    /// Left-eye fields layered over `eyes` (e.g. a one-sided squint).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub left: Option<ExpressionOverride>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub right: Option<ExpressionOverride>,
+}
+
+impl Expression {
+    /// Effective override for one eye.
+    pub fn override_for(&self, right_eye: bool) -> ExpressionOverride {
+        let side = if right_eye { &self.right } else { &self.left };
+        match side {
+            Some(o) => o.or(&self.eyes),
+            None => self.eyes.clone(),
+        }
+    }
+}
+
+/// A set of expressions, loadable from JSON.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ExpressionLibrary {
+    pub expressions: Vec<Expression>,
+}
+
+impl Default for ExpressionLibrary {
+    fn default() -> Self {
+        Self::built_in()
+    }
+}
+
+/// Default eyebrow rotated by `angle` radians around its middle.
+/// Positive raises the inner (nose-side, +x) end.
+fn tilted_eyebrow(angle: f32, raise_inner: f32) -> EyebrowOutlineConfig {
+    let mut outline = EyebrowShape::default().outline;
+    let (sin, cos) = angle.sin_cos();
+    let rotate = |v: [f32; 2]| [v[0] * cos - v[1] * sin, v[0] * sin + v[1] * cos];
+    for anchor in &mut outline.anchors {
+        let [x, y] = rotate(anchor.position);
+        // Extra lift fades out toward the outer tip
+        let lift = raise_inner * ((x + 0.28) / 0.56).clamp(0.0, 1.0);
+        anchor.position = [x, y + lift];
+        anchor.handle_in = rotate(anchor.handle_in);
+        anchor.handle_out = rotate(anchor.handle_out);
+    }
+    EyebrowOutlineConfig::from(&outline)
+}
+
+impl ExpressionLibrary {
+    /// Presets tuned for the default look: happy, sad, angry, surprised,
+    /// sleepy and suspicious.
+    pub fn built_in() -> Self {
+        let preset = |name: &str, eyes: ExpressionOverride| Expression {
+            name: name.into(),
+            blend: ExpressionBlend::Blend,
+            eyes,
+            left: None,
+            right: None,
+        };
+        Self {
+            expressions: vec![
+                preset(
+                    "happy",
+                    ExpressionOverride {
+                        close_arch: Some(0.06),
+                        eyebrow_base_y: Some(0.52),
+                        eyelid_close: Some(0.55),
+                        ..Default::default()
+                    },
+                ),
+                preset(
+                    "sad",
+                    ExpressionOverride {
+                        eyebrow_outline: Some(tilted_eyebrow(0.18, 0.02)),
+                        eyebrow_base_y: Some(0.46),
+                        eyelid_close: Some(0.4),
+                        pupil_radius: Some(0.058),
+                        ..Default::default()
+                    },
+                ),
+                preset(
+                    "angry",
+                    ExpressionOverride {
+                        eyebrow_outline: Some(tilted_eyebrow(-0.25, -0.02)),
+                        eyebrow_base_y: Some(0.42),
+                        eyelid_close: Some(0.35),
+                        lower_lid_raise: Some(0.3),
+                        pupil_radius: Some(0.042),
+                        ..Default::default()
+                    },
+                ),
+                preset(
+                    "surprised",
+                    ExpressionOverride {
+                        eyebrow_base_y: Some(0.58),
+                        eyelid_close: Some(0.0),
+                        pupil_radius: Some(0.04),
+                        ..Default::default()
+                    },
+                ),
+                preset(
+                    "sleepy",
+                    ExpressionOverride {
+                        close_arch: Some(0.0),
+                        eyebrow_base_y: Some(0.45),
+                        upper_lid_close: Some(0.55),
+                        ..Default::default()
+                    },
+                ),
+                Expression {
+                    name: "suspicious".into(),
+                    blend: ExpressionBlend::Blend,
+                    eyes: ExpressionOverride {
+                        upper_lid_close: Some(0.35),
+                        lower_lid_raise: Some(0.5),
+                        ..Default::default()
+                    },
+                    left: Some(ExpressionOverride {
+                        eyebrow_outline: Some(tilted_eyebrow(-0.15, 0.0)),
+                        eyebrow_base_y: Some(0.44),
+                        ..Default::default()
+                    }),
+                    right: Some(ExpressionOverride {
+                        eyebrow_base_y: Some(0.53),
+                        upper_lid_close: Some(0.15),
+                        ..Default::default()
+                    }),
+                },
+            ],
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Expression> {
+        self.expressions.iter().find(|e| e.name == name)
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+// ============================================================
+// Runtime mixing
+// ============================================================
+
+/// One expression's weight moving toward a target.
+struct ExpressionLayer {
+    name: String,
+    from: f32,
+    to: f32,
+    start_time: f32,
+    duration: f32,
+}
+
+impl ExpressionLayer {
+    fn weight(&self, time: f32) -> f32 {
+        if self.duration <= 0.0 {
+            return self.to;
+        }
+        let t = ((time - self.start_time) / self.duration).clamp(0.0, 1.0);
+        self.from + (self.to - self.from) * apply_easing(t, Easing::EaseInOut)
+    }
+
+    fn is_done(&self, time: f32) -> bool {
+        time - self.start_time >= self.duration
+    }
+}
+
+/// Blends any number of library expressions over the base look, each with
+/// its own animated weight.
+///
+/// The base eye state is never modified; overrides are applied to a copy of
+/// the uniforms at output time, so GUI edits and config saves stay clean.
+#[derive(Default)]
+pub struct ExpressionMixer {
+    pub library: ExpressionLibrary,
+    layers: Vec<ExpressionLayer>,
+    time: f32,
+}
+
+/// Add `weight` of the offset from `base` to `target`.
+fn accumulate(value: &mut f32, base: f32, target: f32, weight: f32) {
+    *value += (target - base) * weight;
+}
+
+fn accumulate_anchor(value: &mut BezierAnchor, base: &BezierAnchor, target: &BezierAnchor, w: f32) {
+    for i in 0..2 {
+        accumulate(
+            &mut value.position[i],
+            base.position[i],
+            target.position[i],
+            w,
+        );
+        accumulate(
+            &mut value.handle_in[i],
+            base.handle_in[i],
+            target.handle_in[i],
+            w,
+        );
+        accumulate(
+            &mut value.handle_out[i],
+            base.handle_out[i],
+            target.handle_out[i],
+            w,
+        );
+    }
+}
+
+/// Map `value` so `rest` lands on `target` while 0 and 1 stay put.
+fn remap_eyelid(value: f32, rest: f32, target: f32) -> f32 {
+    if value >= rest {
+        let span = (1.0 - rest).max(1e-4);
+        target + (value - rest) / span * (1.0 - target)
+    } else {
+        value / rest.max(1e-4) * target
+    }
+}
+
+impl ExpressionMixer {
+    pub fn new(library: ExpressionLibrary) -> Self {
+        Self {
+            library,
+            layers: Vec::new(),
+            time: 0.0,
+        }
+    }
+
+    /// Move expression `name` to `weight` (0..=1 typical) over `duration` seconds.
+    pub fn set(&mut self, name: &str, weight: f32, duration: f32, time: f32) -> Result<(), String> {
+        if self.library.get(name).is_none() {
+            return Err(format!("unknown expression `{name}`"));
+        }
+        let weight = weight.max(0.0);
+        let duration = duration.max(0.0);
+        match self.layers.iter_mut().find(|l| l.name == name) {
+            Some(layer) => {
+                layer.from = layer.weight(time);
+                layer.to = weight;
+                layer.start_time = time;
+                layer.duration = duration;
+            }
+            None => self.layers.push(ExpressionLayer {
+                name: name.into(),
+                from: 0.0,
+                to: weight,
+                start_time: time,
+                duration,
+            }),
+        }
+        Ok(())
+    }
+
+    /// Fade every expression out over `duration` seconds.
+    pub fn clear(&mut self, duration: f32, time: f32) {
+        for layer in &mut self.layers {
+            layer.from = layer.weight(time);
+            layer.to = 0.0;
+            layer.start_time = time;
+            layer.duration = duration.max(0.0);
+        }
+    }
+
+    /// Current weight of `name` (0 when inactive).
+    pub fn weight(&self, name: &str) -> f32 {
+        self.layers
+            .iter()
+            .find(|l| l.name == name)
+            .map_or(0.0, |l| l.weight(self.time))
+    }
+
+    /// Whether any weight is still changing.
+    pub fn is_transitioning(&self) -> bool {
+        self.layers.iter().any(|l| !l.is_done(self.time))
+    }
+
+    /// Advance to `time` and drop expressions that have faded out.
+    pub fn update(&mut self, time: f32) {
+        self.time = time;
+        self.layers.retain(|l| !(l.is_done(time) && l.to <= 0.0));
+    }
+
+    /// Apply the active expressions for one eye on top of `state` into `u`.
+    ///
+    /// `rest_eyelid` is the lid value the current `u.eyelid_close` rests at
+    /// (blink rest); expression lids replace it while blinks keep closing fully.
+    pub fn apply(
+        &self,
+        state: &EyeSideState,
+        right_eye: bool,
+        rest_eyelid: f32,
+        u: &mut EyeUniforms,
+    ) {
+        let active: Vec<(&Expression, f32)> = self
+            .layers
+            .iter()
+            .filter_map(|l| {
+                let w = l.weight(self.time);
+                let expr = self.library.get(&l.name)?;
+                (w > 1e-4).then_some((expr, w))
+            })
+            .collect();
+        if active.is_empty() {
+            return;
+        }
+
+        let blend_total: f32 = active
+            .iter()
+            .filter(|(e, _)| e.blend == ExpressionBlend::Blend)
+            .map(|(_, w)| w)
+            .sum();
+        let blend_scale = 1.0 / blend_total.max(1.0);
+
+        // Targets are resampled to the eye's own layout, so anchors pair up
+        // corner to corner whatever count a preset was authored with
+        let base_open = &state.eye_shape.open;
+
+        let base_pupil = state.uniforms.pupil_radius;
+        let mut open = base_open.clone();
+        let mut close_arch = state.eye_shape.close_arch;
+        let mut arch_changed = false;
+        let mut brow = state.eyebrow_shape.outline.clone();
+        let mut brow_y = state.eyebrow_shape.base_y;
+        let mut eyelid = rest_eyelid;
+        let (base_upper, base_lower) = (u.upper_lid_close, u.lower_lid_raise);
+        let (mut upper, mut lower) = (base_upper, base_lower);
+        let mut pupil = base_pupil;
+
+        for (expr, weight) in active {
+            let w = match expr.blend {
+                ExpressionBlend::Blend => weight * blend_scale,
+                ExpressionBlend::Additive => weight,
+            };
+            let o = expr.override_for(right_eye);
+            if let Some(target) = &o.eye_shape {
+                let target = BezierOutline::from(target).resampled_like(base_open);
+                for (i, anchor) in open.anchors.iter_mut().enumerate() {
+                    accumulate_anchor(anchor, &base_open.anchors[i], &target.anchors[i], w);
+                }
+            }
+            if let Some(target) = o.close_arch {
+                accumulate(&mut close_arch, state.eye_shape.close_arch, target, w);
+                arch_changed = true;
+            }
+            if let Some(target) = &o.eyebrow_outline {
+                let target = EyebrowOutline::from(target);
+                let base = &state.eyebrow_shape.outline;
+                for (i, anchor) in brow.anchors.iter_mut().enumerate() {
+                    accumulate_anchor(anchor, &base.anchors[i], &target.anchors[i], w);
+                }
+            }
+            if let Some(target) = o.eyebrow_base_y {
+                accumulate(&mut brow_y, state.eyebrow_shape.base_y, target, w);
+            }
+            if let Some(target) = o.eyelid_close {
+                accumulate(&mut eyelid, rest_eyelid, target, w);
+            }
+            if let Some(target) = o.upper_lid_close {
+                accumulate(&mut upper, base_upper, target, w);
+            }
+            if let Some(target) = o.lower_lid_raise {
+                accumulate(&mut lower, base_lower, target, w);
+            }
+            if let Some(target) = o.pupil_radius {
+                accumulate(&mut pupil, base_pupil, target, w);
+            }
+        }
+
+        let lower_closed = &state.eye_shape.lower_closed;
+        if arch_changed {
+            let closed = state.eye_shape.closed_with_arch(close_arch);
+            u.set_eye_outline(&open, &closed, lower_closed);
+        } else {
+            u.set_eye_outline(&open, &state.eye_shape.closed, lower_closed);
+        }
+        u.upper_lid_close = upper.clamp(0.0, 1.0);
+        u.lower_lid_raise = lower.clamp(0.0, 1.0);
+        u.eyebrow_outline = brow.to_uniform_array();
+        u.eyebrow_base_y = brow_y;
+        u.eyelid_close =
+            remap_eyelid(u.eyelid_close, rest_eyelid, eyelid.clamp(0.0, 1.0)).clamp(0.0, 1.0);
+
+        let pupil = pupil.max(0.0);
+        if base_pupil > 1e-4 && (pupil - base_pupil).abs() > 1e-6 {
+            let scale = pupil / base_pupil;
+            let mut outline = state.pupil_shape.outline.clone();
+            for anchor in &mut outline.anchors {
+                for v in [
+                    &mut anchor.position,
+                    &mut anchor.handle_in,
+                    &mut anchor.handle_out,
+                ] {
+                    v[0] *= scale;
+                    v[1] *= scale;
+                }
+            }
+            u.set_pupil_outline(&outline);
+        }
+        u.pupil_radius = pupil;
+    }
+}
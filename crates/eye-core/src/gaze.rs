@@ -0,0 +1,245 @@
+use crate::animation::{apply_easing, Easing};
+
+/// Smoothing model used for gaze moves below the saccade threshold.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GazeSmoothing {
+    /// Pass input through unchanged.
+    None,
+    /// One-Euro filter: heavy smoothing when slow, low lag when fast.
+    OneEuro,
+    /// Critically damped spring toward the target.
+    Spring,
+}
+
+/// A ballistic saccade in flight.
+struct Saccade {
+    start_time: f32,
+    from: [f32; 2],
+    to: [f32; 2],
+    duration: f32,
+}
+
+/// Filters raw gaze targets (mouse follow, face trackers, remote clients)
+/// before they reach the uniforms.
+///
+/// Small moves are smoothed with [`GazeSmoothing`]; jumps larger than
+/// `saccade_threshold` become a ballistic saccade whose duration follows the
+/// main sequence (`duration = base + slope * amplitude`). A saccade is not
+/// retargeted while in flight unless the target jumps again; the remaining
+/// error is picked up by the smoother once it lands.
+pub struct GazeFilter {
+    pub enabled: bool,
+    pub smoothing: GazeSmoothing,
+
+    /// One-Euro minimum cutoff frequency (Hz). Lower = smoother at rest.
+    pub min_cutoff: f32,
+    /// One-Euro speed coefficient. Higher = less lag on fast moves.
+    pub beta: f32,
+    /// One-Euro cutoff for the derivative estimate (Hz).
+    pub derivative_cutoff: f32,
+
+    /// Spring natural frequency (Hz).
+    pub spring_frequency: f32,
+
+    /// Distance in look units above which a move becomes a saccade.
+    pub saccade_threshold: f32,
+    /// Main-sequence intercept (seconds).
+    pub saccade_base_duration: f32,
+    /// Main-sequence slope (seconds per degree of rotation).
+    pub saccade_duration_per_degree: f32,
+    /// Position profile of a saccade (fast acceleration, gentle landing).
+    pub saccade_easing: Easing,
+
+    /// Filtered position, `None` until seeded.
+    position: Option<[f32; 2]>,
+    /// One-Euro derivative estimate / spring velocity (look units/s).
+    velocity: [f32; 2],
+    prev_target: [f32; 2],
+    last_time: f32,
+    saccade: Option<Saccade>,
+}
+
+impl Default for GazeFilter {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            smoothing: GazeSmoothing::OneEuro,
+            min_cutoff: 1.5,
+            beta: 2.0,
+            derivative_cutoff: 1.0,
+            spring_frequency: 6.0,
+            saccade_threshold: 0.25,
+            saccade_base_duration: 0.021,
+            saccade_duration_per_degree: 0.0022,
+            saccade_easing: Easing::EaseInOut,
+            position: None,
+            velocity: [0.0; 2],
+            prev_target: [0.0; 2],
+            last_time: 0.0,
+            saccade: None,
+        }
+    }
+}
+
+/// Smoothing factor of a first-order low-pass at `cutoff` Hz for step `dt`.
+fn low_pass_alpha(cutoff: f32, dt: f32) -> f32 {
+    let tau = 1.0 / (std::f32::consts::TAU * cutoff.max(1e-3));
+    1.0 / (1.0 + tau / dt)
+}
+
+fn distance(a: [f32; 2], b: [f32; 2]) -> f32 {
+    ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2)).sqrt()
+}
+
+fn lerp2(a: [f32; 2], b: [f32; 2], t: f32) -> [f32; 2] {
+    [a[0] + (b[0] - a[0]) * t, a[1] + (b[1] - a[1]) * t]
+}
+
+impl GazeFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Seed the filter at `look` and drop any motion in progress.
+    ///
+    /// Call while nothing drives the gaze so the next input starts from the
+    /// currently displayed position instead of jumping.
+    pub fn reset(&mut self, time: f32, look: [f32; 2]) {
+        self.position = Some(look);
+        self.velocity = [0.0; 2];
+        self.prev_target = look;
+        self.last_time = time;
+        self.saccade = None;
+    }
+
+    /// Whether a saccade is in flight.
+    pub fn is_saccading(&self) -> bool {
+        self.saccade.is_some()
+    }
+
+    /// Amplitude (look units) of the saccade started by the update at `time`,
+    /// if that update started one.
+    pub fn saccade_started(&self, time: f32) -> Option<f32> {
+        self.saccade
+            .as_ref()
+            .filter(|s| s.start_time == time)
+            .map(|s| distance(s.from, s.to))
+    }
+
+    /// Whether the output has converged on the last target, i.e. further
+    /// updates with the same target would not move the gaze.
+    pub fn is_settled(&self) -> bool {
+        match self.position {
+            Some(pos) => {
+                self.saccade.is_none()
+                    && distance(pos, self.prev_target) < 1e-3
+                    && self.velocity[0].abs() + self.velocity[1].abs() < 1e-2
+            }
+            None => true,
+        }
+    }
+
+    /// Advance the filter toward `target` and return the look to display.
+    ///
+    /// `max_angle` is the eye rotation (radians) at look = 1, used to convert
+    /// jump distance into degrees for the main-sequence duration.
+    pub fn update(&mut self, time: f32, target: [f32; 2], max_angle: f32) -> [f32; 2] {
+        let Some(position) = self.position else {
+            self.reset(time, target);
+            return target;
+        };
+        if !self.enabled {
+            self.reset(time, target);
+            return target;
+        }
+
+        // Clamp long gaps (e.g. a hidden window) so one step cannot jump far
+        let dt = (time - self.last_time).clamp(0.0, 0.1);
+        self.last_time = time;
+
+        // A new large jump starts a saccade from wherever the eye is now
+        let jumped = distance(target, self.prev_target) > self.saccade_threshold;
+        self.prev_target = target;
+        if jumped || (self.saccade.is_none() && distance(target, position) > self.saccade_threshold)
+        {
+            let amplitude_deg = distance(target, position) * max_angle.to_degrees();
+            self.saccade = Some(Saccade {
+                start_time: time,
+                from: position,
+                to: target,
+                duration: self.saccade_base_duration
+                    + self.saccade_duration_per_degree * amplitude_deg,
+            });
+            self.velocity = [0.0; 2];
+        }
+
+        if let Some(saccade) = &self.saccade {
+            let t = ((time - saccade.start_time) / saccade.duration.max(1e-3)).clamp(0.0, 1.0);
+            let pos = lerp2(
+                saccade.from,
+                saccade.to,
+                apply_easing(t, self.saccade_easing),
+            );
+            if t >= 1.0 {
+                self.saccade = None;
+            }
+            self.position = Some(pos);
+            return pos;
+        }
+
+        if dt <= 0.0 {
+            return position;
+        }
+
+        let pos = match self.smoothing {
+            GazeSmoothing::None => {
+                self.velocity = [0.0; 2];
+                target
+            }
+            GazeSmoothing::OneEuro => self.one_euro_step(position, target, dt),
+            GazeSmoothing::Spring => self.spring_step(position, target, dt),
+        };
+        self.position = Some(pos);
+        pos
+    }
+
+    fn one_euro_step(&mut self, position: [f32; 2], target: [f32; 2], dt: f32) -> [f32; 2] {
+        let mut out = position;
+        for axis in 0..2 {
+            let raw_velocity = (target[axis] - position[axis]) / dt;
+            let a_d = low_pass_alpha(self.derivative_cutoff, dt);
+            self.velocity[axis] += a_d * (raw_velocity - self.velocity[axis]);
+
+            let cutoff = self.min_cutoff + self.beta * self.velocity[axis].abs();
+            let a = low_pass_alpha(cutoff, dt);
+            out[axis] = position[axis] + a * (target[axis] - position[axis]);
+        }
+        out
+    }
+
+    fn spring_step(&mut self, position: [f32; 2], target: [f32; 2], dt: f32) -> [f32; 2] {
+        spring_step(position, &mut self.velocity, target, self.spring_frequency, dt)
+    }
+}
+
+/// Closed-form step of a critically damped spring at `frequency` Hz
+/// (stable for any `dt`). Updates `velocity` in place.
+pub(crate) fn spring_step(
+    position: [f32; 2],
+    velocity: &mut [f32; 2],
+    target: [f32; 2],
+    frequency: f32,
+    dt: f32,
+) -> [f32; 2] {
+    let omega = std::f32::consts::TAU * frequency.max(1e-3);
+    let x = omega * dt;
+    let decay = 1.0 / (1.0 + x + 0.48 * x * x + 0.235 * x * x * x);
+    let mut out = position;
+    for axis in 0..2 {
+        let change = position[axis] - target[axis];
+        let temp = (velocity[axis] + omega * change) * dt;
+        velocity[axis] = (velocity[axis] - omega * temp) * decay;
+        out[axis] = target[axis] + (change + temp) * decay;
+    }
+    out
+}
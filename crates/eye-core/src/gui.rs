@@ -1,11 +1,24 @@
+use std::f32::consts::PI;
+
 use egui;
 
-use crate::config::EyeSideConfig;
+use crate::config::NodConfig;
+use crate::expression::ExpressionMixer;
+pub use crate::controller::EyeSideState;
+use crate::animation::{BlinkAnimation, WinkEye};
 use crate::listening::ListeningNod;
+use crate::microsaccade::MicrosaccadeAnimation;
 use crate::nod::NodAnimation;
-use crate::outline::{BezierAnchor, BezierOutline, EyelashShape, EyeShape, EyebrowShape, IrisShape, PupilShape};
-use crate::timeline::{TimelineEasing, TimelineGlobalConfig, TimelineKeyframe, TimelinePlayer};
-use crate::EyeUniforms;
+use crate::outline::{
+    BezierAnchor, BezierOutline, EyelashShape, EyelidShape, EyebrowShape, Highlight,
+    HighlightShape,
+};
+use crate::pupil::{luminance, PupilDynamics};
+use crate::renderer::EyeUniforms;
+use crate::timeline::{
+    AnimationChannel, LayerBlend, TimelineAction, TimelineEasing, TimelineEvent, TimelineLayer,
+    TimelinePlayer, TimelinePose, TrackEye, TrackProperty, TrackValue,
+};
 
 // ============================================================
 // GUI action signaling
@@ -18,8 +31,8 @@ pub struct GuiActions {
     pub nod_triggered: bool,
     pub timeline_export_requested: bool,
     pub timeline_import_requested: bool,
-    /// Load a config file into the keyframe at this index.
-    pub timeline_load_file_into_keyframe: Option<usize>,
+    /// Key the properties of a config file that differ from the editor at the playhead.
+    pub timeline_key_config_file: bool,
 }
 
 // ============================================================
@@ -49,37 +62,16 @@ impl Default for SectionLink {
     }
 }
 
-/// All parameters for one eye.
-#[derive(Clone, Debug)]
-pub struct EyeSideState {
-    pub uniforms: EyeUniforms,
-    pub eye_shape: EyeShape,
-    pub eyebrow_shape: EyebrowShape,
-    pub eyelash_shape: EyelashShape,
-    pub iris_shape: IrisShape,
-    pub pupil_shape: PupilShape,
-}
-
-impl Default for EyeSideState {
-    fn default() -> Self {
-        Self {
-            uniforms: EyeUniforms::default(),
-            eye_shape: EyeShape::default(),
-            eyebrow_shape: EyebrowShape::default(),
-            eyelash_shape: EyelashShape::default(),
-            iris_shape: IrisShape::default(),
-            pupil_shape: PupilShape::default(),
-        }
-    }
-}
-
 // ============================================================
 // Section sync helpers
 // ============================================================
 
 fn sync_shape(from: &EyeSideState, to: &mut EyeSideState) {
     to.uniforms.eyelid_close = from.uniforms.eyelid_close;
+    to.uniforms.upper_lid_close = from.uniforms.upper_lid_close;
+    to.uniforms.lower_lid_raise = from.uniforms.lower_lid_raise;
     to.eye_shape = from.eye_shape.clone();
+    to.eyelid_shape = from.eyelid_shape.clone();
 }
 
 fn sync_iris(from: &EyeSideState, to: &mut EyeSideState) {
@@ -93,21 +85,27 @@ fn sync_iris(from: &EyeSideState, to: &mut EyeSideState) {
     to.uniforms.pupil_radius = from.uniforms.pupil_radius;
     to.iris_shape = from.iris_shape.clone();
     to.pupil_shape = from.pupil_shape.clone();
+    sync_visibility(from, to, EyeUniforms::SHOW_IRIS);
+    sync_visibility(from, to, EyeUniforms::SHOW_PUPIL);
 }
 
 fn sync_eyebrow(from: &EyeSideState, to: &mut EyeSideState) {
     to.eyebrow_shape = from.eyebrow_shape.clone();
+    sync_visibility(from, to, EyeUniforms::SHOW_EYEBROW);
 }
 
 fn sync_eyelash(from: &EyeSideState, to: &mut EyeSideState) {
     to.eyelash_shape = from.eyelash_shape.clone();
+    sync_visibility(from, to, EyeUniforms::SHOW_EYELASH);
 }
 
 fn sync_highlight(from: &EyeSideState, to: &mut EyeSideState) {
-    to.uniforms.highlight_offset = from.uniforms.highlight_offset;
-    to.uniforms.highlight_radius = from.uniforms.highlight_radius;
-    to.uniforms.highlight_intensity = from.uniforms.highlight_intensity;
-    to.uniforms.highlight_blur = from.uniforms.highlight_blur;
+    to.highlights = from.highlights.clone();
+    sync_visibility(from, to, EyeUniforms::SHOW_HIGHLIGHT);
+}
+
+fn sync_visibility(from: &EyeSideState, to: &mut EyeSideState, feature: u32) {
+    to.set_visible(feature, from.is_visible(feature));
 }
 
 /// Apply a section sync based on which side was active before re-linking.
@@ -155,6 +153,32 @@ fn section_eye_selector(ui: &mut egui::Ui, link: &mut SectionLink) -> Option<Sid
     relink_from
 }
 
+/// Renders the global `show_*` checkbox for a feature next to the per-eye
+/// one for the eye the section is editing (both eyes when linked).
+fn show_toggles(
+    ui: &mut egui::Ui,
+    global: &mut bool,
+    label: &str,
+    side: &mut EyeSideState,
+    link: &SectionLink,
+    feature: u32,
+) {
+    ui.horizontal(|ui| {
+        ui.checkbox(global, label);
+        let eye_label = if link.linked {
+            "Both Eyes"
+        } else if link.active == Side::Left {
+            "Left Eye"
+        } else {
+            "Right Eye"
+        };
+        let mut visible = side.is_visible(feature);
+        if ui.checkbox(&mut visible, eye_label).changed() {
+            side.set_visible(feature, visible);
+        }
+    });
+}
+
 // ============================================================
 // Main control panel
 // ============================================================
@@ -170,17 +194,24 @@ pub fn eye_control_panel(
     link_eyelash: &mut SectionLink,
     link_highlight: &mut SectionLink,
     auto_blink: &mut bool,
+    auto_microsaccade: &mut bool,
     follow_mouse: &mut bool,
     show_highlight: &mut bool,
     show_eyebrow: &mut bool,
     show_eyelash: &mut bool,
+    show_iris: &mut bool,
+    show_pupil: &mut bool,
     pause_animation: &mut bool,
     focus_distance: &mut f32,
     nod_animation: &mut NodAnimation,
+    blink_animation: &mut BlinkAnimation,
+    microsaccade_animation: &mut MicrosaccadeAnimation,
     listening_nod: &mut ListeningNod,
+    pupil_dynamics: &mut PupilDynamics,
     audio_rms: f32,
     ws_connected: bool,
     timeline_player: &mut TimelinePlayer,
+    expressions: &mut ExpressionMixer,
 ) -> GuiActions {
     let mut actions = GuiActions::default();
     egui::SidePanel::right("eye_controls")
@@ -198,22 +229,36 @@ pub fn eye_control_panel(
                     } else {
                         &mut right.uniforms.eyelid_close
                     };
-                    let label = if link_shape.linked {
-                        "Eyelid Close"
+                    let suffix = if link_shape.linked {
+                        ""
                     } else if link_shape.active == Side::Left {
-                        "Eyelid Close [L]"
+                        " [L]"
                     } else {
-                        "Eyelid Close [R]"
+                        " [R]"
                     };
                     ui.add_enabled(
                         !*auto_blink,
-                        egui::Slider::new(eyelid, 0.0..=1.0).text(label),
+                        egui::Slider::new(eyelid, 0.0..=1.0).text(format!("Eyelid Close{suffix}")),
                     );
+                    let side = if editing_left { &mut *left } else { &mut *right };
+                    ui.add(
+                        egui::Slider::new(&mut side.uniforms.upper_lid_close, 0.0..=1.0)
+                            .text(format!("Upper Lid{suffix}")),
+                    )
+                    .on_hover_text("Closes the upper lid on top of Eyelid Close");
+                    ui.add(
+                        egui::Slider::new(&mut side.uniforms.lower_lid_raise, 0.0..=1.0)
+                            .text(format!("Lower Lid{suffix}")),
+                    )
+                    .on_hover_text("Raises the lower lid on top of Eyelid Close");
                     if link_shape.linked {
                         right.uniforms.eyelid_close = left.uniforms.eyelid_close;
+                        right.uniforms.upper_lid_close = left.uniforms.upper_lid_close;
+                        right.uniforms.lower_lid_raise = left.uniforms.lower_lid_raise;
                     }
                 }
                 ui.checkbox(auto_blink, "Auto Blink");
+                ui.checkbox(auto_microsaccade, "Auto Microsaccade");
                 ui.checkbox(pause_animation, "Pause Animation");
 
                 ui.separator();
@@ -311,6 +356,24 @@ pub fn eye_control_panel(
                         }
 
                         let editing_left = link_iris.linked || link_iris.active == Side::Left;
+                        let side = if editing_left { &mut *left } else { &mut *right };
+                        show_toggles(
+                            ui,
+                            show_iris,
+                            "Show Iris",
+                            side,
+                            link_iris,
+                            EyeUniforms::SHOW_IRIS,
+                        );
+                        show_toggles(
+                            ui,
+                            show_pupil,
+                            "Show Pupil",
+                            side,
+                            link_iris,
+                            EyeUniforms::SHOW_PUPIL,
+                        );
+
                         let u = if editing_left {
                             &mut left.uniforms
                         } else {
@@ -371,7 +434,7 @@ pub fn eye_control_panel(
                             iris_shape.outline = BezierOutline::circle(iris_radius_val);
                         }
                         let iris_editor_id = format!("iris_shape{side_suffix}");
-                        bezier_outline_editor(ui, &mut iris_shape.outline, &iris_editor_id);
+                        bezier_outline_editor(ui, &mut iris_shape.outline, &iris_editor_id, 1.0);
                         if ui.button("Reset Iris Circle").clicked() {
                             iris_shape.outline = BezierOutline::circle(iris_radius_val);
                         }
@@ -388,7 +451,7 @@ pub fn eye_control_panel(
                             pupil_shape.outline = BezierOutline::circle(pupil_radius_val);
                         }
                         let pupil_editor_id = format!("pupil_shape{side_suffix}");
-                        bezier_outline_editor(ui, &mut pupil_shape.outline, &pupil_editor_id);
+                        bezier_outline_editor(ui, &mut pupil_shape.outline, &pupil_editor_id, 1.0);
                         if ui.button("Reset Pupil Circle").clicked() {
                             pupil_shape.outline = BezierOutline::circle(pupil_radius_val);
                         }
@@ -405,45 +468,114 @@ pub fn eye_control_panel(
                 egui::CollapsingHeader::new("Highlight")
                     .default_open(false)
                     .show(ui, |ui| {
-                        ui.checkbox(show_highlight, "Show Highlight");
-
                         if let Some(from) = section_eye_selector(ui, link_highlight) {
                             apply_relink(from, left, right, sync_highlight);
                         }
 
                         let editing_left =
                             link_highlight.linked || link_highlight.active == Side::Left;
-                        let u = if editing_left {
-                            &mut left.uniforms
+                        show_toggles(
+                            ui,
+                            show_highlight,
+                            "Show Highlight",
+                            if editing_left { &mut *left } else { &mut *right },
+                            link_highlight,
+                            EyeUniforms::SHOW_HIGHLIGHT,
+                        );
+                        let side_suffix = if link_highlight.linked {
+                            ""
+                        } else if link_highlight.active == Side::Left {
+                            "_left"
                         } else {
-                            &mut right.uniforms
+                            "_right"
+                        };
+                        let (highlights, iris_radius) = if editing_left {
+                            (&mut left.highlights, left.uniforms.iris_radius)
+                        } else {
+                            (&mut right.highlights, right.uniforms.iris_radius)
                         };
 
-                        ui.add(
-                            egui::Slider::new(&mut u.highlight_radius, 0.005..=0.15)
-                                .text("Radius"),
-                        );
-                        ui.add(
-                            egui::Slider::new(&mut u.highlight_intensity, 0.0..=1.0)
-                                .text("Intensity"),
-                        );
-                        ui.add(
-                            egui::Slider::new(&mut u.highlight_blur, 0.0..=0.15)
-                                .text("Blur"),
-                        );
-                        ui.add(
-                            egui::Slider::new(&mut u.highlight_offset[0], -0.20..=0.20)
-                                .text("Offset X"),
-                        );
-                        ui.add(
-                            egui::Slider::new(&mut u.highlight_offset[1], -0.20..=0.20)
-                                .text("Offset Y"),
-                        );
-
-                        // Save values before dropping the mutable borrow
-                        let hl_offset = u.highlight_offset;
-                        let hl_radius = u.highlight_radius;
-                        let iris_radius = u.iris_radius;
+                        // --- Highlight list ---
+                        let selected_id = ui.id().with(format!("highlight_selected{side_suffix}"));
+                        let mut selected: usize =
+                            ui.memory(|m| m.data.get_temp(selected_id)).unwrap_or(0);
+                        ui.horizontal_wrapped(|ui| {
+                            for i in 0..highlights.len() {
+                                // Same index as the timeline's highlight tracks
+                                let text = format!("#{i}");
+                                if ui.selectable_label(selected == i, text).clicked() {
+                                    selected = i;
+                                }
+                            }
+                        });
+                        ui.horizontal(|ui| {
+                            let can_add = highlights.len() < EyeUniforms::MAX_HIGHLIGHTS;
+                            if ui
+                                .add_enabled(can_add, egui::Button::new("+ Highlight"))
+                                .clicked()
+                            {
+                                // Start from a smaller copy of the selected one
+                                let mut h = highlights.get(selected).cloned().unwrap_or_default();
+                                h.radius *= 0.5;
+                                h.offset = [-h.offset[0], h.offset[1] - 0.06];
+                                highlights.push(h);
+                                selected = highlights.len() - 1;
+                            }
+                            let can_remove = selected < highlights.len();
+                            if ui
+                                .add_enabled(can_remove, egui::Button::new("Remove"))
+                                .clicked()
+                            {
+                                highlights.remove(selected);
+                            }
+                        });
+                        selected = selected.min(highlights.len().saturating_sub(1));
+                        ui.memory_mut(|m| m.data.insert_temp(selected_id, selected));
+
+                        if let Some(h) = highlights.get_mut(selected) {
+                            egui::ComboBox::from_id_salt("highlight_shape")
+                                .selected_text(h.shape.label())
+                                .show_ui(ui, |ui| {
+                                    for shape in HighlightShape::ALL {
+                                        ui.selectable_value(&mut h.shape, shape, shape.label());
+                                    }
+                                });
+                            ui.add(egui::Slider::new(&mut h.radius, 0.005..=0.15).text("Radius"));
+                            let shaped = h.shape != HighlightShape::Circle;
+                            ui.add_enabled(
+                                shaped,
+                                egui::Slider::new(&mut h.aspect, 0.2..=3.0).text("Aspect"),
+                            );
+                            ui.add_enabled(
+                                shaped,
+                                egui::Slider::new(&mut h.rotation, -PI..=PI).text("Rotation"),
+                            );
+                            ui.horizontal(|ui| {
+                                ui.label("Color");
+                                color_edit_rgb(ui, &mut h.color);
+                            });
+                            ui.add(
+                                egui::Slider::new(&mut h.intensity, 0.0..=1.0).text("Intensity"),
+                            );
+                            ui.add(egui::Slider::new(&mut h.blur, 0.0..=0.15).text("Blur"));
+                            ui.add(
+                                egui::Slider::new(&mut h.parallax, -0.15..=0.15).text("Parallax"),
+                            );
+                            ui.add(
+                                egui::Slider::new(&mut h.offset[0], -0.20..=0.20).text("Offset X"),
+                            );
+                            ui.add(
+                                egui::Slider::new(&mut h.offset[1], -0.20..=0.20).text("Offset Y"),
+                            );
+                            if h.shape == HighlightShape::Bezier {
+                                ui.label("Outline (1.0 = radius)");
+                                let editor_id = format!("highlight_shape{side_suffix}_{selected}");
+                                bezier_outline_editor(ui, &mut h.outline, &editor_id, 0.45);
+                                if ui.button("Reset Sparkle").clicked() {
+                                    h.outline = BezierOutline::sparkle(1.0);
+                                }
+                            }
+                        }
 
                         // --- Highlight position preview (zoomed-in) ---
                         ui.separator();
@@ -491,32 +623,31 @@ pub fn eye_control_panel(
                             egui::Stroke::new(1.0, egui::Color32::from_rgba_premultiplied(80, 80, 120, 100)),
                         );
 
-                        // Draw highlight circle
-                        let hl_center = to_screen(hl_offset);
-                        let hl_screen_r = hl_radius * scale;
-                        painter.circle_filled(
-                            hl_center,
-                            hl_screen_r,
-                            egui::Color32::from_rgba_premultiplied(255, 255, 255, 180),
-                        );
-                        painter.circle_stroke(
-                            hl_center,
-                            hl_screen_r,
-                            egui::Stroke::new(1.5, egui::Color32::WHITE),
-                        );
+                        // Draw every highlight, the selected one on top
+                        let order = (0..highlights.len())
+                            .filter(|&i| i != selected)
+                            .chain((selected < highlights.len()).then_some(selected));
+                        for i in order {
+                            let points: Vec<egui::Pos2> = highlight_preview_points(&highlights[i])
+                                .into_iter()
+                                .map(to_screen)
+                                .collect();
+                            let stroke = if i == selected {
+                                egui::Stroke::new(1.5, egui::Color32::WHITE)
+                            } else {
+                                egui::Stroke::new(1.0, egui::Color32::from_gray(140))
+                            };
+                            painter.add(egui::Shape::closed_line(points, stroke));
+                        }
 
-                        // Drag to move highlight position
+                        // Drag to move the selected highlight
                         if response.dragged() {
-                            if let Some(pos) = response.interact_pointer_pos() {
-                                let u_drag = if editing_left {
-                                    &mut left.uniforms
-                                } else {
-                                    &mut right.uniforms
-                                };
-                                u_drag.highlight_offset[0] =
-                                    ((pos.x - center.x) / scale).clamp(-0.20, 0.20);
-                                u_drag.highlight_offset[1] =
-                                    (-(pos.y - center.y) / scale).clamp(-0.20, 0.20);
+                            if let (Some(pos), Some(h)) = (
+                                response.interact_pointer_pos(),
+                                highlights.get_mut(selected),
+                            ) {
+                                h.offset[0] = ((pos.x - center.x) / scale).clamp(-0.20, 0.20);
+                                h.offset[1] = (-(pos.y - center.y) / scale).clamp(-0.20, 0.20);
                             }
                         }
 
@@ -550,7 +681,7 @@ pub fn eye_control_panel(
                             "_right"
                         };
                         let editor_id = format!("eye_shape{side_suffix}");
-                        bezier_outline_editor(ui, &mut eye_shape.open, &editor_id);
+                        bezier_outline_editor(ui, &mut eye_shape.open, &editor_id, 1.0);
                         let old_arch = eye_shape.close_arch;
                         ui.add(
                             egui::Slider::new(&mut eye_shape.close_arch, -0.06..=0.06)
@@ -563,6 +694,43 @@ pub fn eye_control_panel(
                             eye_shape.open = BezierOutline::ellipse(0.28, 0.35);
                         }
 
+                        ui.separator();
+                        ui.label("Lower Lid Target");
+                        let lower_editor_id = format!("lower_lid{side_suffix}");
+                        bezier_outline_editor(ui, &mut eye_shape.lower_closed, &lower_editor_id, 1.0);
+                        if ui.button("Reset to Closed Shape").clicked() {
+                            eye_shape.lower_closed = eye_shape.closed.clone();
+                        }
+
+                        // --- Lid skin and crease ---
+                        ui.separator();
+                        let eyelid_shape = if editing_left {
+                            &mut left.eyelid_shape
+                        } else {
+                            &mut right.eyelid_shape
+                        };
+                        ui.horizontal(|ui| {
+                            ui.checkbox(&mut eyelid_shape.fill, "Lid Fill");
+                            color_edit_rgb(ui, &mut eyelid_shape.color);
+                        });
+                        ui.horizontal(|ui| {
+                            ui.checkbox(&mut eyelid_shape.crease, "Crease");
+                            color_edit_rgb(ui, &mut eyelid_shape.crease_color);
+                        });
+                        ui.add_enabled(
+                            eyelid_shape.crease,
+                            egui::Slider::new(&mut eyelid_shape.crease_thickness, 0.002..=0.03)
+                                .text("Crease Thickness"),
+                        );
+                        ui.add_enabled(
+                            eyelid_shape.crease,
+                            egui::Slider::new(&mut eyelid_shape.crease_offset, 0.0..=0.2)
+                                .text("Crease Offset"),
+                        );
+                        if ui.button("Reset Lid").clicked() {
+                            *eyelid_shape = EyelidShape::default();
+                        }
+
                         // Sync linked fields
                         if link_shape.linked {
                             sync_shape(&*left, right);
@@ -579,10 +747,16 @@ pub fn eye_control_panel(
                             apply_relink(from, left, right, sync_eyebrow);
                         }
 
-                        ui.checkbox(show_eyebrow, "Show Eyebrow");
-
                         let editing_left =
                             link_eyebrow.linked || link_eyebrow.active == Side::Left;
+                        show_toggles(
+                            ui,
+                            show_eyebrow,
+                            "Show Eyebrow",
+                            if editing_left { &mut *left } else { &mut *right },
+                            link_eyebrow,
+                            EyeUniforms::SHOW_EYEBROW,
+                        );
                         let eyebrow_shape = if editing_left {
                             &mut left.eyebrow_shape
                         } else {
@@ -657,10 +831,16 @@ pub fn eye_control_panel(
                             apply_relink(from, left, right, sync_eyelash);
                         }
 
-                        ui.checkbox(show_eyelash, "Show Eyelash");
-
                         let editing_left =
                             link_eyelash.linked || link_eyelash.active == Side::Left;
+                        show_toggles(
+                            ui,
+                            show_eyelash,
+                            "Show Eyelash",
+                            if editing_left { &mut *left } else { &mut *right },
+                            link_eyelash,
+                            EyeUniforms::SHOW_EYELASH,
+                        );
                         let eyelash_shape = if editing_left {
                             &mut left.eyelash_shape
                         } else {
@@ -703,6 +883,12 @@ pub fn eye_control_panel(
                         });
                         right.uniforms.bg_color = left.uniforms.bg_color;
 
+                        let mut transparent = left.uniforms.bg_alpha < 0.5;
+                        if ui.checkbox(&mut transparent, "Transparent BG").changed() {
+                            left.uniforms.bg_alpha = if transparent { 0.0 } else { 1.0 };
+                        }
+                        right.uniforms.bg_alpha = left.uniforms.bg_alpha;
+
                         ui.horizontal(|ui| {
                             ui.label("Sclera Color");
                             color_edit_rgb(ui, &mut left.uniforms.sclera_color);
@@ -712,6 +898,164 @@ pub fn eye_control_panel(
 
                 ui.separator();
 
+                // --- Blink timing ---
+                egui::CollapsingHeader::new("Blink")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        let time = left.uniforms.time;
+                        ui.horizontal(|ui| {
+                            if ui.button("Blink").clicked() {
+                                blink_animation.trigger(time);
+                            }
+                            for eye in WinkEye::ALL {
+                                if ui.button(format!("Wink {}", eye.label())).clicked() {
+                                    blink_animation.wink(time, eye);
+                                }
+                            }
+                        });
+                        let mut rest = blink_animation.rest_value();
+                        if ui
+                            .add(egui::Slider::new(&mut rest, 0.0..=0.8).text("Rest Eyelid"))
+                            .changed()
+                        {
+                            blink_animation.set_rest_value(rest);
+                        }
+                        range_sliders(
+                            ui,
+                            "Duration (s)",
+                            &mut blink_animation.duration_min,
+                            &mut blink_animation.duration_max,
+                            0.05..=1.0,
+                        );
+                        range_sliders(
+                            ui,
+                            "Close Ratio",
+                            &mut blink_animation.close_ratio_min,
+                            &mut blink_animation.close_ratio_max,
+                            0.05..=0.95,
+                        );
+                        range_sliders(
+                            ui,
+                            "Interval (s)",
+                            &mut blink_animation.interval_min,
+                            &mut blink_animation.interval_max,
+                            0.5..=30.0,
+                        );
+                        ui.add(
+                            egui::Slider::new(&mut blink_animation.cluster_chance, 0.0..=1.0)
+                                .text("Cluster Chance"),
+                        );
+                        ui.add(
+                            egui::Slider::new(&mut blink_animation.partial_chance, 0.0..=1.0)
+                                .text("Partial Chance"),
+                        );
+                        range_sliders(
+                            ui,
+                            "Partial Depth",
+                            &mut blink_animation.partial_depth_min,
+                            &mut blink_animation.partial_depth_max,
+                            0.0..=1.0,
+                        );
+                        ui.add(
+                            egui::Slider::new(&mut blink_animation.saccade_blink_threshold, 0.1..=2.0)
+                                .text("Saccade Blink Threshold"),
+                        );
+                        ui.add(
+                            egui::Slider::new(&mut blink_animation.saccade_blink_chance, 0.0..=1.0)
+                                .text("Saccade Blink Chance"),
+                        );
+                        ui.add(
+                            egui::Slider::new(&mut blink_animation.lid_follow, 0.0..=0.5)
+                                .text("Lid Follow"),
+                        );
+                    });
+
+                // --- Microsaccades ---
+                egui::CollapsingHeader::new("Microsaccade")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        ui.add(
+                            egui::Slider::new(&mut microsaccade_animation.rate, 0.1..=4.0)
+                                .text("Rate (/s)"),
+                        );
+                        range_sliders(
+                            ui,
+                            "Amplitude",
+                            &mut microsaccade_animation.amplitude_min,
+                            &mut microsaccade_animation.amplitude_max,
+                            0.0..=0.1,
+                        );
+                        ui.add(
+                            egui::Slider::new(&mut microsaccade_animation.duration, 0.01..=0.2)
+                                .text("Duration (s)"),
+                        );
+                        ui.add(
+                            egui::Slider::new(&mut microsaccade_animation.center_bias, 0.0..=1.0)
+                                .text("Center Bias"),
+                        );
+                        ui.add(
+                            egui::Slider::new(&mut microsaccade_animation.drift_rate, 0.0..=5.0)
+                                .text("Drift Back (/s)"),
+                        );
+                    });
+
+                // --- Pupil Dynamics ---
+                egui::CollapsingHeader::new("Pupil")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        ui.checkbox(&mut pupil_dynamics.enabled, "Animate pupil size");
+                        ui.horizontal(|ui| {
+                            let mut external = pupil_dynamics.light_level.is_some();
+                            if ui.checkbox(&mut external, "Light").changed() {
+                                pupil_dynamics.light_level = external
+                                    .then(|| luminance(left.uniforms.bg_color));
+                            }
+                            match &mut pupil_dynamics.light_level {
+                                Some(level) => {
+                                    ui.add(egui::Slider::new(level, 0.0..=1.0));
+                                }
+                                None => {
+                                    ui.label(format!(
+                                        "from background ({:.2})",
+                                        luminance(left.uniforms.bg_color)
+                                    ));
+                                }
+                            }
+                        });
+                        ui.add(
+                            egui::Slider::new(&mut pupil_dynamics.arousal, 0.0..=1.0)
+                                .text("Arousal"),
+                        );
+                        range_sliders(
+                            ui,
+                            "Scale (bright/dark)",
+                            &mut pupil_dynamics.min_scale,
+                            &mut pupil_dynamics.max_scale,
+                            0.2..=2.0,
+                        );
+                        ui.add(
+                            egui::Slider::new(&mut pupil_dynamics.arousal_gain, 0.0..=1.0)
+                                .text("Arousal Gain"),
+                        );
+                        ui.add(
+                            egui::Slider::new(&mut pupil_dynamics.constriction_time, 0.05..=2.0)
+                                .text("Constriction (s)"),
+                        );
+                        ui.add(
+                            egui::Slider::new(&mut pupil_dynamics.dilation_time, 0.05..=5.0)
+                                .text("Dilation (s)"),
+                        );
+                        ui.add(
+                            egui::Slider::new(&mut pupil_dynamics.hippus_amplitude, 0.0..=0.2)
+                                .text("Hippus Amplitude"),
+                        );
+                        ui.add(
+                            egui::Slider::new(&mut pupil_dynamics.hippus_frequency, 0.01..=1.0)
+                                .text("Hippus (Hz)"),
+                        );
+                        ui.label(format!("Scale: {:.3}", pupil_dynamics.scale()));
+                    });
+
                 // --- Nod Animation ---
                 egui::CollapsingHeader::new("Nod")
                     .default_open(false)
@@ -805,8 +1149,21 @@ pub fn eye_control_panel(
 
                 ui.separator();
 
+                // --- Expressions ---
+                expression_panel(ui, expressions, left.uniforms.time);
+
+                ui.separator();
+
                 // --- Timeline ---
-                timeline_panel(ui, timeline_player, left, right, *focus_distance, &mut actions);
+                timeline_panel(
+                    ui,
+                    timeline_player,
+                    left,
+                    right,
+                    *focus_distance,
+                    nod_animation,
+                    &mut actions,
+                );
 
                 ui.separator();
 
@@ -838,6 +1195,68 @@ pub fn eye_control_panel(
     actions
 }
 
+/// Min/max slider pair that keeps `min <= max`.
+fn range_sliders(
+    ui: &mut egui::Ui,
+    label: &str,
+    min: &mut f32,
+    max: &mut f32,
+    range: std::ops::RangeInclusive<f32>,
+) {
+    if ui
+        .add(egui::Slider::new(min, range.clone()).text(format!("{label} min")))
+        .changed()
+    {
+        *max = max.max(*min);
+    }
+    if ui
+        .add(egui::Slider::new(max, range).text(format!("{label} max")))
+        .changed()
+    {
+        *min = min.min(*max);
+    }
+}
+
+// ============================================================
+// Expression panel
+// ============================================================
+
+/// Fade time used when toggling an expression from the panel.
+const EXPRESSION_TRANSITION: f32 = 0.3;
+
+fn expression_panel(ui: &mut egui::Ui, expressions: &mut ExpressionMixer, time: f32) {
+    egui::CollapsingHeader::new("Expressions")
+        .default_open(false)
+        .show(ui, |ui| {
+            let names: Vec<String> = expressions
+                .library
+                .expressions
+                .iter()
+                .map(|e| e.name.clone())
+                .collect();
+            for name in names {
+                ui.horizontal(|ui| {
+                    let current = expressions.weight(&name);
+                    let mut weight = current;
+                    if ui
+                        .add(egui::Slider::new(&mut weight, 0.0..=1.0).text(&name))
+                        .changed()
+                    {
+                        let _ = expressions.set(&name, weight, 0.0, time);
+                    }
+                    let target = if current > 0.5 { 0.0 } else { 1.0 };
+                    let label = if current > 0.5 { "Off" } else { "On" };
+                    if ui.small_button(label).clicked() {
+                        let _ = expressions.set(&name, target, EXPRESSION_TRANSITION, time);
+                    }
+                });
+            }
+            if ui.button("Clear").clicked() {
+                expressions.clear(EXPRESSION_TRANSITION, time);
+            }
+        });
+}
+
 // ============================================================
 // Timeline panel
 // ============================================================
@@ -848,6 +1267,7 @@ fn timeline_panel(
     left: &mut EyeSideState,
     right: &mut EyeSideState,
     focus_distance: f32,
+    nod_animation: &NodAnimation,
     actions: &mut GuiActions,
 ) {
     egui::CollapsingHeader::new("Timeline")
@@ -855,184 +1275,307 @@ fn timeline_panel(
         .show(ui, |ui| {
             let total = player.timeline.total_duration();
 
+            let wall = left.uniforms.time;
+
             // Transport controls
             ui.horizontal(|ui| {
                 if player.is_playing() {
-                    if ui.button("Stop").clicked() {
-                        player.stop();
+                    if ui.button("Pause").clicked() {
+                        player.pause();
                     }
-                } else if ui.button("Play").clicked() {
-                    if !player.timeline.keyframes.is_empty() {
-                        let wall = left.uniforms.time;
+                } else {
+                    let label = if player.is_paused() { "Resume" } else { "Play" };
+                    if ui.button(label).clicked() && !player.timeline.is_empty() {
                         player.play(wall);
                     }
                 }
+                if ui
+                    .add_enabled(player.is_active(), egui::Button::new("Stop"))
+                    .clicked()
+                {
+                    player.stop();
+                }
                 ui.checkbox(&mut player.looping, "Loop");
+                ui.add_enabled(
+                    player.looping,
+                    egui::Checkbox::new(&mut player.ping_pong, "Ping-pong"),
+                );
             });
 
-            // Progress bar
+            let mut speed = player.speed();
+            if ui
+                .add(
+                    egui::Slider::new(&mut speed, 0.1..=4.0)
+                        .logarithmic(true)
+                        .text("Speed"),
+                )
+                .changed()
+            {
+                player.set_speed(speed, wall);
+            }
+
+            // Scrubber: dragging pauses on the chosen frame
             if total > 0.0 {
-                let progress = if player.is_playing() {
-                    player.current_time() / total
-                } else {
-                    0.0
-                };
-                let bar = egui::ProgressBar::new(progress.clamp(0.0, 1.0))
-                    .text(format!("{:.1}s / {:.1}s", player.current_time(), total));
-                ui.add(bar);
+                let mut t = player.current_time();
+                let response = ui.add(
+                    egui::Slider::new(&mut t, 0.0..=total)
+                        .text(format!("/ {total:.1}s"))
+                        .fixed_decimals(2),
+                );
+                if response.changed() {
+                    player.scrub(t, wall);
+                }
             }
 
             ui.separator();
-            ui.label("Keyframes:");
+            timeline_layers_edit(ui, &mut player.timeline.layers);
 
-            // Keyframe list
-            let kf_count = player.timeline.keyframes.len();
-            let mut delete_idx: Option<usize> = None;
+            ui.separator();
+            let playhead = player.current_time();
+            let current = TimelinePose::from_state(left, right, focus_distance);
+            ui.label(format!("Tracks (keys at playhead {playhead:.2}s):"));
 
+            // Track list
+            let mut delete_track: Option<usize> = None;
             egui::ScrollArea::vertical()
                 .max_height(150.0)
-                .id_salt("timeline_keyframes")
+                .id_salt("timeline_tracks")
                 .show(ui, |ui| {
-                    for i in 0..kf_count {
-                        let kf = &player.timeline.keyframes[i];
-                        let is_selected = player.selected_keyframe == Some(i);
-                        let blink_mark = if kf.blink { " [B]" } else { "" };
-                        let text = format!(
-                            "{}: \"{}\" @{:.2}s  {:.2}s  {}{}",
-                            i,
-                            kf.label,
-                            kf.fire_time,
-                            kf.transition_duration,
-                            kf.easing.label(),
-                            blink_mark
-                        );
+                    let timeline = &player.timeline;
+                    for (i, track) in timeline.tracks.iter().enumerate() {
+                        let is_selected = player.selected_track == Some(i);
+                        let mut text = format!("{}  [{} keys]", track.label(), track.keys.len());
+                        // Layered timelines skip tracks on unclaimed channels
+                        let channel = track.property.channel();
+                        if timeline.is_layered() && timeline.layer_for(channel).is_none() {
+                            text.push_str(&format!("  (no {} layer)", channel.label()));
+                        }
                         if ui.selectable_label(is_selected, &text).clicked() {
-                            player.selected_keyframe = Some(i);
+                            player.selected_track = Some(i);
                         }
                     }
                 });
 
-            // Add keyframe button
-            if ui.button("+ Add Keyframe").clicked() {
-                let fire_time = if player.timeline.keyframes.is_empty() {
-                    0.0
-                } else {
-                    total + 1.0
-                };
-                let kf = TimelineKeyframe {
-                    label: format!("keyframe {}", kf_count),
-                    fire_time,
-                    transition_duration: 0.5,
-                    easing: TimelineEasing::default(),
-                    blink: false,
-                    left: EyeSideConfig::from(&*left),
-                    right: EyeSideConfig::from(&*right),
-                    global: TimelineGlobalConfig {
-                        bg_color: left.uniforms.bg_color,
-                        eye_separation: left.uniforms.eye_separation,
-                        max_angle: left.uniforms.max_angle,
-                        eye_angle: left.uniforms.eye_angle,
-                        focus_distance,
-                    },
-                };
-                player.timeline.keyframes.push(kf);
-                player.selected_keyframe = Some(kf_count);
-                player.timeline.sort();
-                // Re-find selected after sort
-                if let Some(sel) = player.selected_keyframe {
-                    if sel < player.timeline.keyframes.len() {
-                        // Keep selection, it may have moved
-                        let target_label =
-                            format!("keyframe {}", kf_count);
-                        for (j, k) in player.timeline.keyframes.iter().enumerate() {
-                            if k.label == target_label {
-                                player.selected_keyframe = Some(j);
-                                break;
-                            }
+            // Add track: property + eye, keyed with the current value
+            let draft_id = ui.id().with("timeline_track_draft");
+            let mut draft: TrackDraft = ui.memory(|m| m.data.get_temp(draft_id)).unwrap_or_default();
+            ui.horizontal(|ui| {
+                egui::ComboBox::from_id_salt("timeline_track_property")
+                    .selected_text(draft.property.with_index(0).label())
+                    .show_ui(ui, |ui| {
+                        let indexed = TrackProperty::HIGHLIGHTS
+                            .into_iter()
+                            .chain(TrackProperty::ANCHORS);
+                        for p in TrackProperty::FIXED.into_iter().chain(indexed) {
+                            let index = draft.property.index().unwrap_or(0);
+                            let p = p.with_index(index.min(max_track_index(p)));
+                            let text = p.with_index(0).label();
+                            ui.selectable_value(&mut draft.property, p, text);
                         }
-                    }
+                    });
+                if let Some(mut index) = draft.property.index() {
+                    let max = max_track_index(draft.property);
+                    ui.add(egui::DragValue::new(&mut index).range(0..=max));
+                    draft.property = draft.property.with_index(index);
                 }
+                if !draft.property.is_global() {
+                    egui::ComboBox::from_id_salt("timeline_track_eye")
+                        .selected_text(draft.eye.label())
+                        .width(60.0)
+                        .show_ui(ui, |ui| {
+                            for e in TrackEye::ALL {
+                                ui.selectable_value(&mut draft.eye, e, e.label());
+                            }
+                        });
+                }
+            });
+            if ui.button("+ Add Track").clicked()
+                && draft.property.value_in(&current, draft.eye).is_some()
+            {
+                let timeline = &mut player.timeline;
+                timeline.track_mut(draft.property, draft.eye).key_pose(
+                    playhead,
+                    &current,
+                    TimelineEasing::default(),
+                );
+                let eye = if draft.property.is_global() { TrackEye::Both } else { draft.eye };
+                player.selected_track = timeline
+                    .tracks
+                    .iter()
+                    .position(|t| t.property == draft.property && t.eye == eye);
             }
+            ui.memory_mut(|m| m.data.insert_temp(draft_id, draft));
 
-            // Selected keyframe editor
-            if let Some(sel_idx) = player.selected_keyframe {
-                if sel_idx < player.timeline.keyframes.len() {
+            // Selected track editor
+            if let Some(sel_idx) = player.selected_track {
+                if sel_idx < player.timeline.tracks.len() {
                     ui.separator();
-                    ui.label(format!("Selected: #{}", sel_idx));
-
-                    let kf = &mut player.timeline.keyframes[sel_idx];
-
-                    ui.horizontal(|ui| {
-                        ui.label("Label:");
-                        ui.text_edit_singleline(&mut kf.label);
-                    });
-                    ui.add(
-                        egui::Slider::new(&mut kf.fire_time, 0.0..=30.0)
-                            .text("Fire Time (s)")
-                            .clamping(egui::SliderClamping::Never),
-                    );
-                    ui.add(
-                        egui::Slider::new(&mut kf.transition_duration, 0.0..=10.0)
-                            .text("Transition (s)")
-                            .clamping(egui::SliderClamping::Never),
-                    );
-
-                    // Easing dropdown
-                    egui::ComboBox::from_label("Easing")
-                        .selected_text(kf.easing.label())
-                        .show_ui(ui, |ui| {
-                            for e in TimelineEasing::ALL {
-                                ui.selectable_value(&mut kf.easing, e, e.label());
+                    let track = &mut player.timeline.tracks[sel_idx];
+                    ui.label(format!("Selected: {}", track.label()));
+
+                    let is_color = track.property.label().contains("Color");
+                    let mut delete_key: Option<usize> = None;
+                    egui::ScrollArea::vertical()
+                        .max_height(200.0)
+                        .id_salt("timeline_track_keys")
+                        .show(ui, |ui| {
+                            let times: Vec<f32> = track.keys.iter().map(|key| key.time).collect();
+                            for (k, key) in track.keys.iter_mut().enumerate() {
+                                // Length of the segment arriving at this key
+                                let span = k
+                                    .checked_sub(1)
+                                    .map_or(1.0, |prev| (key.time - times[prev]).max(0.01));
+                                ui.push_id(k, |ui| {
+                                    ui.horizontal(|ui| {
+                                        ui.add(
+                                            egui::DragValue::new(&mut key.time)
+                                                .speed(0.01)
+                                                .range(0.0..=f32::MAX)
+                                                .suffix("s"),
+                                        );
+                                        timeline_easing_combo(ui, &mut key.easing);
+                                        if ui.small_button("x").clicked() {
+                                            delete_key = Some(k);
+                                        }
+                                    });
+                                    timeline_easing_params(ui, &mut key.easing, span);
+                                    track_value_edit(ui, &mut key.value, is_color);
+                                });
                             }
                         });
-
-                    ui.checkbox(&mut kf.blink, "Blink");
+                    if let Some(k) = delete_key {
+                        track.keys.remove(k);
+                    }
 
                     ui.horizontal(|ui| {
-                        // Preview: apply this keyframe's config to the editor
-                        if ui.button("Preview").clicked() {
-                            kf.left.apply_to(left);
-                            kf.right.apply_to(right);
-                        }
-
-                        // Capture: overwrite this keyframe with current state
-                        if ui.button("Capture").clicked() {
-                            kf.left = EyeSideConfig::from(&*left);
-                            kf.right = EyeSideConfig::from(&*right);
-                            kf.global = TimelineGlobalConfig {
-                                bg_color: left.uniforms.bg_color,
-                                eye_separation: left.uniforms.eye_separation,
-                                max_angle: left.uniforms.max_angle,
-                                eye_angle: left.uniforms.eye_angle,
-                                focus_distance,
-                            };
+                        // Key: store the editor's current value at the playhead
+                        if ui.button("Key Current").clicked() {
+                            track.key_pose(playhead, &current, TimelineEasing::default());
                         }
 
-                        // Load from config file
-                        if ui.button("Load File").clicked() {
-                            actions.timeline_load_file_into_keyframe = Some(sel_idx);
+                        // Preview: show the track's value at the playhead in the editor
+                        if ui.button("Preview").clicked() {
+                            if let Some(value) = track.sample(playhead) {
+                                let mut pose = current.clone();
+                                track.set_in(&mut pose, &value);
+                                let mut focus = focus_distance;
+                                pose.apply_to_state(left, right, &mut focus);
+                            }
                         }
 
-                        if ui.button("Delete").clicked() {
-                            delete_idx = Some(sel_idx);
+                        if ui.button("Delete Track").clicked() {
+                            delete_track = Some(sel_idx);
                         }
                     });
                 }
             }
 
             // Handle deferred delete
-            if let Some(idx) = delete_idx {
-                player.timeline.keyframes.remove(idx);
-                if player.timeline.keyframes.is_empty() {
-                    player.selected_keyframe = None;
-                } else if let Some(sel) = player.selected_keyframe {
-                    if sel >= player.timeline.keyframes.len() {
-                        player.selected_keyframe = Some(player.timeline.keyframes.len() - 1);
-                    }
-                }
+            if let Some(idx) = delete_track {
+                player.timeline.tracks.remove(idx);
+                player.selected_track = None;
+            }
+
+            // Load from config file: key every property that differs from the editor
+            if ui.button("Key Config File at Playhead").clicked() {
+                actions.timeline_key_config_file = true;
             }
 
-            // Re-sort after edits (fire_time may have changed)
+            // Event track
+            ui.separator();
+            ui.label("Events:");
+            let mut delete_event: Option<usize> = None;
+            for (i, event) in player.timeline.events.iter_mut().enumerate() {
+                ui.push_id(("event", i), |ui| {
+                    ui.horizontal_wrapped(|ui| {
+                        ui.add(
+                            egui::DragValue::new(&mut event.time)
+                                .speed(0.01)
+                                .range(0.0..=f32::MAX)
+                                .suffix("s"),
+                        );
+                        ui.label(event.action.label());
+                        match &mut event.action {
+                            TimelineAction::Marker { label } => {
+                                ui.add(egui::TextEdit::singleline(label).desired_width(100.0));
+                            }
+                            TimelineAction::Blink {
+                                close_duration,
+                                hold_duration,
+                                open_duration,
+                            } => blink_timing_edit(ui, close_duration, hold_duration, open_duration),
+                            TimelineAction::Wink {
+                                eye,
+                                close_duration,
+                                hold_duration,
+                                open_duration,
+                            } => {
+                                egui::ComboBox::from_id_salt("wink_eye")
+                                    .selected_text(eye.label())
+                                    .width(60.0)
+                                    .show_ui(ui, |ui| {
+                                        for e in WinkEye::ALL {
+                                            ui.selectable_value(eye, e, e.label());
+                                        }
+                                    });
+                                blink_timing_edit(ui, close_duration, hold_duration, open_duration);
+                            }
+                            TimelineAction::Nod { overrides } => {
+                                let mut custom = overrides.is_some();
+                                if ui.checkbox(&mut custom, "Custom").changed() {
+                                    // Start from the current nod settings
+                                    *overrides = custom.then(|| NodConfig::from(&*nod_animation));
+                                }
+                                if let Some(config) = overrides {
+                                    ui.label("amount");
+                                    ui.add(egui::DragValue::new(&mut config.amount).speed(0.01));
+                                    ui.label("duration");
+                                    ui.add(
+                                        egui::DragValue::new(&mut config.duration)
+                                            .speed(0.01)
+                                            .range(0.05..=5.0)
+                                            .suffix("s"),
+                                    );
+                                }
+                            }
+                            TimelineAction::Microsaccade => {}
+                        }
+                        if ui.small_button("x").clicked() {
+                            delete_event = Some(i);
+                        }
+                    });
+                });
+            }
+            if let Some(i) = delete_event {
+                player.timeline.events.remove(i);
+            }
+            ui.horizontal(|ui| {
+                let mut new_action = None;
+                if ui.button("+ Marker").clicked() {
+                    let label = format!("marker {}", player.timeline.events.len());
+                    new_action = Some(TimelineAction::Marker { label });
+                }
+                if ui.button("+ Blink").clicked() {
+                    new_action = Some(TimelineAction::blink());
+                }
+                if ui.button("+ Wink").clicked() {
+                    new_action = Some(TimelineAction::wink(WinkEye::Left));
+                }
+                if ui.button("+ Nod").clicked() {
+                    new_action = Some(TimelineAction::Nod { overrides: None });
+                }
+                if ui.button("+ Microsaccade").clicked() {
+                    new_action = Some(TimelineAction::Microsaccade);
+                }
+                if let Some(action) = new_action {
+                    player.timeline.events.push(TimelineEvent {
+                        time: playhead,
+                        action,
+                    });
+                }
+            });
+
+            // Re-sort after edits (key and event times may have changed)
             player.timeline.sort();
 
             ui.separator();
@@ -1047,12 +1590,146 @@ fn timeline_panel(
         });
 }
 
+/// Property and eye picked for the next "+ Add Track".
+#[derive(Clone, Copy, Default)]
+struct TrackDraft {
+    property: TrackProperty,
+    eye: TrackEye,
+}
+
+/// Channel layers of the timeline. Realtime animation keeps running on the
+/// channels no full-weight override layer claims.
+/// Highest index offered for a new track of `property`'s kind.
+fn max_track_index(property: TrackProperty) -> usize {
+    if property.is_highlight() {
+        EyeUniforms::MAX_HIGHLIGHTS - 1
+    } else {
+        5
+    }
+}
+
+fn timeline_layers_edit(ui: &mut egui::Ui, layers: &mut Vec<TimelineLayer>) {
+    if layers.is_empty() {
+        ui.label("Layers: none (timeline drives every channel)");
+    } else {
+        ui.label("Layers:");
+    }
+    let mut delete_layer: Option<usize> = None;
+    for (i, layer) in layers.iter_mut().enumerate() {
+        ui.push_id(("timeline_layer", i), |ui| {
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::TextEdit::singleline(&mut layer.name)
+                        .desired_width(80.0)
+                        .hint_text("name"),
+                );
+                egui::ComboBox::from_id_salt("blend")
+                    .selected_text(layer.blend.label())
+                    .width(70.0)
+                    .show_ui(ui, |ui| {
+                        for b in LayerBlend::ALL {
+                            ui.selectable_value(&mut layer.blend, b, b.label());
+                        }
+                    });
+                ui.add(egui::Slider::new(&mut layer.weight, 0.0..=1.0).text("Weight"));
+                if ui.small_button("x").clicked() {
+                    delete_layer = Some(i);
+                }
+            });
+            ui.horizontal(|ui| {
+                for c in AnimationChannel::ALL {
+                    let mut on = layer.channels.contains(&c);
+                    if ui.checkbox(&mut on, c.label()).changed() {
+                        if on {
+                            layer.channels.push(c);
+                        } else {
+                            layer.channels.retain(|&other| other != c);
+                        }
+                    }
+                }
+            });
+        });
+    }
+    if let Some(i) = delete_layer {
+        layers.remove(i);
+    }
+    if ui.button("+ Layer").clicked() {
+        layers.push(TimelineLayer {
+            name: format!("layer {}", layers.len() + 1),
+            ..TimelineLayer::default()
+        });
+    }
+}
+
+fn blink_timing_edit(
+    ui: &mut egui::Ui,
+    close_duration: &mut Option<f32>,
+    hold_duration: &mut f32,
+    open_duration: &mut Option<f32>,
+) {
+    optional_duration_edit(ui, "close", close_duration, 0.1);
+    ui.label("hold");
+    ui.add(
+        egui::DragValue::new(hold_duration)
+            .speed(0.01)
+            .range(0.0..=5.0)
+            .suffix("s"),
+    );
+    optional_duration_edit(ui, "open", open_duration, 0.2);
+}
+
+/// Duration that is either custom or left to the animation's own default.
+fn optional_duration_edit(ui: &mut egui::Ui, label: &str, value: &mut Option<f32>, initial: f32) {
+    let mut custom = value.is_some();
+    if ui.checkbox(&mut custom, label).changed() {
+        *value = custom.then_some(initial);
+    }
+    if let Some(v) = value {
+        ui.add(egui::DragValue::new(v).speed(0.005).range(0.01..=2.0).suffix("s"));
+    }
+}
+
+fn value_drag(v: &mut f32) -> egui::DragValue<'_> {
+    egui::DragValue::new(v).speed(0.005).max_decimals(3)
+}
+
+fn track_value_edit(ui: &mut egui::Ui, value: &mut TrackValue, is_color: bool) {
+    ui.horizontal(|ui| match value {
+        TrackValue::Scalar(v) => {
+            ui.add(value_drag(v));
+        }
+        TrackValue::Vec2(v) => {
+            ui.add(value_drag(&mut v[0]));
+            ui.add(value_drag(&mut v[1]));
+        }
+        TrackValue::Vec3(v) if is_color => color_edit_rgb(ui, v),
+        TrackValue::Vec3(v) => {
+            for c in v.iter_mut() {
+                ui.add(value_drag(c));
+            }
+        }
+        TrackValue::Anchor(a) => {
+            ui.label("pos");
+            ui.add(value_drag(&mut a.position[0]));
+            ui.add(value_drag(&mut a.position[1]));
+            ui.label("in");
+            ui.add(value_drag(&mut a.handle_in[0]));
+            ui.add(value_drag(&mut a.handle_in[1]));
+            ui.label("out");
+            ui.add(value_drag(&mut a.handle_out[0]));
+            ui.add(value_drag(&mut a.handle_out[1]));
+        }
+    });
+}
+
 // ============================================================
 // Interactive 2D Bezier curve editor (generic)
 // ============================================================
 
-// Drag target encoding: 0-3 = anchor[i], 4-7 = handle_in[i-4], 8-11 = handle_out[i-8]
+// Drag target encoding, with N = DRAG_STRIDE:
+// 0..N = anchor[i], N..2N = handle_in[i-N], 2N..3N = handle_out[i-2N]
 const DRAG_NONE: i32 = -1;
+const DRAG_STRIDE: i32 = BezierOutline::MAX_ANCHORS as i32;
 
 // ============================================================
 // Blender-style modal editing state
@@ -1081,11 +1758,11 @@ impl BezierAnchorSnapshot {
     }
 }
 
-fn snapshot_all(anchors: &[BezierAnchor; 4]) -> [BezierAnchorSnapshot; 4] {
-    core::array::from_fn(|i| BezierAnchorSnapshot::from_anchor(&anchors[i]))
+fn snapshot_all(anchors: &[BezierAnchor]) -> Vec<BezierAnchorSnapshot> {
+    anchors.iter().map(BezierAnchorSnapshot::from_anchor).collect()
 }
 
-fn restore_all(snaps: &[BezierAnchorSnapshot; 4], anchors: &mut [BezierAnchor; 4]) {
+fn restore_all(snaps: &[BezierAnchorSnapshot], anchors: &mut [BezierAnchor]) {
     for (s, a) in snaps.iter().zip(anchors.iter_mut()) {
         s.restore_to(a);
     }
@@ -1093,16 +1770,16 @@ fn restore_all(snaps: &[BezierAnchorSnapshot; 4], anchors: &mut [BezierAnchor; 4
 
 /// Compute the screen-space centroid of selected anchors.
 fn centroid_screen(
-    anchors: &[BezierAnchor; 4],
-    selected: &[bool; 4],
+    anchors: &[BezierAnchor],
+    selected: &[bool],
     to_screen: &impl Fn([f32; 2]) -> egui::Pos2,
 ) -> egui::Pos2 {
     let mut sx = 0.0f32;
     let mut sy = 0.0f32;
     let mut n = 0u32;
-    for i in 0..4 {
-        if selected[i] {
-            let scr = to_screen(anchors[i].position);
+    for (a, &sel) in anchors.iter().zip(selected) {
+        if sel {
+            let scr = to_screen(a.position);
             sx += scr.x;
             sy += scr.y;
             n += 1;
@@ -1117,16 +1794,16 @@ fn centroid_screen(
 
 /// Compute the eye-space centroid of selected anchors from snapshots.
 fn centroid_eye_space(
-    snaps: &[BezierAnchorSnapshot; 4],
-    selected: &[bool; 4],
+    snaps: &[BezierAnchorSnapshot],
+    selected: &[bool],
 ) -> [f32; 2] {
     let mut sx = 0.0f32;
     let mut sy = 0.0f32;
     let mut n = 0u32;
-    for i in 0..4 {
-        if selected[i] {
-            sx += snaps[i].position[0];
-            sy += snaps[i].position[1];
+    for (snap, &sel) in snaps.iter().zip(selected) {
+        if sel {
+            sx += snap.position[0];
+            sy += snap.position[1];
             n += 1;
         }
     }
@@ -1145,15 +1822,15 @@ enum BezierEditMode {
     Idle,
     Grab {
         /// Which anchors are being grabbed.
-        selected: [bool; 4],
-        original_anchors: [BezierAnchorSnapshot; 4],
+        selected: Vec<bool>,
+        original_anchors: Vec<BezierAnchorSnapshot>,
         /// Mouse position (screen coords) at the moment G was pressed.
         grab_origin: [f32; 2],
     },
     Scale {
         /// Which anchors are being scaled.
-        selected: [bool; 4],
-        original_anchors: [BezierAnchorSnapshot; 4],
+        selected: Vec<bool>,
+        original_anchors: Vec<BezierAnchorSnapshot>,
         /// Pivot point in screen coords (centroid of selected anchors).
         pivot_screen_pos: [f32; 2],
         initial_mouse_dist: f32,
@@ -1162,8 +1839,8 @@ enum BezierEditMode {
     },
     Rotate {
         /// Which anchors are being rotated.
-        selected: [bool; 4],
-        original_anchors: [BezierAnchorSnapshot; 4],
+        selected: Vec<bool>,
+        original_anchors: Vec<BezierAnchorSnapshot>,
         /// Pivot point in screen coords (centroid of selected anchors).
         pivot_screen_pos: [f32; 2],
         initial_mouse_angle: f32,
@@ -1174,7 +1851,8 @@ enum BezierEditMode {
 struct BezierEditorState {
     drag_idx: i32,
     /// Which anchors are selected (anchor-level selection).
-    selected_anchors: [bool; 4],
+    /// Kept the same length as the outline's anchors.
+    selected_anchors: Vec<bool>,
     mode: BezierEditMode,
     /// Skip the next click-to-select (set after modal confirm via click).
     skip_click_select: bool,
@@ -1186,7 +1864,7 @@ impl Default for BezierEditorState {
     fn default() -> Self {
         Self {
             drag_idx: DRAG_NONE,
-            selected_anchors: [false; 4],
+            selected_anchors: Vec::new(),
             mode: BezierEditMode::Idle,
             skip_click_select: false,
             box_select_origin: None,
@@ -1204,11 +1882,97 @@ impl BezierEditorState {
     }
 
     fn clear_selection(&mut self) {
-        self.selected_anchors = [false; 4];
+        self.selected_anchors.fill(false);
+    }
+
+    /// Add an anchor after the first selected one (or on the longest
+    /// segment) and select it.
+    fn insert_anchor(&mut self, outline: &mut BezierOutline) {
+        let n = outline.anchors.len();
+        let seg = self.selected_anchors.iter().position(|&s| s).unwrap_or_else(|| {
+            (0..n)
+                .max_by(|&a, &b| {
+                    chord_len(outline, a).total_cmp(&chord_len(outline, b))
+                })
+                .unwrap_or(0)
+        });
+        if outline.insert_anchor(seg) {
+            self.selected_anchors = vec![false; outline.anchors.len()];
+            self.selected_anchors[seg + 1] = true;
+        }
     }
+
+    /// Remove the selected anchors, keeping at least `MIN_ANCHORS`.
+    fn delete_selected(&mut self, outline: &mut BezierOutline) {
+        for i in (0..self.selected_anchors.len()).rev() {
+            if self.selected_anchors[i] && outline.remove_anchor(i) {
+                self.selected_anchors.remove(i);
+            }
+        }
+    }
+}
+
+fn chord_len(outline: &BezierOutline, seg: usize) -> f32 {
+    let a = outline.anchors[seg].position;
+    let b = outline.anchors[(seg + 1) % outline.anchors.len()].position;
+    ((b[0] - a[0]).powi(2) + (b[1] - a[1]).powi(2)).sqrt()
 }
 
-fn bezier_outline_editor(ui: &mut egui::Ui, outline: &mut BezierOutline, editor_id: &str) {
+/// Outline of a highlight in eye space (offset included, gaze parallax
+/// not), as a closed polyline for the preview.
+fn highlight_preview_points(h: &Highlight) -> Vec<[f32; 2]> {
+    // Unit shape: circle/ellipse, or the Bezier outline
+    let unit: Vec<[f32; 2]> = if h.shape == HighlightShape::Bezier {
+        let cps = h.outline.to_uniform_array();
+        let subdiv = 16;
+        let mut points = Vec::new();
+        for seg in 0..h.outline.segment_count() as usize {
+            let [x0, y0, x1, y1] = cps[seg * 2];
+            let [x2, y2, x3, y3] = cps[seg * 2 + 1];
+            for j in 0..subdiv {
+                let t = j as f32 / subdiv as f32;
+                let omt = 1.0 - t;
+                let w = [omt * omt * omt, 3.0 * omt * omt * t, 3.0 * omt * t * t, t * t * t];
+                points.push([
+                    w[0] * x0 + w[1] * x1 + w[2] * x2 + w[3] * x3,
+                    w[0] * y0 + w[1] * y1 + w[2] * y2 + w[3] * y3,
+                ]);
+            }
+        }
+        points
+    } else {
+        (0..48)
+            .map(|j| {
+                let a = j as f32 / 48.0 * std::f32::consts::TAU;
+                [a.cos(), a.sin()]
+            })
+            .collect()
+    };
+
+    let (aspect, rotation) = match h.shape {
+        HighlightShape::Circle => (1.0, 0.0),
+        _ => (h.aspect, h.rotation),
+    };
+    let (sin, cos) = rotation.sin_cos();
+    unit.into_iter()
+        .map(|[x, y]| {
+            let (x, y) = (x * h.radius, y * h.radius * aspect);
+            [
+                h.offset[0] + cos * x - sin * y,
+                h.offset[1] + sin * x + cos * y,
+            ]
+        })
+        .collect()
+}
+
+/// Outline editor. `zoom` 1.0 shows eye space [-0.5, 0.5]; larger values
+/// zoom in.
+fn bezier_outline_editor(
+    ui: &mut egui::Ui,
+    outline: &mut BezierOutline,
+    editor_id: &str,
+    zoom: f32,
+) {
     let available_width = ui.available_width();
     let size = available_width.min(300.0);
     let (response, painter) = ui.allocate_painter(
@@ -1217,8 +1981,8 @@ fn bezier_outline_editor(ui: &mut egui::Ui, outline: &mut BezierOutline, editor_
     );
     let rect = response.rect;
 
-    // Coordinate mapping: eye space [-0.5, 0.5] -> canvas pixels
-    let scale = rect.width() * 0.85;
+    // Coordinate mapping: eye space [-0.5, 0.5] / zoom -> canvas pixels
+    let scale = rect.width() * 0.85 * zoom;
     let center = rect.center();
 
     let to_screen = |p: [f32; 2]| -> egui::Pos2 {
@@ -1235,6 +1999,12 @@ fn bezier_outline_editor(ui: &mut egui::Ui, outline: &mut BezierOutline, editor_
     let state_id = response.id.with(editor_id).with("editor_state");
     let mut es: BezierEditorState =
         ui.memory(|m| m.data.get_temp(state_id)).unwrap_or_default();
+    // The outline may have been replaced (reset, config load) since last frame
+    if es.selected_anchors.len() != outline.anchors.len() {
+        es.selected_anchors = vec![false; outline.anchors.len()];
+        es.mode = BezierEditMode::Idle;
+        es.drag_idx = DRAG_NONE;
+    }
 
     // Find hovered point (for visual feedback)
     let hover_threshold = 12.0f32;
@@ -1242,8 +2012,7 @@ fn bezier_outline_editor(ui: &mut egui::Ui, outline: &mut BezierOutline, editor_
     if es.drag_idx == DRAG_NONE && matches!(es.mode, BezierEditMode::Idle) {
         if let Some(pos) = response.hover_pos() {
             let mut best_dist = hover_threshold;
-            for i in 0..4 {
-                let a = &outline.anchors[i];
+            for (i, a) in outline.anchors.iter().enumerate() {
                 let d = pos.distance(to_screen(a.position));
                 if d < best_dist {
                     best_dist = d;
@@ -1253,13 +2022,13 @@ fn bezier_outline_editor(ui: &mut egui::Ui, outline: &mut BezierOutline, editor_
                 let d = pos.distance(to_screen(hi));
                 if d < best_dist {
                     best_dist = d;
-                    hovered_idx = 4 + i as i32;
+                    hovered_idx = DRAG_STRIDE + i as i32;
                 }
                 let ho = [a.position[0] + a.handle_out[0], a.position[1] + a.handle_out[1]];
                 let d = pos.distance(to_screen(ho));
                 if d < best_dist {
                     best_dist = d;
-                    hovered_idx = 8 + i as i32;
+                    hovered_idx = 2 * DRAG_STRIDE + i as i32;
                 }
             }
         }
@@ -1283,8 +2052,9 @@ fn bezier_outline_editor(ui: &mut egui::Ui, outline: &mut BezierOutline, editor_
     let curve_color = egui::Color32::from_rgb(220, 220, 220);
     let curve_stroke = egui::Stroke::new(2.0, curve_color);
     let anchors = &outline.anchors;
-    for seg in 0..4 {
-        let next = (seg + 1) % 4;
+    let n = anchors.len();
+    for seg in 0..n {
+        let next = (seg + 1) % n;
         let a = &anchors[seg];
         let b = &anchors[next];
         let p0 = a.position;
@@ -1319,8 +2089,7 @@ fn bezier_outline_editor(ui: &mut egui::Ui, outline: &mut BezierOutline, editor_
     let anchor_hover = egui::Color32::from_rgb(255, 255, 180);
     let select_ring_color = egui::Color32::from_rgb(100, 180, 255);
 
-    for i in 0..4 {
-        let a = &anchors[i];
+    for (i, a) in anchors.iter().enumerate() {
         let hi = [a.position[0] + a.handle_in[0], a.position[1] + a.handle_in[1]];
         let ho = [a.position[0] + a.handle_out[0], a.position[1] + a.handle_out[1]];
         let hi_scr = to_screen(hi);
@@ -1330,8 +2099,10 @@ fn bezier_outline_editor(ui: &mut egui::Ui, outline: &mut BezierOutline, editor_
         painter.line_segment([hi_scr, ho_scr], egui::Stroke::new(1.0, handle_line_color));
 
         // Handle points
-        let hi_active = hovered_idx == 4 + i as i32 || es.drag_idx == 4 + i as i32 || es.selected_anchors[i];
-        let ho_active = hovered_idx == 8 + i as i32 || es.drag_idx == 8 + i as i32 || es.selected_anchors[i];
+        let hi_idx = DRAG_STRIDE + i as i32;
+        let ho_idx = 2 * DRAG_STRIDE + i as i32;
+        let hi_active = hovered_idx == hi_idx || es.drag_idx == hi_idx || es.selected_anchors[i];
+        let ho_active = hovered_idx == ho_idx || es.drag_idx == ho_idx || es.selected_anchors[i];
         painter.circle_filled(hi_scr, if hi_active { 5.0 } else { 3.5 }, if hi_active { handle_hover } else { handle_color });
         painter.circle_filled(ho_scr, if ho_active { 5.0 } else { 3.5 }, if ho_active { handle_hover } else { handle_color });
 
@@ -1343,8 +2114,8 @@ fn bezier_outline_editor(ui: &mut egui::Ui, outline: &mut BezierOutline, editor_
     }
 
     // Draw anchor points (on top of everything)
-    for i in 0..4 {
-        let a_scr = to_screen(anchors[i].position);
+    for (i, a) in anchors.iter().enumerate() {
+        let a_scr = to_screen(a.position);
         let active = hovered_idx == i as i32 || es.drag_idx == i as i32 || es.selected_anchors[i];
         painter.circle_filled(a_scr, if active { 7.0 } else { 5.0 }, if active { anchor_hover } else { anchor_color });
 
@@ -1437,8 +2208,7 @@ fn bezier_outline_editor(ui: &mut egui::Ui, outline: &mut BezierOutline, editor_
             let threshold = 15.0f32;
             let mut best_dist = threshold;
             let mut clicked_anchor: Option<usize> = None;
-            for i in 0..4 {
-                let a = &outline.anchors[i];
+            for (i, a) in outline.anchors.iter().enumerate() {
                 let d = pos.distance(to_screen(a.position));
                 if d < best_dist {
                     best_dist = d;
@@ -1478,8 +2248,7 @@ fn bezier_outline_editor(ui: &mut egui::Ui, outline: &mut BezierOutline, editor_
             let mut best_dist = threshold;
             es.drag_idx = DRAG_NONE;
 
-            for i in 0..4 {
-                let a = &outline.anchors[i];
+            for (i, a) in outline.anchors.iter().enumerate() {
 
                 let d = pos.distance(to_screen(a.position));
                 if d < best_dist {
@@ -1491,14 +2260,14 @@ fn bezier_outline_editor(ui: &mut egui::Ui, outline: &mut BezierOutline, editor_
                 let d = pos.distance(to_screen(hi));
                 if d < best_dist {
                     best_dist = d;
-                    es.drag_idx = 4 + i as i32;
+                    es.drag_idx = DRAG_STRIDE + i as i32;
                 }
 
                 let ho = [a.position[0] + a.handle_out[0], a.position[1] + a.handle_out[1]];
                 let d = pos.distance(to_screen(ho));
                 if d < best_dist {
                     best_dist = d;
-                    es.drag_idx = 8 + i as i32;
+                    es.drag_idx = 2 * DRAG_STRIDE + i as i32;
                 }
             }
 
@@ -1513,16 +2282,16 @@ fn bezier_outline_editor(ui: &mut egui::Ui, outline: &mut BezierOutline, editor_
         if let Some(pos) = response.interact_pointer_pos() {
             let p = from_screen(pos);
 
-            if es.drag_idx < 4 {
+            if es.drag_idx < DRAG_STRIDE {
                 let i = es.drag_idx as usize;
                 outline.anchors[i].position = p;
-            } else if es.drag_idx < 8 {
-                let i = (es.drag_idx - 4) as usize;
+            } else if es.drag_idx < 2 * DRAG_STRIDE {
+                let i = (es.drag_idx - DRAG_STRIDE) as usize;
                 let anchor = outline.anchors[i].position;
                 outline.anchors[i].handle_in = [p[0] - anchor[0], p[1] - anchor[1]];
                 outline.anchors[i].enforce_collinear_from_in();
             } else {
-                let i = (es.drag_idx - 8) as usize;
+                let i = (es.drag_idx - 2 * DRAG_STRIDE) as usize;
                 let anchor = outline.anchors[i].position;
                 outline.anchors[i].handle_out = [p[0] - anchor[0], p[1] - anchor[1]];
                 outline.anchors[i].enforce_collinear_from_out();
@@ -1545,8 +2314,8 @@ fn bezier_outline_editor(ui: &mut egui::Ui, outline: &mut BezierOutline, editor_
                 );
                 es.clear_selection();
                 let mut any_selected = false;
-                for i in 0..4 {
-                    let scr = to_screen(outline.anchors[i].position);
+                for (i, a) in outline.anchors.iter().enumerate() {
+                    let scr = to_screen(a.position);
                     if sel_rect.contains(scr) {
                         es.selected_anchors[i] = true;
                         any_selected = true;
@@ -1569,7 +2338,7 @@ fn bezier_outline_editor(ui: &mut egui::Ui, outline: &mut BezierOutline, editor_
                     let mouse_pos = ui.input(|i| i.pointer.hover_pos())
                         .unwrap_or(egui::pos2(center.x, center.y));
                     es.mode = BezierEditMode::Grab {
-                        selected: es.selected_anchors,
+                        selected: es.selected_anchors.clone(),
                         original_anchors: snapshot_all(&outline.anchors),
                         grab_origin: [mouse_pos.x, mouse_pos.y],
                     };
@@ -1579,7 +2348,7 @@ fn bezier_outline_editor(ui: &mut egui::Ui, outline: &mut BezierOutline, editor_
                     let mouse_pos = ui.input(|i| i.pointer.hover_pos()).unwrap_or(pivot);
                     let initial_dist = pivot.distance(mouse_pos).max(1.0);
                     es.mode = BezierEditMode::Scale {
-                        selected: es.selected_anchors,
+                        selected: es.selected_anchors.clone(),
                         original_anchors: snapshot_all(&outline.anchors),
                         pivot_screen_pos: [pivot.x, pivot.y],
                         initial_mouse_dist: initial_dist,
@@ -1591,12 +2360,15 @@ fn bezier_outline_editor(ui: &mut egui::Ui, outline: &mut BezierOutline, editor_
                     let mouse_pos = ui.input(|i| i.pointer.hover_pos()).unwrap_or(pivot);
                     let initial_angle = (mouse_pos.y - pivot.y).atan2(mouse_pos.x - pivot.x);
                     es.mode = BezierEditMode::Rotate {
-                        selected: es.selected_anchors,
+                        selected: es.selected_anchors.clone(),
                         original_anchors: snapshot_all(&outline.anchors),
                         pivot_screen_pos: [pivot.x, pivot.y],
                         initial_mouse_angle: initial_angle,
                     };
                     ui.ctx().request_repaint();
+                } else if ui.input(|i| i.key_pressed(egui::Key::Delete)) {
+                    es.delete_selected(outline);
+                    ui.ctx().request_repaint();
                 } else if ui.input(|i| i.key_pressed(egui::Key::Escape)) {
                     es.clear_selection();
                     response.surrender_focus();
@@ -1607,7 +2379,7 @@ fn bezier_outline_editor(ui: &mut egui::Ui, outline: &mut BezierOutline, editor_
                 if es.has_selection() {
                     es.clear_selection();
                 } else {
-                    es.selected_anchors = [true; 4];
+                    es.selected_anchors.fill(true);
                 }
                 ui.ctx().request_repaint();
             }
@@ -1619,9 +2391,8 @@ fn bezier_outline_editor(ui: &mut egui::Ui, outline: &mut BezierOutline, editor_
                 let dx = delta[0] - origin[0];
                 let dy = delta[1] - origin[1];
 
-                for i in 0..4 {
+                for (i, orig) in original_anchors.iter().enumerate() {
                     if selected[i] {
-                        let orig = &original_anchors[i];
                         outline.anchors[i].position = [orig.position[0] + dx, orig.position[1] + dy];
                         outline.anchors[i].handle_in = orig.handle_in;
                         outline.anchors[i].handle_out = orig.handle_out;
@@ -1661,9 +2432,8 @@ fn bezier_outline_editor(ui: &mut egui::Ui, outline: &mut BezierOutline, editor_
 
                 let centroid = centroid_eye_space(&original_anchors, &selected);
 
-                for i in 0..4 {
+                for (i, orig) in original_anchors.iter().enumerate() {
                     if selected[i] {
-                        let orig = &original_anchors[i];
                         outline.anchors[i].position = [
                             centroid[0] + (orig.position[0] - centroid[0]) * sx,
                             centroid[1] + (orig.position[1] - centroid[1]) * sy,
@@ -1704,9 +2474,8 @@ fn bezier_outline_editor(ui: &mut egui::Ui, outline: &mut BezierOutline, editor_
 
                 let centroid = centroid_eye_space(&original_anchors, &selected);
 
-                for i in 0..4 {
+                for (i, orig) in original_anchors.iter().enumerate() {
                     if selected[i] {
-                        let orig = &original_anchors[i];
                         // Rotate position around centroid
                         let rel_x = orig.position[0] - centroid[0];
                         let rel_y = orig.position[1] - centroid[1];
@@ -1739,6 +2508,29 @@ fn bezier_outline_editor(ui: &mut egui::Ui, outline: &mut BezierOutline, editor_
         }
     }
 
+    // --- Anchor count ---
+    ui.horizontal(|ui| {
+        ui.label(format!("{} anchors", outline.anchors.len()));
+        let idle = matches!(es.mode, BezierEditMode::Idle);
+        let can_add = idle && outline.anchors.len() < BezierOutline::MAX_ANCHORS;
+        if ui
+            .add_enabled(can_add, egui::Button::new("+ Anchor"))
+            .on_hover_text("Split the segment after the selected anchor (or the longest)")
+            .clicked()
+        {
+            es.insert_anchor(outline);
+        }
+        let can_delete =
+            idle && es.has_selection() && outline.anchors.len() > BezierOutline::MIN_ANCHORS;
+        if ui
+            .add_enabled(can_delete, egui::Button::new("Delete Anchor"))
+            .on_hover_text("Remove the selected anchors (Delete)")
+            .clicked()
+        {
+            es.delete_selected(outline);
+        }
+    });
+
     ui.memory_mut(|m| m.data.insert_temp(state_id, es));
 }
 
@@ -2719,6 +3511,218 @@ fn nod_curve_editor(
     ui.memory_mut(|m| m.data.insert_temp(state_id, es));
 }
 
+// ============================================================
+// Timeline easing editor
+// ============================================================
+
+/// Pick the easing kind; parameters are only reset when the kind changes.
+fn timeline_easing_combo(ui: &mut egui::Ui, easing: &mut TimelineEasing) {
+    egui::ComboBox::from_id_salt("easing")
+        .selected_text(easing.label())
+        .width(90.0)
+        .show_ui(ui, |ui| {
+            for e in TimelineEasing::ALL {
+                if ui.selectable_label(easing.same_kind(e), e.label()).clicked()
+                    && !easing.same_kind(e)
+                {
+                    *easing = e;
+                }
+            }
+        });
+}
+
+/// Parameters and curve preview of a parametric easing. `duration` is the
+/// length of the transition it drives (springs are timed in seconds).
+fn timeline_easing_params(ui: &mut egui::Ui, easing: &mut TimelineEasing, duration: f32) {
+    match easing {
+        TimelineEasing::CubicBezier { x1, y1, x2, y2 } => {
+            ui.horizontal(|ui| {
+                ui.label("P1");
+                ui.add(egui::DragValue::new(x1).speed(0.005).range(0.0..=1.0).max_decimals(3));
+                ui.add(value_drag(y1));
+                ui.label("P2");
+                ui.add(egui::DragValue::new(x2).speed(0.005).range(0.0..=1.0).max_decimals(3));
+                ui.add(value_drag(y2));
+            });
+        }
+        TimelineEasing::Steps { count, jump_start } => {
+            ui.horizontal(|ui| {
+                ui.add(egui::DragValue::new(count).range(1..=64).prefix("steps: "));
+                ui.checkbox(jump_start, "Jump at start");
+            });
+        }
+        TimelineEasing::Spring { stiffness, damping } => {
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::DragValue::new(stiffness)
+                        .speed(1.0)
+                        .range(1.0..=1000.0)
+                        .prefix("stiffness: "),
+                );
+                ui.add(
+                    egui::DragValue::new(damping)
+                        .speed(0.2)
+                        .range(0.0..=200.0)
+                        .prefix("damping: "),
+                );
+            });
+        }
+        _ => return,
+    }
+    egui::CollapsingHeader::new("Curve")
+        .id_salt("easing_curve")
+        .show(ui, |ui| easing_curve_editor(ui, easing, duration));
+}
+
+const EASING_DRAG_NONE: i32 = -1;
+/// Visible progress range; leaves room for overshoot.
+const EASING_PLOT_MIN: f32 = -0.25;
+const EASING_PLOT_MAX: f32 = 1.25;
+
+#[derive(Clone, Debug)]
+struct EasingEditorState {
+    drag_idx: i32,
+}
+
+impl Default for EasingEditorState {
+    fn default() -> Self {
+        Self {
+            drag_idx: EASING_DRAG_NONE,
+        }
+    }
+}
+
+/// Plot of progress over normalized time. Cubic bezier control points
+/// (0 = P1, 1 = P2) can be dragged; x stays within [0, 1] like CSS.
+fn easing_curve_editor(ui: &mut egui::Ui, easing: &mut TimelineEasing, duration: f32) {
+    let size = ui.available_width().min(260.0);
+    let (response, painter) =
+        ui.allocate_painter(egui::vec2(size, size * 0.75), egui::Sense::click_and_drag());
+    let rect = response.rect;
+
+    let margin = 16.0;
+    let plot_rect = egui::Rect::from_min_max(
+        egui::pos2(rect.left() + margin, rect.top() + margin),
+        egui::pos2(rect.right() - margin, rect.bottom() - margin),
+    );
+    let y_span = EASING_PLOT_MAX - EASING_PLOT_MIN;
+    let to_screen = |p: [f32; 2]| -> egui::Pos2 {
+        egui::pos2(
+            plot_rect.left() + p[0] * plot_rect.width(),
+            plot_rect.bottom() - (p[1] - EASING_PLOT_MIN) / y_span * plot_rect.height(),
+        )
+    };
+    let from_screen = |p: egui::Pos2| -> [f32; 2] {
+        [
+            (p.x - plot_rect.left()) / plot_rect.width(),
+            (plot_rect.bottom() - p.y) / plot_rect.height() * y_span + EASING_PLOT_MIN,
+        ]
+    };
+
+    let state_id = response.id.with("easing_editor_state");
+    let mut es: EasingEditorState = ui.memory(|m| m.data.get_temp(state_id)).unwrap_or_default();
+
+    let handles: Vec<[f32; 2]> = match *easing {
+        TimelineEasing::CubicBezier { x1, y1, x2, y2 } => vec![[x1, y1], [x2, y2]],
+        _ => Vec::new(),
+    };
+    let nearest_handle = |pos: egui::Pos2, threshold: f32| -> i32 {
+        let mut best = (threshold, EASING_DRAG_NONE);
+        for (i, h) in handles.iter().enumerate() {
+            let d = pos.distance(to_screen(*h));
+            if d < best.0 {
+                best = (d, i as i32);
+            }
+        }
+        best.1
+    };
+    let hovered_idx = match response.hover_pos() {
+        Some(pos) if es.drag_idx == EASING_DRAG_NONE => nearest_handle(pos, 12.0),
+        _ => EASING_DRAG_NONE,
+    };
+
+    // --- Background ---
+    painter.rect_filled(rect, 4.0, egui::Color32::from_gray(30));
+    let grid_color = egui::Color32::from_gray(55);
+    let grid_label_color = egui::Color32::from_gray(90);
+    for &gy in &[0.0f32, 0.5, 1.0] {
+        let y = to_screen([0.0, gy]).y;
+        painter.line_segment(
+            [egui::pos2(plot_rect.left(), y), egui::pos2(plot_rect.right(), y)],
+            egui::Stroke::new(0.5, grid_color),
+        );
+        painter.text(
+            egui::pos2(plot_rect.left() - 2.0, y),
+            egui::Align2::RIGHT_CENTER,
+            format!("{:.1}", gy),
+            egui::FontId::proportional(9.0),
+            grid_label_color,
+        );
+    }
+    for &gx in &[0.0f32, 0.5, 1.0] {
+        let x = plot_rect.left() + gx * plot_rect.width();
+        painter.line_segment(
+            [egui::pos2(x, plot_rect.top()), egui::pos2(x, plot_rect.bottom())],
+            egui::Stroke::new(0.5, grid_color),
+        );
+    }
+
+    // --- Colors ---
+    let curve_color = egui::Color32::from_rgb(80, 200, 140);
+    let handle_line_color = egui::Color32::from_gray(80);
+    let handle_color = egui::Color32::from_rgb(220, 180, 60);
+    let handle_hover = egui::Color32::from_rgb(255, 220, 100);
+
+    // --- Sampled curve ---
+    let subdiv = 96;
+    let points: Vec<egui::Pos2> = (0..=subdiv)
+        .map(|j| {
+            let t = j as f32 / subdiv as f32;
+            let v = easing.apply(t, duration).clamp(EASING_PLOT_MIN, EASING_PLOT_MAX);
+            to_screen([t, v])
+        })
+        .collect();
+    painter.add(egui::Shape::line(points, egui::Stroke::new(2.0, curve_color)));
+
+    // --- Control points ---
+    let ends = [[0.0, 0.0], [1.0, 1.0]];
+    for (i, h) in handles.iter().enumerate() {
+        let scr = to_screen(*h);
+        painter.line_segment(
+            [to_screen(ends[i]), scr],
+            egui::Stroke::new(1.0, handle_line_color),
+        );
+        let active = hovered_idx == i as i32 || es.drag_idx == i as i32;
+        painter.circle_filled(
+            scr,
+            if active { 5.0 } else { 3.5 },
+            if active { handle_hover } else { handle_color },
+        );
+    }
+
+    // --- Drag interaction ---
+    if response.drag_started() {
+        if let Some(pos) = response.interact_pointer_pos() {
+            es.drag_idx = nearest_handle(pos, 15.0);
+        }
+    }
+    if response.dragged() && es.drag_idx != EASING_DRAG_NONE {
+        if let (Some(pos), TimelineEasing::CubicBezier { x1, y1, x2, y2 }) =
+            (response.interact_pointer_pos(), easing)
+        {
+            let p = from_screen(pos);
+            let (x, y) = if es.drag_idx == 0 { (x1, y1) } else { (x2, y2) };
+            *x = p[0].clamp(0.0, 1.0);
+            *y = p[1].clamp(EASING_PLOT_MIN, EASING_PLOT_MAX);
+        }
+    }
+    if response.drag_stopped() {
+        es.drag_idx = EASING_DRAG_NONE;
+    }
+
+    ui.memory_mut(|m| m.data.insert_temp(state_id, es));
+}
+
 fn color_edit_rgb(ui: &mut egui::Ui, color: &mut [f32; 3]) {
     let mut rgba = egui::Color32::from_rgb(
         (color[0] * 255.0) as u8,
@@ -0,0 +1,123 @@
+use crate::gaze::spring_step;
+
+/// Head orientation and eye-in-head look produced by [`HeadEyeCoordination`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HeadEyePose {
+    /// `[head_yaw, head_pitch]` uniforms.
+    pub head: [f32; 2],
+    /// `[look_x, look_y]` uniforms (eye rotation relative to the head).
+    pub eye: [f32; 2],
+}
+
+/// Splits a gaze target between head and eyes.
+///
+/// Gaze is expressed in the same [-1, 1] look units as `look_x` / `head_yaw`,
+/// measured in world space: gaze ≈ eye + head. The head chases
+/// `head_gain * gaze` (or an explicit head target) through a critically damped
+/// spring, so on a gaze shift the eyes jump first and then re-center as the
+/// head catches up. Whenever the head moves while the gaze is held, the eyes
+/// counter-rotate by `vor_gain` (vestibulo-ocular reflex).
+pub struct HeadEyeCoordination {
+    pub enabled: bool,
+    /// Fraction of a gaze shift eventually carried by the head (0 = eyes only).
+    pub head_gain: f32,
+    /// Head spring natural frequency (Hz). Lower = lazier head.
+    pub head_frequency: f32,
+    /// Counter-rotation applied to the eyes per unit of head rotation.
+    pub vor_gain: f32,
+
+    head: [f32; 2],
+    head_velocity: [f32; 2],
+    gaze: [f32; 2],
+    last_time: f32,
+}
+
+impl Default for HeadEyeCoordination {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            head_gain: 0.6,
+            head_frequency: 1.2,
+            vor_gain: 1.0,
+            head: [0.0; 2],
+            head_velocity: [0.0; 2],
+            gaze: [0.0; 2],
+            last_time: 0.0,
+        }
+    }
+}
+
+impl HeadEyeCoordination {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Seed from the displayed pose and stop head motion.
+    pub fn reset(&mut self, time: f32, pose: HeadEyePose) {
+        self.head = pose.head;
+        self.head_velocity = [0.0; 2];
+        self.gaze = if self.enabled {
+            [
+                pose.eye[0] + pose.head[0] * self.vor_gain,
+                pose.eye[1] + pose.head[1] * self.vor_gain,
+            ]
+        } else {
+            pose.eye
+        };
+        self.last_time = time;
+    }
+
+    /// Current world-space gaze (last target passed to [`update`](Self::update)).
+    pub fn gaze(&self) -> [f32; 2] {
+        self.gaze
+    }
+
+    /// Whether the head has come to rest.
+    pub fn is_settled(&self) -> bool {
+        self.head_velocity[0].abs() + self.head_velocity[1].abs() < 1e-3
+    }
+
+    /// Advance the head toward its target and derive the eye-in-head look that
+    /// keeps `gaze` fixated.
+    ///
+    /// `head_target` overrides the head's own choice (`head_gain * gaze`).
+    pub fn update(
+        &mut self,
+        time: f32,
+        gaze: [f32; 2],
+        head_target: Option<[f32; 2]>,
+    ) -> HeadEyePose {
+        let dt = (time - self.last_time).clamp(0.0, 0.1);
+        self.last_time = time;
+        self.gaze = gaze;
+
+        if !self.enabled {
+            // Legacy rig: the head turns with the gaze
+            self.head = head_target.unwrap_or(gaze);
+            self.head_velocity = [0.0; 2];
+            return HeadEyePose {
+                head: self.head,
+                eye: gaze,
+            };
+        }
+
+        let target = head_target.unwrap_or([gaze[0] * self.head_gain, gaze[1] * self.head_gain]);
+        if dt > 0.0 {
+            self.head = spring_step(
+                self.head,
+                &mut self.head_velocity,
+                target,
+                self.head_frequency,
+                dt,
+            );
+        }
+
+        HeadEyePose {
+            head: self.head,
+            eye: [
+                (gaze[0] - self.head[0] * self.vor_gain).clamp(-1.0, 1.0),
+                (gaze[1] - self.head[1] * self.vor_gain).clamp(-1.0, 1.0),
+            ],
+        }
+    }
+}
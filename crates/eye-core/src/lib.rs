@@ -1,20 +1,40 @@
 pub mod animation;
 pub mod config;
+pub mod controller;
+pub mod export;
+pub mod expression;
+pub mod gaze;
+pub mod head;
 pub mod listening;
 pub mod microsaccade;
 pub mod nod;
+pub mod offscreen;
 pub mod outline;
+pub mod protocol;
+pub mod pupil;
+#[cfg(feature = "remote")]
+pub mod remote;
 pub mod renderer;
 pub mod timeline;
 
 #[cfg(feature = "gui")]
 pub mod gui;
 
-pub use animation::BlinkAnimation;
+pub use animation::{BlinkAnimation, WinkEye};
 pub use config::EyeConfig;
+pub use controller::{EyeController, EyeEvent, EyeInputs, EyeSideState, EyeSnapshot, RemoteGaze};
+pub use export::{ExportError, ExportSettings};
+pub use expression::{Expression, ExpressionBlend, ExpressionLibrary, ExpressionMixer, ExpressionOverride};
+pub use gaze::{GazeFilter, GazeSmoothing};
+pub use head::{HeadEyeCoordination, HeadEyePose};
 pub use listening::ListeningNod;
 pub use microsaccade::MicrosaccadeAnimation;
 pub use nod::NodAnimation;
-pub use outline::{BezierAnchor, BezierOutline, EyelashShape, EyeShape, EyebrowGuide, EyebrowOutline, EyebrowShape, IrisShape, PupilShape};
-pub use renderer::{EyePairUniforms, EyeRenderer, EyeUniforms};
-pub use timeline::{Timeline, TimelineEasing, TimelineFrame, TimelineGlobalConfig, TimelineKeyframe, TimelinePlayer};
+pub use offscreen::{OffscreenError, RgbaImage};
+pub use outline::{BezierAnchor, BezierOutline, EyelashShape, EyelidShape, EyeShape, EyebrowGuide, EyebrowOutline, EyebrowShape, Highlight, HighlightShape, IrisShape, PupilShape};
+pub use protocol::{Command, CommandMessage, Notification, NotificationMessage, Reply, ReplyBody};
+pub use pupil::PupilDynamics;
+#[cfg(feature = "remote")]
+pub use remote::{RemoteServer, RemoteServerConfig};
+pub use renderer::{EyePairUniforms, EyeRenderer, EyeUniforms, HighlightUniform, LoadMode};
+pub use timeline::{AnimationChannel, LayerBlend, Timeline, TimelineEasing, TimelineFrame, TimelineGlobalConfig, TimelineAction, TimelineEvent, TimelineKeyframe, TimelineLayer, TimelinePlayer, TimelinePose, TimelineTrack, TrackEye, TrackKey, TrackProperty, TrackSample, TrackValue};
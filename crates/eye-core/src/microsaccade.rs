@@ -12,19 +12,40 @@ struct MicrosaccadeEvent {
     duration: f32,
 }
 
+/// Look speed (look units per second) above which the gaze counts as a
+/// large saccade and idle microsaccades hold off.
+const SACCADE_SPEED: f32 = 2.0;
+/// Hold-off after a blink or large saccade before the next idle microsaccade.
+const SUPPRESS_DELAY: f32 = 0.2;
+
 /// Microsaccade animation: tiny, quick involuntary iris/pupil shifts.
 ///
 /// When triggered, both eyes shift in the same random direction
-/// (biased toward center). The iris stays at the new position
-/// until the next trigger.
+/// (biased toward center). The iris stays at the new position,
+/// slowly drifting back toward zero, until the next trigger.
+/// [`MicrosaccadeAnimation::update`] triggers them on its own at
+/// randomised intervals.
 pub struct MicrosaccadeAnimation {
     rng: Xorshift32,
     active_event: Option<MicrosaccadeEvent>,
     /// Current resting offset (persists after animation completes).
     pub offset_x: f32,
     pub offset_y: f32,
+    /// Average idle microsaccades per second; intervals vary from half to
+    /// one and a half times the mean.
+    pub rate: f32,
+    /// Offset range of a single microsaccade (iris_follow ≈ 0.14, so this is tiny).
+    pub amplitude_min: f32,
+    pub amplitude_max: f32,
+    /// Average shift duration in seconds (varies by a third either way).
+    pub duration: f32,
     /// How strongly the direction is biased toward center (0.0 = none, 1.0 = always toward center).
-    center_bias: f32,
+    pub center_bias: f32,
+    /// Rate (1/s) at which the resting offset decays back toward zero.
+    pub drift_rate: f32,
+    next_time: Option<f32>,
+    last_time: Option<f32>,
+    last_look: Option<[f32; 2]>,
 }
 
 impl MicrosaccadeAnimation {
@@ -34,7 +55,41 @@ impl MicrosaccadeAnimation {
             active_event: None,
             offset_x: 0.0,
             offset_y: 0.0,
+            rate: 1.2,
+            amplitude_min: 0.024,
+            amplitude_max: 0.028,
+            duration: 0.045,
             center_bias: 0.6,
+            drift_rate: 0.5,
+            next_time: None,
+            last_time: None,
+            last_look: None,
+        }
+    }
+
+    /// Autonomous scheduling: trigger a microsaccade whenever one is due.
+    ///
+    /// Call once per frame with the current gaze direction. While `blinking`
+    /// or during a large saccade, due microsaccades are held off until the
+    /// eye has been still for a moment.
+    pub fn update(&mut self, time: f32, look_x: f32, look_y: f32, blinking: bool) {
+        let saccading = match (self.last_look, self.last_time) {
+            (Some([lx, ly]), Some(last)) if time > last => {
+                let (dx, dy) = (look_x - lx, look_y - ly);
+                (dx * dx + dy * dy).sqrt() / (time - last) > SACCADE_SPEED
+            }
+            _ => false,
+        };
+        self.last_look = Some([look_x, look_y]);
+
+        let Some(next) = self.next_time else {
+            self.next_time = Some(time + self.next_interval());
+            return;
+        };
+        if blinking || saccading {
+            self.next_time = Some(next.max(time + SUPPRESS_DELAY));
+        } else if time >= next {
+            self.trigger(time, look_x, look_y);
         }
     }
 
@@ -43,8 +98,9 @@ impl MicrosaccadeAnimation {
     /// `current_look_x` and `current_look_y` are the current gaze direction,
     /// used to bias the saccade toward the center.
     pub fn trigger(&mut self, time: f32, current_look_x: f32, current_look_y: f32) {
-        let from_x = self.offset_x;
-        let from_y = self.offset_y;
+        // Start from wherever an interrupted shift or the drift has reached
+        let (from_x, from_y) = self.evaluate(time);
+        self.active_event = None;
 
         // Random direction (angle in radians)
         let angle = self.rng.range(0.0, std::f32::consts::TAU);
@@ -74,9 +130,10 @@ impl MicrosaccadeAnimation {
             (1.0, 0.0)
         };
 
-        // Very small amplitude in screen space (iris_follow ≈ 0.14, so this is tiny)
-        let amplitude = self.rng.range(0.024, 0.028);
-        let duration = self.rng.range(0.03, 0.06);
+        let amplitude = self
+            .rng
+            .range(self.amplitude_min, self.amplitude_max.max(self.amplitude_min));
+        let duration = (self.duration * self.rng.range(2.0 / 3.0, 4.0 / 3.0)).max(0.005);
 
         let to_x = dx * amplitude;
         let to_y = dy * amplitude;
@@ -89,12 +146,26 @@ impl MicrosaccadeAnimation {
             to_y,
             duration,
         });
+        self.next_time = Some(time + duration + self.next_interval());
+    }
+
+    /// Random wait before the next idle microsaccade.
+    fn next_interval(&mut self) -> f32 {
+        let mean = 1.0 / self.rate.max(0.01);
+        self.rng.range(mean * 0.5, mean * 1.5)
     }
 
     /// Evaluate the current iris offset.
     ///
     /// Returns `(offset_x, offset_y)` — always valid (persists after animation).
     pub fn evaluate(&mut self, time: f32) -> (f32, f32) {
+        // Resting offset drifts back toward zero
+        let dt = self.last_time.map_or(0.0, |last| (time - last).max(0.0));
+        self.last_time = Some(time);
+        let decay = (-self.drift_rate.max(0.0) * dt).exp();
+        self.offset_x *= decay;
+        self.offset_y *= decay;
+
         if let Some(ref event) = self.active_event {
             let elapsed = time - event.start_time;
             if elapsed < 0.0 {
@@ -1,3 +1,4 @@
+use crate::config::NodConfig;
 use crate::outline::BezierAnchor;
 
 /// Evaluate a cubic Bezier at parameter t ∈ [0, 1].
@@ -103,12 +104,16 @@ pub struct NodOutput {
     pub nod_sink: f32,
     /// Eyelid close value (overrides normal eyelid state during nod).
     pub eyelid_close: f32,
+    /// Rotation pivot Y position used by this nod.
+    pub pivot_y: f32,
 }
 
 struct NodEvent {
     start_time: f32,
     /// Eyelid close value captured at the moment the nod was triggered.
     initial_eyelid_close: f32,
+    /// Parameters used instead of the animation's own for this nod only.
+    overrides: Option<NodConfig>,
 }
 
 /// Nod animation state machine.
@@ -152,9 +157,21 @@ impl Default for NodAnimation {
 impl NodAnimation {
     /// Start a nod at the given time, capturing the current eyelid state.
     pub fn trigger(&mut self, time: f32, current_eyelid_close: f32) {
+        self.trigger_with(time, current_eyelid_close, None);
+    }
+
+    /// Start a nod that uses `overrides` instead of this animation's
+    /// parameters (the animation itself is left unchanged).
+    pub fn trigger_with(
+        &mut self,
+        time: f32,
+        current_eyelid_close: f32,
+        overrides: Option<NodConfig>,
+    ) {
         self.active_event = Some(NodEvent {
             start_time: time,
             initial_eyelid_close: current_eyelid_close,
+            overrides,
         });
     }
 
@@ -170,17 +187,28 @@ impl NodAnimation {
     pub fn evaluate(&mut self, time: f32) -> Option<NodOutput> {
         let event = self.active_event.as_ref()?;
 
+        let overridden = event.overrides.as_ref().map(|config| {
+            let mut nod = NodAnimation::default();
+            config.apply_to(&mut nod);
+            nod
+        });
+        let params = overridden.as_ref().unwrap_or(self);
+
         let elapsed = time - event.start_time;
         if elapsed < 0.0 {
             return None;
         }
 
-        let global_t = elapsed / self.duration;
+        let global_t = elapsed / params.duration;
         if global_t >= 1.0 {
             self.active_event = None;
             return None;
         }
 
+        Some(params.output(event, global_t))
+    }
+
+    fn output(&self, event: &NodEvent, global_t: f32) -> NodOutput {
         let curve_y = self.curve.evaluate(global_t).max(0.0);
 
         // Face tilt angle: amount is max angle in radians
@@ -202,10 +230,11 @@ impl NodAnimation {
             self.mid_closeness + (self.end_openness - self.mid_closeness) * local_t
         };
 
-        Some(NodOutput {
+        NodOutput {
             nod_pitch,
             nod_sink,
             eyelid_close: eyelid_close.clamp(0.0, 1.0),
-        })
+            pivot_y: self.pivot_y,
+        }
     }
 }
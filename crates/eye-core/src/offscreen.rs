@@ -0,0 +1,231 @@
+use std::fmt;
+
+use crate::renderer::{EyePairUniforms, EyeRenderer};
+
+/// Tightly packed 8-bit RGBA image read back from the GPU.
+/// Rows are stored top to bottom with no padding (`width * 4` bytes each).
+/// Alpha is straight (not premultiplied), as PNG expects.
+#[derive(Clone, Debug)]
+pub struct RgbaImage {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
+impl RgbaImage {
+    /// RGBA value of a single pixel.
+    pub fn pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        let i = ((y * self.width + x) * 4) as usize;
+        [
+            self.pixels[i],
+            self.pixels[i + 1],
+            self.pixels[i + 2],
+            self.pixels[i + 3],
+        ]
+    }
+
+    /// Encode the image as PNG bytes.
+    #[cfg(feature = "png")]
+    pub fn encode_png(&self) -> Result<Vec<u8>, png::EncodingError> {
+        let mut out = Vec::new();
+        {
+            let mut encoder = png::Encoder::new(&mut out, self.width, self.height);
+            encoder.set_color(png::ColorType::Rgba);
+            encoder.set_depth(png::BitDepth::Eight);
+            let mut writer = encoder.write_header()?;
+            writer.write_image_data(&self.pixels)?;
+        }
+        Ok(out)
+    }
+
+    /// Encode the image as PNG and write it to `path`.
+    #[cfg(feature = "png")]
+    pub fn write_png(&self, path: impl AsRef<std::path::Path>) -> Result<(), png::EncodingError> {
+        let bytes = self.encode_png()?;
+        std::fs::write(path, bytes)?;
+        Ok(())
+    }
+}
+
+/// Errors from offscreen rendering.
+#[derive(Debug)]
+pub enum OffscreenError {
+    /// The renderer was built for a format that cannot be read back as RGBA8.
+    UnsupportedFormat(wgpu::TextureFormat),
+    /// Width or height was zero.
+    EmptySize,
+    /// Mapping the readback buffer failed.
+    Map(wgpu::BufferAsyncError),
+}
+
+impl fmt::Display for OffscreenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedFormat(format) => {
+                write!(f, "offscreen readback does not support {format:?}")
+            }
+            Self::EmptySize => write!(f, "offscreen target must be at least 1x1"),
+            Self::Map(e) => write!(f, "failed to map readback buffer: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for OffscreenError {}
+
+/// Whether the format's bytes are stored as BGRA and need swizzling.
+fn is_bgra(format: wgpu::TextureFormat) -> Option<bool> {
+    match format {
+        wgpu::TextureFormat::Rgba8Unorm | wgpu::TextureFormat::Rgba8UnormSrgb => Some(false),
+        wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb => Some(true),
+        _ => None,
+    }
+}
+
+fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(c: f32) -> f32 {
+    if c <= 0.003_130_8 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Convert premultiplied RGBA8 to straight alpha in place.
+/// For sRGB targets the division happens in linear space, where the shader premultiplied.
+fn unpremultiply(pixels: &mut [u8], srgb: bool) {
+    for px in pixels.chunks_exact_mut(4) {
+        let a = px[3];
+        if a == 255 {
+            continue;
+        }
+        if a == 0 {
+            px[..3].fill(0);
+            continue;
+        }
+        let alpha = a as f32 / 255.0;
+        for c in &mut px[..3] {
+            let v = *c as f32 / 255.0;
+            let straight = if srgb {
+                linear_to_srgb((srgb_to_linear(v) / alpha).min(1.0))
+            } else {
+                (v / alpha).min(1.0)
+            };
+            *c = (straight * 255.0).round() as u8;
+        }
+    }
+}
+
+impl EyeRenderer {
+    /// Render `params` into an offscreen texture of `width` x `height` and
+    /// read it back as RGBA8.
+    ///
+    /// Works with any adapter, including software ones (llvmpipe / WARP), so
+    /// it can be used for thumbnails and golden images without a window.
+    /// The renderer must have been created with an 8-bit RGBA or BGRA format;
+    /// sRGB formats yield sRGB-encoded bytes. `params.left.aspect_ratio`
+    /// should match `width / height`.
+    pub fn render_to_image(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        width: u32,
+        height: u32,
+        params: &EyePairUniforms,
+    ) -> Result<RgbaImage, OffscreenError> {
+        let format = self.target_format();
+        let swap_rb = is_bgra(format).ok_or(OffscreenError::UnsupportedFormat(format))?;
+        if width == 0 || height == 0 {
+            return Err(OffscreenError::EmptySize);
+        }
+
+        let size = wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        };
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("eye_offscreen_texture"),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        // Rows in the readback buffer must be 256-byte aligned
+        let unpadded_row = width * 4;
+        let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+        let padded_row = unpadded_row.div_ceil(align) * align;
+        let readback = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("eye_offscreen_readback"),
+            size: padded_row as u64 * height as u64,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("eye_offscreen_encoder"),
+        });
+        self.render(&mut encoder, &view, queue, params);
+        encoder.copy_texture_to_buffer(
+            wgpu::TexelCopyTextureInfo {
+                texture: &texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::TexelCopyBufferInfo {
+                buffer: &readback,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_row),
+                    rows_per_image: Some(height),
+                },
+            },
+            size,
+        );
+        queue.submit(Some(encoder.finish()));
+
+        let slice = readback.slice(..);
+        let (tx, rx) = std::sync::mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = tx.send(result);
+        });
+        device.poll(wgpu::Maintain::Wait);
+        rx.recv()
+            .expect("map_async callback dropped")
+            .map_err(OffscreenError::Map)?;
+
+        let mut pixels = Vec::with_capacity((unpadded_row * height) as usize);
+        {
+            let data = slice.get_mapped_range();
+            for row in data.chunks_exact(padded_row as usize) {
+                pixels.extend_from_slice(&row[..unpadded_row as usize]);
+            }
+        }
+        readback.unmap();
+
+        if swap_rb {
+            for px in pixels.chunks_exact_mut(4) {
+                px.swap(0, 2);
+            }
+        }
+        unpremultiply(&mut pixels, format.is_srgb());
+
+        Ok(RgbaImage {
+            width,
+            height,
+            pixels,
+        })
+    }
+}
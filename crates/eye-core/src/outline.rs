@@ -1,13 +1,16 @@
-/// Cubic Bezier curve outline for eye shape morphing.
-///
-/// The eye outline is defined as a closed path of 4 cubic Bezier segments,
-/// connecting 4 anchor points (Left, Top, Right, Bottom). Each anchor has
-/// two handles (handle_in, handle_out) that are constrained to be collinear.
+//! Cubic Bezier curve outline for eye shape morphing.
+//!
+//! The eye outline is defined as a closed path of cubic Bezier segments,
+//! connecting 3 to [`BezierOutline::MAX_ANCHORS`] anchor points. The presets
+//! use 4 (Left, Top, Right, Bottom). Each anchor has two handles
+//! (handle_in, handle_out) that are constrained to be collinear.
+
+use serde::{Deserialize, Serialize};
 
 /// Kappa constant for cubic Bezier circle approximation.
 /// A circle of radius r is approximated by 4 cubic Bezier segments
 /// where each handle length = r * KAPPA.
-const KAPPA: f32 = 0.552_284_749_8;
+const KAPPA: f32 = 0.552_284_7;
 
 #[derive(Clone, Debug)]
 pub struct BezierAnchor {
@@ -45,14 +48,24 @@ impl BezierAnchor {
     }
 }
 
+/// GPU layout of an outline: two vec4f per segment, see
+/// [`BezierOutline::to_uniform_array`].
+pub type OutlineUniform = [[f32; 4]; 2 * BezierOutline::MAX_ANCHORS];
+
 #[derive(Clone, Debug)]
 pub struct BezierOutline {
-    /// 4 anchor points: [Left, Top, Right, Bottom] (counterclockwise).
-    /// Segments: Left→Top, Top→Right, Right→Bottom, Bottom→Left.
-    pub anchors: [BezierAnchor; 4],
+    /// Anchor points in path order, starting at the left corner and running
+    /// over the top (the presets use [Left, Top, Right, Bottom]).
+    /// Segment i connects anchor i to anchor (i+1) % n.
+    pub anchors: Vec<BezierAnchor>,
 }
 
 impl BezierOutline {
+    /// Fewest anchors an editable outline keeps.
+    pub const MIN_ANCHORS: usize = 3;
+    /// Most anchors the uniform layout holds; extra anchors are not drawn.
+    pub const MAX_ANCHORS: usize = 12;
+
     /// Create a circle approximation with the given radius.
     pub fn circle(radius: f32) -> Self {
         Self::ellipse(radius, radius)
@@ -63,7 +76,7 @@ impl BezierOutline {
         let hx = rx * KAPPA;
         let hy = ry * KAPPA;
         Self {
-            anchors: [
+            anchors: vec![
                 // Left (-rx, 0): handle_in goes down, handle_out goes up
                 BezierAnchor {
                     position: [-rx, 0.0],
@@ -99,7 +112,7 @@ impl BezierOutline {
         let hw = half_width * KAPPA;
         let ht = thickness * KAPPA;
         Self {
-            anchors: [
+            anchors: vec![
                 // Left tip (-half_width, 0): tapers to a point
                 BezierAnchor {
                     position: [-half_width, 0.0],
@@ -133,7 +146,7 @@ impl BezierOutline {
         let tiny = 0.005;
         let hw = half_width * KAPPA;
         Self {
-            anchors: [
+            anchors: vec![
                 // Left corner
                 BezierAnchor {
                     position: [-half_width, y_pos],
@@ -176,7 +189,7 @@ impl BezierOutline {
         let hw = half_width * KAPPA;
 
         Self {
-            anchors: [
+            anchors: vec![
                 // Left corner — sits at slit level
                 BezierAnchor {
                     position: [-half_width, y_slit],
@@ -205,15 +218,51 @@ impl BezierOutline {
         }
     }
 
-    /// Convert to a flat array of 8 × [f32; 4] for GPU uniform upload.
+    /// Create a four-point sparkle with tips at `radius` on each axis and
+    /// sides curving in toward the center. Tips are sharp corners, so the
+    /// handles of each anchor both point inward.
+    pub fn sparkle(radius: f32) -> Self {
+        let tip = |x: f32, y: f32| BezierAnchor {
+            position: [x, y],
+            handle_in: [-x * 0.6, -y * 0.6],
+            handle_out: [-x * 0.6, -y * 0.6],
+        };
+        Self {
+            anchors: vec![
+                tip(-radius, 0.0),
+                tip(0.0, radius),
+                tip(radius, 0.0),
+                tip(0.0, -radius),
+            ],
+        }
+    }
+
+    /// Number of segments drawn (one per anchor, capped at `MAX_ANCHORS`).
+    pub fn segment_count(&self) -> u32 {
+        self.anchors.len().min(Self::MAX_ANCHORS) as u32
+    }
+
+    /// Number of segments from the left corner (anchor 0) over the top to
+    /// the right corner, taken as the rightmost anchor.
+    pub fn upper_segment_count(&self) -> u32 {
+        let n = self.segment_count() as usize;
+        let right = (1..n)
+            .max_by(|&a, &b| self.anchors[a].position[0].total_cmp(&self.anchors[b].position[0]))
+            .unwrap_or(0);
+        right as u32
+    }
+
+    /// Convert to a flat array of 2 × `MAX_ANCHORS` [f32; 4] for GPU uniform
+    /// upload. Slots past [`segment_count`](Self::segment_count) stay zero.
     ///
-    /// Layout: For segment i (connecting anchor[i] to anchor[(i+1)%4]):
+    /// Layout: For segment i (connecting anchor[i] to anchor[(i+1)%n]):
     ///   uniform[i*2]   = [P0.x, P0.y, P1.x, P1.y]  (anchor, anchor+handle_out)
     ///   uniform[i*2+1] = [P2.x, P2.y, P3.x, P3.y]  (next_anchor+handle_in, next_anchor)
-    pub fn to_uniform_array(&self) -> [[f32; 4]; 8] {
-        let mut result = [[0.0f32; 4]; 8];
-        for seg in 0..4 {
-            let next = (seg + 1) % 4;
+    pub fn to_uniform_array(&self) -> OutlineUniform {
+        let mut result = [[0.0f32; 4]; 2 * Self::MAX_ANCHORS];
+        let n = self.segment_count() as usize;
+        for seg in 0..n {
+            let next = (seg + 1) % n;
             let a = &self.anchors[seg];
             let b = &self.anchors[next];
 
@@ -288,6 +337,325 @@ impl BezierOutline {
             self.auto_adjust_handle_at(i);
         }
     }
+
+    /// Split segment `seg` at parameter `t` without changing the shape. The
+    /// new anchor lands at index `seg + 1`.
+    pub fn split_segment(&mut self, seg: usize, t: f32) {
+        let n = self.anchors.len();
+        let next = (seg + 1) % n;
+        let a = &self.anchors[seg];
+        let b = &self.anchors[next];
+        let p0 = a.position;
+        let p1 = add2(p0, a.handle_out);
+        let p3 = b.position;
+        let p2 = add2(p3, b.handle_in);
+
+        // de Casteljau
+        let q0 = lerp2(p0, p1, t);
+        let q1 = lerp2(p1, p2, t);
+        let q2 = lerp2(p2, p3, t);
+        let r0 = lerp2(q0, q1, t);
+        let r1 = lerp2(q1, q2, t);
+        let s = lerp2(r0, r1, t);
+
+        self.anchors[seg].handle_out = sub2(q0, p0);
+        self.anchors[next].handle_in = sub2(q2, p3);
+        self.anchors.insert(
+            seg + 1,
+            BezierAnchor {
+                position: s,
+                handle_in: sub2(r0, s),
+                handle_out: sub2(r1, s),
+            },
+        );
+    }
+
+    /// Add an anchor at the middle of segment `seg`. Returns `false` when the
+    /// outline already has `MAX_ANCHORS`.
+    pub fn insert_anchor(&mut self, seg: usize) -> bool {
+        if self.anchors.len() >= Self::MAX_ANCHORS || seg >= self.anchors.len() {
+            return false;
+        }
+        self.split_segment(seg, 0.5);
+        true
+    }
+
+    /// Remove anchor `i`, merging its two segments into one that follows
+    /// them as closely as a single cubic can. Returns `false` when the
+    /// outline would drop below `MIN_ANCHORS`.
+    pub fn remove_anchor(&mut self, i: usize) -> bool {
+        let n = self.anchors.len();
+        if n <= Self::MIN_ANCHORS || i >= n {
+            return false;
+        }
+        let prev = (i + n - 1) % n;
+        let next = (i + 1) % n;
+        // A split at t leaves |handle_in| : |handle_out| = t : (1 - t), so this
+        // exactly undoes split_segment; chord lengths cover degenerate handles
+        let a = &self.anchors[i];
+        let (len_a, len_b) = match (dist2(a.handle_in, [0.0; 2]), dist2(a.handle_out, [0.0; 2])) {
+            (h_in, h_out) if h_in > 1e-6 && h_out > 1e-6 => (h_in, h_out),
+            _ => (
+                dist2(self.anchors[prev].position, a.position),
+                dist2(a.position, self.anchors[next].position),
+            ),
+        };
+        let t = if len_a + len_b > 1e-8 {
+            len_a / (len_a + len_b)
+        } else {
+            0.5
+        };
+        let out = self.anchors[prev].handle_out;
+        let inn = self.anchors[next].handle_in;
+        self.anchors[prev].handle_out = scale2(out, 1.0 / t.max(0.05));
+        self.anchors[next].handle_in = scale2(inn, 1.0 / (1.0 - t).max(0.05));
+        self.anchors.remove(i);
+        true
+    }
+
+    /// Copy with at least `count` anchors, subdividing segments without
+    /// changing the shape. Which segments are split depends only on the
+    /// anchor counts, so two outlines with the same count are upsampled
+    /// alike and their anchors still correspond (for blending).
+    pub fn upsampled(&self, count: usize) -> Self {
+        let n = self.anchors.len();
+        if count <= n || n == 0 {
+            return self.clone();
+        }
+        let extra = count - n;
+        let mut out = self.clone();
+        // Walk backwards so earlier segment indices stay valid
+        for seg in (0..n).rev() {
+            let splits = (seg + 1) * extra / n - seg * extra / n;
+            // Cut off equal parameter steps from the end of the segment
+            for k in (1..=splits).rev() {
+                out.split_segment(seg, k as f32 / (k + 1) as f32);
+            }
+        }
+        out
+    }
+
+    /// Copy with exactly `count` anchors (within `MIN_ANCHORS..=MAX_ANCHORS`),
+    /// spread evenly by arc length over the upper and lower arcs. Both
+    /// corners are kept and each arc keeps its share of the anchors, so
+    /// anchor `i` lands on the same part of the outline whichever anchors
+    /// it had before. Spans within one old segment keep their exact shape;
+    /// spans across an old anchor are fitted with a single cubic.
+    pub fn resampled(&self, count: usize) -> Self {
+        let n = self.anchors.len();
+        let count = count.clamp(Self::MIN_ANCHORS, Self::MAX_ANCHORS);
+        if count == n || n < 2 {
+            return self.clone();
+        }
+        let right = (self.upper_segment_count() as usize).clamp(1, n - 1);
+        let upper = ((count * right) as f32 / n as f32).round() as usize;
+        self.resampled_arcs(count, upper)
+    }
+
+    /// Copy with `other`'s anchor count and the same number of them on the
+    /// upper arc, so anchor `i` of both pairs up corner to corner (e.g. a
+    /// closed outline blended with the open one). See
+    /// [`resampled`](Self::resampled).
+    pub fn resampled_like(&self, other: &Self) -> Self {
+        let n = self.anchors.len();
+        let count = other
+            .anchors
+            .len()
+            .clamp(Self::MIN_ANCHORS, Self::MAX_ANCHORS);
+        let upper = other.upper_segment_count() as usize;
+        if n < 2 || (count == n && upper == self.upper_segment_count() as usize) {
+            return self.clone();
+        }
+        self.resampled_arcs(count, upper)
+    }
+
+    /// `count` anchors spread evenly by arc length, `upper` of them (counting
+    /// the left corner) from the left corner up to the right one.
+    fn resampled_arcs(&self, count: usize, upper: usize) -> Self {
+        let n = self.anchors.len();
+
+        // Cumulative arc length at RESAMPLE_STEPS points of every segment
+        let segments: Vec<_> = (0..n).map(|seg| self.segment_points(seg)).collect();
+        let lengths: Vec<[f32; RESAMPLE_STEPS + 1]> = segments
+            .iter()
+            .map(|p| {
+                let mut acc = [0.0; RESAMPLE_STEPS + 1];
+                let mut prev = p[0];
+                for k in 1..=RESAMPLE_STEPS {
+                    let q = cubic_point(p, k as f32 / RESAMPLE_STEPS as f32);
+                    acc[k] = acc[k - 1] + dist2(prev, q);
+                    prev = q;
+                }
+                acc
+            })
+            .collect();
+
+        let mut starts = vec![0.0; n + 1];
+        for seg in 0..n {
+            starts[seg + 1] = starts[seg] + lengths[seg][RESAMPLE_STEPS];
+        }
+        // Arc length from anchor 0 to (segment, t)
+        let arc_at = |seg: usize, t: f32| {
+            let x = t * RESAMPLE_STEPS as f32;
+            let step = (x as usize).min(RESAMPLE_STEPS - 1);
+            let acc = &lengths[seg];
+            starts[seg] + acc[step] + (acc[step + 1] - acc[step]) * (x - step as f32)
+        };
+
+        // Anchor `k` of the result as (segment, t); corners at t = 0
+        let right = (self.upper_segment_count() as usize).clamp(1, n - 1);
+        let upper = upper.clamp(1, count - 1);
+        let mut stops = Vec::with_capacity(count);
+        for (first, last, parts) in [(0, right, upper), (right, n, count - upper)] {
+            let total = starts[last] - starts[first];
+            stops.push((first, 0.0));
+            for k in 1..parts {
+                let target = starts[first] + total * k as f32 / parts as f32;
+                let seg = (first..last)
+                    .find(|&seg| target <= starts[seg + 1])
+                    .unwrap_or(last - 1);
+                let acc = &lengths[seg];
+                let local = target - starts[seg];
+                let step = acc.partition_point(|&l| l < local).clamp(1, RESAMPLE_STEPS);
+                let span = acc[step] - acc[step - 1];
+                let frac = if span > 1e-8 {
+                    (local - acc[step - 1]) / span
+                } else {
+                    0.0
+                };
+                let t = (step as f32 - 1.0 + frac.clamp(0.0, 1.0)) / RESAMPLE_STEPS as f32;
+                // Snap onto old anchors so spans ending there stay exact
+                stops.push(if t > 1.0 - 1e-4 {
+                    ((seg + 1) % n, 0.0)
+                } else {
+                    (seg, t.max(0.0))
+                });
+            }
+        }
+
+        let mut anchors: Vec<BezierAnchor> = stops
+            .iter()
+            .map(|&(seg, t)| BezierAnchor {
+                position: cubic_point(&segments[seg], t),
+                handle_in: [0.0; 2],
+                handle_out: [0.0; 2],
+            })
+            .collect();
+        for k in 0..count {
+            let (seg, t0) = stops[k];
+            let (next_seg, t1) = stops[(k + 1) % count];
+            let p = &segments[seg];
+            let [p0, p1, p2, p3] = if next_seg == seg && t1 > t0 {
+                sub_cubic(p, t0, t1)
+            } else if next_seg == (seg + 1) % n && t1 == 0.0 {
+                sub_cubic(p, t0, 1.0)
+            } else {
+                // Tangent directions at both ends, handle lengths of a
+                // circular arc with the same length and turn
+                let q = &segments[next_seg];
+                let p0 = cubic_point(p, t0);
+                let p3 = cubic_point(q, t1);
+                let span = (arc_at(next_seg, t1) - arc_at(seg, t0)).rem_euclid(starts[n]);
+                let chord = unit2(sub2(p3, p0)).unwrap_or([0.0; 2]);
+                let out_dir = unit2(cubic_tangent(p, t0)).unwrap_or(chord);
+                let in_dir = unit2(cubic_tangent(q, t1)).unwrap_or(chord);
+                let turn = (out_dir[0] * in_dir[0] + out_dir[1] * in_dir[1])
+                    .clamp(-1.0, 1.0)
+                    .acos();
+                let ratio = if turn > 1e-3 {
+                    4.0 / 3.0 * (turn / 4.0).tan() / turn
+                } else {
+                    1.0 / 3.0
+                };
+                let reach = span.max(dist2(p0, p3)) * ratio;
+                [
+                    p0,
+                    add2(p0, scale2(out_dir, reach)),
+                    sub2(p3, scale2(in_dir, reach)),
+                    p3,
+                ]
+            };
+            anchors[k].handle_out = sub2(p1, p0);
+            anchors[(k + 1) % count].handle_in = sub2(p2, p3);
+        }
+        Self { anchors }
+    }
+
+    /// Control points of segment `seg`.
+    fn segment_points(&self, seg: usize) -> [[f32; 2]; 4] {
+        let n = self.anchors.len();
+        let a = &self.anchors[seg];
+        let b = &self.anchors[(seg + 1) % n];
+        [
+            a.position,
+            add2(a.position, a.handle_out),
+            add2(b.position, b.handle_in),
+            b.position,
+        ]
+    }
+}
+
+/// Arc length samples per segment for [`BezierOutline::resampled`].
+const RESAMPLE_STEPS: usize = 32;
+
+fn cubic_point(p: &[[f32; 2]; 4], t: f32) -> [f32; 2] {
+    let q0 = lerp2(p[0], p[1], t);
+    let q1 = lerp2(p[1], p[2], t);
+    let q2 = lerp2(p[2], p[3], t);
+    lerp2(lerp2(q0, q1, t), lerp2(q1, q2, t), t)
+}
+
+/// Derivative direction at `t`, from the de Casteljau construction.
+fn cubic_tangent(p: &[[f32; 2]; 4], t: f32) -> [f32; 2] {
+    let q0 = lerp2(p[0], p[1], t);
+    let q1 = lerp2(p[1], p[2], t);
+    let q2 = lerp2(p[2], p[3], t);
+    sub2(lerp2(q1, q2, t), lerp2(q0, q1, t))
+}
+
+/// Control points of the part of `p` between `t0` and `t1`.
+fn sub_cubic(p: &[[f32; 2]; 4], t0: f32, t1: f32) -> [[f32; 2]; 4] {
+    let head = split_cubic(p, t1).0;
+    if t1 <= 1e-8 {
+        return head;
+    }
+    split_cubic(&head, t0 / t1).1
+}
+
+/// `p` split at `t` into its two halves.
+fn split_cubic(p: &[[f32; 2]; 4], t: f32) -> ([[f32; 2]; 4], [[f32; 2]; 4]) {
+    let q0 = lerp2(p[0], p[1], t);
+    let q1 = lerp2(p[1], p[2], t);
+    let q2 = lerp2(p[2], p[3], t);
+    let r0 = lerp2(q0, q1, t);
+    let r1 = lerp2(q1, q2, t);
+    let s = lerp2(r0, r1, t);
+    ([p[0], q0, r0, s], [s, r1, q2, p[3]])
+}
+
+fn unit2(a: [f32; 2]) -> Option<[f32; 2]> {
+    let len = dist2(a, [0.0; 2]);
+    (len > 1e-8).then(|| scale2(a, 1.0 / len))
+}
+
+fn add2(a: [f32; 2], b: [f32; 2]) -> [f32; 2] {
+    [a[0] + b[0], a[1] + b[1]]
+}
+
+fn sub2(a: [f32; 2], b: [f32; 2]) -> [f32; 2] {
+    [a[0] - b[0], a[1] - b[1]]
+}
+
+fn scale2(a: [f32; 2], s: f32) -> [f32; 2] {
+    [a[0] * s, a[1] * s]
+}
+
+fn lerp2(a: [f32; 2], b: [f32; 2], t: f32) -> [f32; 2] {
+    [a[0] + (b[0] - a[0]) * t, a[1] + (b[1] - a[1]) * t]
+}
+
+fn dist2(a: [f32; 2], b: [f32; 2]) -> f32 {
+    ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2)).sqrt()
 }
 
 /// Holds both open and closed eye outline shapes.
@@ -295,10 +663,14 @@ impl BezierOutline {
 #[derive(Clone, Debug)]
 pub struct EyeShape {
     pub open: BezierOutline,
+    /// Where the upper lid ends up when closed (and the whole eye on a blink).
     pub closed: BezierOutline,
     /// Controls the arch direction when the eye is closed.
     /// Negative = reverse arch (default), positive = smile arch.
     pub close_arch: f32,
+    /// Where the lower lid ends up at full `lower_lid_raise`. Only its lower
+    /// half and corners are used. Not touched by `close_arch`.
+    pub lower_closed: BezierOutline,
 }
 
 impl EyeShape {
@@ -306,14 +678,42 @@ impl EyeShape {
     pub fn update_closed(&mut self) {
         self.closed = BezierOutline::closed_slit_asymmetric(0.20, -0.20, self.close_arch);
     }
+
+    /// The closed outline as if `close_arch` were `arch`, keeping its shape
+    /// otherwise. Anchors between the corners move by the change in arch,
+    /// fully at the middle and easing off toward the corners, which stay put.
+    pub fn closed_with_arch(&self, arch: f32) -> BezierOutline {
+        let mut closed = self.closed.clone();
+        let delta = arch - self.close_arch;
+        let n = closed.anchors.len();
+        if delta.abs() < 1e-6 || n < 2 {
+            return closed;
+        }
+        let right = (closed.upper_segment_count() as usize).clamp(1, n - 1);
+        let left = closed.anchors[0].position[0];
+        let mid = (left + closed.anchors[right].position[0]) * 0.5;
+        let half = ((closed.anchors[right].position[0] - left) * 0.5)
+            .abs()
+            .max(1e-6);
+        for (i, anchor) in closed.anchors.iter_mut().enumerate() {
+            if i == 0 || i == right {
+                continue;
+            }
+            let u = (anchor.position[0] - mid) / half;
+            anchor.position[1] += delta * (1.0 - u * u).max(0.0).sqrt();
+        }
+        closed
+    }
 }
 
 impl Default for EyeShape {
     fn default() -> Self {
         let close_arch = -0.015;
+        let closed = BezierOutline::closed_slit_asymmetric(0.20, -0.20, close_arch);
         Self {
             open: BezierOutline::ellipse(0.28, 0.35),
-            closed: BezierOutline::closed_slit_asymmetric(0.20, -0.20, close_arch),
+            lower_closed: closed.clone(),
+            closed,
             close_arch,
         }
     }
@@ -731,6 +1131,112 @@ impl Default for EyelashShape {
     }
 }
 
+/// Lid skin and crease parameters. Both are off by default.
+#[derive(Clone, Debug)]
+pub struct EyelidShape {
+    /// Fill the lid area between the open outline and the lid edges.
+    pub fill: bool,
+    /// Lid skin color [R, G, B] in linear sRGB, 0..1.
+    pub color: [f32; 3],
+    /// Draw a crease stroke above the upper lid.
+    pub crease: bool,
+    /// Crease color [R, G, B] in linear sRGB, 0..1.
+    pub crease_color: [f32; 3],
+    /// Crease stroke thickness in eye-space units, at its middle.
+    pub crease_thickness: f32,
+    /// Height of the crease above the open upper lid edge.
+    pub crease_offset: f32,
+}
+
+impl Default for EyelidShape {
+    fn default() -> Self {
+        Self {
+            fill: false,
+            color: [0.80, 0.52, 0.40],
+            crease: false,
+            crease_color: [0.30, 0.15, 0.12],
+            crease_thickness: 0.008,
+            crease_offset: 0.06,
+        }
+    }
+}
+
+/// Geometry of a [`Highlight`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HighlightShape {
+    /// Circle of `radius`.
+    #[default]
+    Circle,
+    /// Ellipse `radius` wide and `radius * aspect` tall, turned by `rotation`.
+    Ellipse,
+    /// The highlight's own outline (window panes, stars), in units of
+    /// `radius` and stretched and turned like the ellipse.
+    Bezier,
+}
+
+impl HighlightShape {
+    pub const ALL: [Self; 3] = [Self::Circle, Self::Ellipse, Self::Bezier];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Circle => "Circle",
+            Self::Ellipse => "Ellipse",
+            Self::Bezier => "Bezier",
+        }
+    }
+
+    /// Shape id read by the shader.
+    pub fn to_uniform(self) -> u32 {
+        match self {
+            Self::Circle => 0,
+            Self::Ellipse => 1,
+            Self::Bezier => 2,
+        }
+    }
+}
+
+/// A specular highlight, added over the iris and sclera.
+#[derive(Clone, Debug)]
+pub struct Highlight {
+    pub shape: HighlightShape,
+    /// Center relative to the eye center, in eye space.
+    pub offset: [f32; 2],
+    /// Circle radius, or the horizontal radius of an ellipse or outline.
+    pub radius: f32,
+    /// Height to width ratio of an ellipse or outline.
+    pub aspect: f32,
+    /// Rotation of an ellipse or outline in radians, counter-clockwise.
+    pub rotation: f32,
+    /// Shape for [`HighlightShape::Bezier`], with 1.0 meaning `radius`.
+    pub outline: BezierOutline,
+    /// Color [R, G, B] in linear sRGB, 0..1, added at full intensity.
+    pub color: [f32; 3],
+    pub intensity: f32,
+    /// Edge softness (0 = sharp).
+    pub blur: f32,
+    /// Shift per unit of gaze, in eye space. Reflections of a far light
+    /// drift less than the iris moves (`iris_follow`).
+    pub parallax: f32,
+}
+
+impl Default for Highlight {
+    fn default() -> Self {
+        Self {
+            shape: HighlightShape::Circle,
+            offset: [-0.04, 0.06],
+            radius: 0.03,
+            aspect: 1.0,
+            rotation: 0.0,
+            outline: BezierOutline::sparkle(1.0),
+            color: [1.0, 1.0, 1.0],
+            intensity: 0.9,
+            blur: 0.0,
+            parallax: 0.05,
+        }
+    }
+}
+
 /// Iris shape parameters.
 /// Uses a single BezierOutline (no open/closed states — iris doesn't morph on blink).
 #[derive(Clone, Debug)]
@@ -760,3 +1266,157 @@ impl Default for PupilShape {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Farthest any point along `a` lies from the path of `b`.
+    fn shape_error(a: &BezierOutline, b: &BezierOutline) -> f32 {
+        let points = |o: &BezierOutline| -> Vec<[f32; 2]> {
+            (0..o.anchors.len())
+                .flat_map(|seg| {
+                    let p = o.segment_points(seg);
+                    (0..64).map(move |k| cubic_point(&p, k as f32 / 64.0))
+                })
+                .collect()
+        };
+        let path = points(b);
+        let to_path = |p: [f32; 2]| {
+            (0..path.len())
+                .map(|i| {
+                    let (q0, q1) = (path[i], path[(i + 1) % path.len()]);
+                    let d = sub2(q1, q0);
+                    let len2 = d[0] * d[0] + d[1] * d[1];
+                    let t = if len2 > 0.0 {
+                        ((p[0] - q0[0]) * d[0] + (p[1] - q0[1]) * d[1]) / len2
+                    } else {
+                        0.0
+                    };
+                    dist2(p, lerp2(q0, q1, t.clamp(0.0, 1.0)))
+                })
+                .fold(f32::MAX, f32::min)
+        };
+        points(a).into_iter().map(to_path).fold(0.0, f32::max)
+    }
+
+    fn right_corner(o: &BezierOutline) -> [f32; 2] {
+        o.anchors[o.upper_segment_count() as usize].position
+    }
+
+    fn same_anchors(a: &BezierOutline, b: &BezierOutline) -> bool {
+        a.anchors.len() == b.anchors.len()
+            && a.anchors.iter().zip(&b.anchors).all(|(a, b)| {
+                a.position == b.position
+                    && a.handle_in == b.handle_in
+                    && a.handle_out == b.handle_out
+            })
+    }
+
+    /// The default eye with an anchor added on the upper arc, as the
+    /// outline editor's "+ anchor" does.
+    fn edited_eye() -> BezierOutline {
+        let mut open = BezierOutline::ellipse(0.28, 0.35);
+        assert!(open.insert_anchor(1));
+        open
+    }
+
+    #[test]
+    fn resampled_keeps_corners() {
+        for outline in [BezierOutline::ellipse(0.28, 0.35), edited_eye()] {
+            for count in 3..=BezierOutline::MAX_ANCHORS {
+                let r = outline.resampled(count);
+                assert_eq!(r.anchors.len(), count);
+                assert_eq!(r.anchors[0].position, outline.anchors[0].position);
+                assert_eq!(right_corner(&r), right_corner(&outline), "count {count}");
+            }
+        }
+    }
+
+    #[test]
+    fn resampled_clamps_count() {
+        let outline = edited_eye();
+        assert_eq!(
+            outline.resampled(0).anchors.len(),
+            BezierOutline::MIN_ANCHORS
+        );
+        assert_eq!(
+            outline.resampled(100).anchors.len(),
+            BezierOutline::MAX_ANCHORS
+        );
+    }
+
+    #[test]
+    fn resampled_to_same_count_is_identity() {
+        let outline = edited_eye();
+        assert!(same_anchors(
+            &outline.resampled(outline.anchors.len()),
+            &outline
+        ));
+        assert!(same_anchors(&outline.resampled_like(&outline), &outline));
+    }
+
+    #[test]
+    fn resampled_follows_shape() {
+        let outline = BezierOutline::ellipse(0.28, 0.35);
+        for count in 4..=BezierOutline::MAX_ANCHORS {
+            let r = outline.resampled(count);
+            let error = shape_error(&r, &outline).max(shape_error(&outline, &r));
+            assert!(error < 0.005, "count {count}: error {error}");
+            let back = r.resampled(4);
+            let error = shape_error(&back, &outline).max(shape_error(&outline, &back));
+            assert!(error < 0.005, "count {count} and back: error {error}");
+        }
+    }
+
+    #[test]
+    fn resampled_like_splits_at_the_same_corner() {
+        let open = edited_eye();
+        let closed = EyeShape::default().closed;
+        let r = closed.resampled_like(&open);
+        assert_eq!(r.anchors.len(), open.anchors.len());
+        assert_eq!(r.upper_segment_count(), open.upper_segment_count());
+        assert_eq!(r.anchors[0].position, closed.anchors[0].position);
+        assert_eq!(right_corner(&r), right_corner(&closed));
+        let error = shape_error(&r, &closed).max(shape_error(&closed, &r));
+        assert!(error < 0.005, "error {error}");
+    }
+
+    #[test]
+    fn upsampled_keeps_shape() {
+        let outline = BezierOutline::ellipse(0.28, 0.35);
+        for count in 5..=BezierOutline::MAX_ANCHORS {
+            let r = outline.upsampled(count);
+            assert_eq!(r.anchors.len(), count);
+            assert_eq!(r.anchors[0].position, outline.anchors[0].position);
+            assert_eq!(right_corner(&r), right_corner(&outline));
+            let error = shape_error(&r, &outline).max(shape_error(&outline, &r));
+            assert!(error < 1e-4, "count {count}: error {error}");
+        }
+        assert!(same_anchors(&outline.upsampled(3), &outline));
+    }
+
+    #[test]
+    fn closed_with_arch_moves_only_the_arch() {
+        let shape = EyeShape::default();
+        let expected = BezierOutline::closed_slit_asymmetric(0.20, -0.20, 0.04);
+        let arched = shape.closed_with_arch(0.04);
+        for (a, b) in arched.anchors.iter().zip(&expected.anchors) {
+            assert!(dist2(a.position, b.position) < 1e-6);
+        }
+
+        let mut custom = shape.clone();
+        custom.closed = edited_eye();
+        let arched = custom.closed_with_arch(0.04);
+        assert_eq!(
+            arched.anchors[0].position,
+            custom.closed.anchors[0].position
+        );
+        assert_eq!(right_corner(&arched), right_corner(&custom.closed));
+        let top = arched.anchors[1].position[1] - custom.closed.anchors[1].position[1];
+        assert!(
+            (top - (0.04 - shape.close_arch)).abs() < 1e-6,
+            "top moved {top}"
+        );
+    }
+}
@@ -0,0 +1,513 @@
+//! Versioned JSON command protocol for remote control (e.g. over WebSocket).
+//!
+//! Every message is a JSON object tagged by `"type"`, with an optional protocol
+//! version `"v"` and an optional request `"id"`:
+//!
+//! ```json
+//! {"v": 1, "id": 7, "type": "trigger_blink"}
+//! {"v": 1, "id": 8, "type": "set_eye", "eye": "left", "fields": {"iris_radius": 0.12}}
+//! {"v": 1, "type": "set_eye", "eye": "both", "fields": {"highlights": [{"shape": "ellipse", ...}]}}
+//! {"v": 1, "type": "head", "yaw": 0.3}
+//! {"v": 1, "type": "set_expression", "name": "happy", "weight": 0.7, "duration": 0.4}
+//! ```
+//!
+//! Commands carrying an `id` are answered with an `ack` (or `pong`); failures are
+//! always answered with an `error`, echoing the `id` when known. Objects without
+//! a `"type"` are read as legacy gaze messages
+//! (`{"look_x": .., "look_y": .., "focus_distance": .., "eyelid_close": ..}`).
+//!
+//! After a `subscribe`, the server also pushes notifications:
+//!
+//! ```json
+//! {"v": 1, "type": "event", "time": 3.2, "event": "blink_started"}
+//! {"v": 1, "type": "event", "time": 4.0, "event": "timeline_marker_reached", "index": 2, "label": "smile"}
+//! {"v": 1, "type": "snapshot", "time": 4.1, "look_x": 0.2, "look_y": 0.0, "eyelid_close": 0.2, ...}
+//! ```
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::animation::WinkEye;
+use crate::config::{EyeConfig, EyeSideConfig, LEGACY_HIGHLIGHT_FIELDS};
+use crate::controller::{EyeController, EyeEvent, EyeSideState, EyeSnapshot, RemoteGaze};
+use crate::expression::{Expression, ExpressionLibrary};
+use crate::timeline::Timeline;
+
+/// Protocol version spoken by this library.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+fn default_version() -> u32 {
+    PROTOCOL_VERSION
+}
+
+// ============================================================
+// Commands
+// ============================================================
+
+/// Which eye a per-eye command targets.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EyeTarget {
+    Left,
+    Right,
+    Both,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Command {
+    /// Keep-alive; answered with `pong`.
+    Ping,
+    /// Update the remote gaze. Omitted look/focus fields keep their last value;
+    /// an omitted `eyelid_close` releases the eyelid override.
+    Gaze {
+        #[serde(default)]
+        look_x: Option<f32>,
+        #[serde(default)]
+        look_y: Option<f32>,
+        #[serde(default)]
+        focus_distance: Option<f32>,
+        #[serde(default)]
+        eyelid_close: Option<f32>,
+    },
+    /// Stop applying remote gaze; mouse follow takes over again.
+    ReleaseGaze,
+    /// Point the head independently of the gaze. Omitted axes keep their last
+    /// value; the eyes counter-rotate to hold the current gaze target.
+    Head {
+        #[serde(default)]
+        yaw: Option<f32>,
+        #[serde(default)]
+        pitch: Option<f32>,
+    },
+    /// Let the head follow the gaze again.
+    ReleaseHead,
+    TriggerBlink,
+    /// Close only one eye briefly.
+    Wink { eye: WinkEye },
+    TriggerNod,
+    TriggerMicrosaccade,
+    /// Replace shapes, globals and toggles with a full config.
+    LoadConfig { config: Box<EyeConfig> },
+    /// Replace the timeline (playback is stopped). Legacy keyframe timelines
+    /// are migrated to tracks.
+    LoadTimeline { timeline: Timeline },
+    /// Start playback, or resume if paused.
+    TimelinePlay {
+        #[serde(default)]
+        looping: Option<bool>,
+        #[serde(default)]
+        ping_pong: Option<bool>,
+    },
+    TimelineStop,
+    /// Hold the current frame.
+    TimelinePause,
+    TimelineResume,
+    /// Playback rate multiplier (1.0 = real time).
+    TimelineSpeed { speed: f32 },
+    /// Jump to `time` seconds into the timeline.
+    TimelineSeek { time: f32 },
+    /// Set the weight of the timeline layer named `layer` (0.0 = off).
+    TimelineLayerWeight { layer: String, weight: f32 },
+    /// Overwrite individual `EyeSideConfig` fields, e.g. `{"iris_color": [1, 0, 0]}`.
+    /// `highlights` replaces the whole highlight list; the older single-highlight
+    /// fields (`highlight_offset`, `highlight_radius`, `highlight_intensity`,
+    /// `highlight_blur`) are still accepted and edit the first highlight.
+    SetEye {
+        eye: EyeTarget,
+        fields: serde_json::Map<String, serde_json::Value>,
+    },
+    /// Fade expression `name` to `weight` over `duration` seconds.
+    /// Several expressions can be active at once.
+    SetExpression {
+        name: String,
+        #[serde(default = "default_expression_weight")]
+        weight: f32,
+        #[serde(default = "default_expression_duration")]
+        duration: f32,
+    },
+    /// Fade all expressions out.
+    ClearExpressions {
+        #[serde(default = "default_expression_duration")]
+        duration: f32,
+    },
+    /// Replace the expression library.
+    LoadExpressions { expressions: Vec<Expression> },
+    SetAutoBlink { enabled: bool },
+    /// Toggle idle microsaccades.
+    SetAutoMicrosaccade { enabled: bool },
+    SetFollowMouse { enabled: bool },
+    /// Feed the pupil dynamics. Without `light_level` the pupil follows the
+    /// background luminance again; without `arousal` it is left unchanged.
+    Pupil {
+        #[serde(default)]
+        light_level: Option<f32>,
+        #[serde(default)]
+        arousal: Option<f32>,
+    },
+    /// Start receiving notifications on this connection. Handled by the
+    /// transport; the controller only acknowledges it.
+    Subscribe {
+        /// Push [`EyeEvent`]s as they happen.
+        #[serde(default = "default_true")]
+        events: bool,
+        /// Push a snapshot at most this often (seconds, up to an hour).
+        /// `None` = no snapshots.
+        #[serde(default)]
+        snapshot_interval: Option<f32>,
+    },
+    /// Stop receiving notifications on this connection.
+    Unsubscribe,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_expression_weight() -> f32 {
+    1.0
+}
+
+fn default_expression_duration() -> f32 {
+    0.3
+}
+
+/// A command with its envelope.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CommandMessage {
+    #[serde(default = "default_version")]
+    pub v: u32,
+    /// Client-chosen request id, echoed in the reply.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<u64>,
+    #[serde(flatten)]
+    pub command: Command,
+}
+
+impl CommandMessage {
+    pub fn new(id: Option<u64>, command: Command) -> Self {
+        Self {
+            v: PROTOCOL_VERSION,
+            id,
+            command,
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+
+    /// Parse a message, accepting legacy untagged gaze objects.
+    pub fn from_json(json: &str) -> Result<Self, ProtocolError> {
+        let mut value: serde_json::Value = serde_json::from_str(json).map_err(ProtocolError::Json)?;
+        let id = value.get("id").and_then(|v| v.as_u64());
+
+        if let Some(obj) = value.as_object_mut() {
+            if !obj.contains_key("type") {
+                obj.insert("type".into(), "gaze".into());
+            }
+        }
+
+        let msg: Self = serde_json::from_value(value).map_err(|e| ProtocolError::Invalid {
+            id,
+            message: e.to_string(),
+        })?;
+        if msg.v > PROTOCOL_VERSION {
+            return Err(ProtocolError::UnsupportedVersion { id, version: msg.v });
+        }
+        Ok(msg)
+    }
+}
+
+// ============================================================
+// Replies
+// ============================================================
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ReplyBody {
+    Ack,
+    Pong,
+    Error { message: String },
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Reply {
+    pub v: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<u64>,
+    #[serde(flatten)]
+    pub body: ReplyBody,
+}
+
+impl Reply {
+    pub fn new(id: Option<u64>, body: ReplyBody) -> Self {
+        Self {
+            v: PROTOCOL_VERSION,
+            id,
+            body,
+        }
+    }
+
+    pub fn error(id: Option<u64>, message: impl Into<String>) -> Self {
+        Self::new(
+            id,
+            ReplyBody::Error {
+                message: message.into(),
+            },
+        )
+    }
+
+    /// Whether this reply should be sent: errors always, acks only when the
+    /// client asked for them by setting an `id`.
+    pub fn should_send(&self) -> bool {
+        self.id.is_some() || matches!(self.body, ReplyBody::Error { .. })
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+}
+
+// ============================================================
+// Notifications (server → client, unsolicited)
+// ============================================================
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Notification {
+    Event {
+        time: f32,
+        #[serde(flatten)]
+        event: EyeEvent,
+    },
+    Snapshot(EyeSnapshot),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NotificationMessage {
+    pub v: u32,
+    #[serde(flatten)]
+    pub body: Notification,
+}
+
+impl NotificationMessage {
+    pub fn new(body: Notification) -> Self {
+        Self {
+            v: PROTOCOL_VERSION,
+            body,
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+}
+
+impl EyeController {
+    /// Notifications for the events raised by the last `update`.
+    pub fn event_notifications(&self) -> Vec<NotificationMessage> {
+        let time = self.left.uniforms.time;
+        self.events()
+            .iter()
+            .map(|event| {
+                NotificationMessage::new(Notification::Event {
+                    time,
+                    event: event.clone(),
+                })
+            })
+            .collect()
+    }
+
+    pub fn snapshot_notification(&self) -> NotificationMessage {
+        NotificationMessage::new(Notification::Snapshot(self.snapshot()))
+    }
+}
+
+// ============================================================
+// Errors
+// ============================================================
+
+#[derive(Debug)]
+pub enum ProtocolError {
+    /// Not valid JSON.
+    Json(serde_json::Error),
+    /// Valid JSON but not a known command.
+    Invalid { id: Option<u64>, message: String },
+    /// The client speaks a newer protocol.
+    UnsupportedVersion { id: Option<u64>, version: u32 },
+}
+
+impl ProtocolError {
+    /// The error reply to send back to the client.
+    pub fn to_reply(&self) -> Reply {
+        let id = match self {
+            Self::Json(_) => None,
+            Self::Invalid { id, .. } | Self::UnsupportedVersion { id, .. } => *id,
+        };
+        Reply::error(id, self.to_string())
+    }
+}
+
+impl fmt::Display for ProtocolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Json(e) => write!(f, "invalid JSON: {e}"),
+            Self::Invalid { message, .. } => write!(f, "invalid command: {message}"),
+            Self::UnsupportedVersion { version, .. } => write!(
+                f,
+                "unsupported protocol version {version} (server speaks {PROTOCOL_VERSION})"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ProtocolError {}
+
+// ============================================================
+// Dispatch
+// ============================================================
+
+/// Merge `fields` into one eye's config representation and apply it.
+fn set_eye_fields(
+    side: &mut EyeSideState,
+    fields: &serde_json::Map<String, serde_json::Value>,
+) -> Result<(), String> {
+    let mut value = serde_json::to_value(EyeSideConfig::from(&*side)).map_err(|e| e.to_string())?;
+    let obj = value.as_object_mut().expect("EyeSideConfig serializes to an object");
+    for (key, v) in fields {
+        if !obj.contains_key(key) && !LEGACY_HIGHLIGHT_FIELDS.contains(&key.as_str()) {
+            return Err(format!("unknown eye field `{key}`"));
+        }
+        obj.insert(key.clone(), v.clone());
+    }
+    let mut config: EyeSideConfig = serde_json::from_value(value).map_err(|e| e.to_string())?;
+    config.migrate_legacy_highlight();
+
+    // Runtime-only fields are not part of EyeSideConfig and survive untouched
+    config.apply_to(side);
+    Ok(())
+}
+
+impl EyeController {
+    /// Apply a protocol message and build the reply for it.
+    pub fn handle_message(&mut self, msg: &CommandMessage, time: f32) -> Reply {
+        match self.apply_command(&msg.command, time) {
+            Ok(body) => Reply::new(msg.id, body),
+            Err(message) => Reply::error(msg.id, message),
+        }
+    }
+
+    /// Apply a single command. `time` is the same clock passed to [`EyeController::update`].
+    pub fn apply_command(&mut self, command: &Command, time: f32) -> Result<ReplyBody, String> {
+        match command {
+            Command::Ping => return Ok(ReplyBody::Pong),
+            Command::Gaze {
+                look_x,
+                look_y,
+                focus_distance,
+                eyelid_close,
+            } => {
+                let gaze = self.remote_gaze.get_or_insert(RemoteGaze {
+                    look_x: 0.0,
+                    look_y: 0.0,
+                    focus_distance: 1.5,
+                    eyelid_close: None,
+                });
+                if let Some(v) = look_x {
+                    gaze.look_x = v.clamp(-1.0, 1.0);
+                }
+                if let Some(v) = look_y {
+                    gaze.look_y = v.clamp(-1.0, 1.0);
+                }
+                if let Some(v) = focus_distance {
+                    gaze.focus_distance = v.clamp(0.5, 20.0);
+                }
+                gaze.eyelid_close = eyelid_close.map(|v| v.clamp(0.0, 1.0));
+            }
+            Command::ReleaseGaze => self.remote_gaze = None,
+            Command::Head { yaw, pitch } => {
+                let u = &self.left.uniforms;
+                let head = self.head_target.get_or_insert([u.head_yaw, u.head_pitch]);
+                if let Some(v) = yaw {
+                    head[0] = v.clamp(-1.0, 1.0);
+                }
+                if let Some(v) = pitch {
+                    head[1] = v.clamp(-1.0, 1.0);
+                }
+            }
+            Command::ReleaseHead => self.head_target = None,
+            Command::TriggerBlink => self.trigger_blink(time),
+            Command::Wink { eye } => self.trigger_wink(time, *eye),
+            Command::TriggerNod => self.trigger_nod(time),
+            Command::TriggerMicrosaccade => self.trigger_microsaccade(time),
+            Command::LoadConfig { config } => {
+                if config.version > EyeConfig::CURRENT_VERSION {
+                    return Err(format!("unsupported config version {}", config.version));
+                }
+                config.apply_to_controller(self);
+            }
+            Command::LoadTimeline { timeline } => self.load_timeline(timeline.clone()),
+            Command::TimelinePlay { looping, ping_pong } => {
+                if self.timeline_player.timeline.is_empty() {
+                    return Err("timeline is empty".into());
+                }
+                if let Some(looping) = looping {
+                    self.timeline_player.looping = *looping;
+                }
+                if let Some(ping_pong) = ping_pong {
+                    self.timeline_player.ping_pong = *ping_pong;
+                }
+                self.timeline_player.play(time);
+            }
+            Command::TimelineStop => self.timeline_player.stop(),
+            Command::TimelinePause => self.timeline_player.pause(),
+            Command::TimelineResume => self.timeline_player.resume(time),
+            Command::TimelineSpeed { speed } => self.timeline_player.set_speed(*speed, time),
+            Command::TimelineSeek { time: t } => self.timeline_player.seek(*t, time),
+            Command::TimelineLayerWeight { layer, weight } => {
+                let Some(target) = self
+                    .timeline_player
+                    .timeline
+                    .layers
+                    .iter_mut()
+                    .find(|l| l.name == *layer)
+                else {
+                    return Err(format!("unknown timeline layer `{layer}`"));
+                };
+                target.weight = weight.clamp(0.0, 1.0);
+            }
+            Command::SetEye { eye, fields } => {
+                if matches!(eye, EyeTarget::Left | EyeTarget::Both) {
+                    set_eye_fields(&mut self.left, fields)?;
+                }
+                if matches!(eye, EyeTarget::Right | EyeTarget::Both) {
+                    set_eye_fields(&mut self.right, fields)?;
+                }
+            }
+            Command::SetExpression {
+                name,
+                weight,
+                duration,
+            } => self.set_expression(name, *weight, *duration, time)?,
+            Command::ClearExpressions { duration } => self.expressions.clear(*duration, time),
+            Command::LoadExpressions { expressions } => {
+                self.expressions.library = ExpressionLibrary {
+                    expressions: expressions.clone(),
+                };
+            }
+            Command::SetAutoBlink { enabled } => self.auto_blink = *enabled,
+            Command::SetAutoMicrosaccade { enabled } => self.auto_microsaccade = *enabled,
+            Command::SetFollowMouse { enabled } => self.follow_mouse = *enabled,
+            Command::Pupil { light_level, arousal } => {
+                self.pupil_dynamics.light_level = light_level.map(|l| l.clamp(0.0, 1.0));
+                if let Some(arousal) = arousal {
+                    self.pupil_dynamics.arousal = arousal.clamp(0.0, 1.0);
+                }
+            }
+            Command::Subscribe { .. } | Command::Unsubscribe => {}
+        }
+        Ok(ReplyBody::Ack)
+    }
+}
@@ -0,0 +1,115 @@
+/// Pupil size dynamics: light reflex, arousal dilation and hippus.
+///
+/// Produces a scale factor for the configured `pupil_radius` (and the pupil
+/// outline with it). Bright light constricts the pupil quickly, darkness and
+/// arousal dilate it slowly, and a slow irregular oscillation (hippus) keeps
+/// it from ever sitting perfectly still.
+///
+/// This module is pure logic — feed it the current time and a light level
+/// each frame.
+pub struct PupilDynamics {
+    /// Whether the pupil size is animated. When off the scale stays at 1.
+    pub enabled: bool,
+    /// External light level in [0, 1]. `None` uses the background luminance.
+    pub light_level: Option<f32>,
+    /// Arousal / interest in [0, 1]; dilates the pupil.
+    pub arousal: f32,
+    /// Scale in full brightness (constricted).
+    pub min_scale: f32,
+    /// Scale in darkness (dilated).
+    pub max_scale: f32,
+    /// Extra scale at full arousal, still capped by `max_scale`.
+    pub arousal_gain: f32,
+    /// Time constant of the constriction response in seconds.
+    pub constriction_time: f32,
+    /// Time constant of the (slower) dilation response in seconds.
+    pub dilation_time: f32,
+    /// Hippus amplitude as a share of the pupil size.
+    pub hippus_amplitude: f32,
+    /// Base hippus frequency in Hz.
+    pub hippus_frequency: f32,
+
+    /// Smoothed light/arousal response, before hippus.
+    response: f32,
+    scale: f32,
+    last_time: Option<f32>,
+}
+
+impl Default for PupilDynamics {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            light_level: None,
+            arousal: 0.0,
+            min_scale: 0.6,
+            max_scale: 1.4,
+            arousal_gain: 0.25,
+            constriction_time: 0.25,
+            dilation_time: 1.2,
+            hippus_amplitude: 0.03,
+            hippus_frequency: 0.2,
+            response: 1.0,
+            scale: 1.0,
+            last_time: None,
+        }
+    }
+}
+
+/// Relative luminance of an RGB color (Rec. 709 weights).
+pub fn luminance(rgb: [f32; 3]) -> f32 {
+    (0.2126 * rgb[0] + 0.7152 * rgb[1] + 0.0722 * rgb[2]).clamp(0.0, 1.0)
+}
+
+impl PupilDynamics {
+    /// Advance the response toward the size for the current light and
+    /// arousal. `bg_color` supplies the light level unless one is set.
+    pub fn update(&mut self, time: f32, bg_color: [f32; 3]) {
+        if !self.enabled {
+            self.response = 1.0;
+            self.scale = 1.0;
+            self.last_time = None;
+            return;
+        }
+
+        let target = self.target_scale(bg_color);
+        // Clamp long gaps (e.g. a hidden window) so the step stays stable
+        let dt = match self.last_time {
+            Some(last) => (time - last).clamp(0.0, 0.1),
+            None => {
+                self.response = target;
+                0.0
+            }
+        };
+        self.last_time = Some(time);
+
+        // Constriction is fast, dilation slow
+        let tau = if target < self.response {
+            self.constriction_time
+        } else {
+            self.dilation_time
+        };
+        self.response += (target - self.response) * (1.0 - (-dt / tau.max(1e-3)).exp());
+
+        // Two incommensurate sines read as an irregular drift
+        let phase = std::f32::consts::TAU * self.hippus_frequency * time;
+        let hippus = 0.6 * phase.sin() + 0.4 * (phase * 1.73 + 1.3).sin();
+        self.scale = (self.response * (1.0 + self.hippus_amplitude * hippus)).max(0.0);
+    }
+
+    /// Current scale for the pupil radius and outline.
+    pub fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    /// Steady-state scale for the given background, without hippus.
+    pub fn target_scale(&self, bg_color: [f32; 3]) -> f32 {
+        let light = self
+            .light_level
+            .unwrap_or_else(|| luminance(bg_color))
+            .clamp(0.0, 1.0);
+        let min = self.min_scale.max(0.0);
+        let max = self.max_scale.max(min);
+        let scale = max + (min - max) * light + self.arousal.clamp(0.0, 1.0) * self.arousal_gain;
+        scale.clamp(min, max)
+    }
+}
@@ -0,0 +1,454 @@
+//! WebSocket server speaking the [`protocol`](crate::protocol) (feature `remote`).
+//!
+//! Network I/O runs on background threads; commands are queued and applied to an
+//! [`EyeController`] on the host's thread when it calls [`RemoteServer::process`],
+//! so the controller never needs to be shared.
+
+use std::collections::HashMap;
+use std::io;
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use crate::controller::EyeController;
+use crate::protocol::{Command, CommandMessage, Reply, ReplyBody};
+
+/// Identifies a connected client for the lifetime of the server.
+pub type ClientId = u64;
+
+/// Longer `subscribe` snapshot intervals are clamped to this.
+const MAX_SNAPSHOT_INTERVAL: Duration = Duration::from_secs(3600);
+
+// ============================================================
+// Configuration
+// ============================================================
+
+#[derive(Clone, Debug)]
+pub struct RemoteServerConfig {
+    /// Address to listen on, e.g. `"127.0.0.1:8765"` or `"0.0.0.0:0"`.
+    pub addr: String,
+    /// Connections beyond this are closed right after accept.
+    pub max_clients: usize,
+    /// Seconds without a `gaze` message after which remote gaze is released
+    /// and the controller falls back to idle behaviour (mouse follow, etc.).
+    pub gaze_timeout: f32,
+    /// How often idle sockets are polled for shutdown and outgoing notifications.
+    pub poll_interval: Duration,
+    /// Connections that have not completed the WebSocket upgrade within this
+    /// time are dropped. Also bounds how long a send to a stalled client may block.
+    pub handshake_timeout: Duration,
+}
+
+impl Default for RemoteServerConfig {
+    fn default() -> Self {
+        Self {
+            addr: "127.0.0.1:8765".into(),
+            max_clients: 8,
+            gaze_timeout: 2.0,
+            poll_interval: Duration::from_millis(10),
+            handshake_timeout: Duration::from_secs(5),
+        }
+    }
+}
+
+// ============================================================
+// Thread messages
+// ============================================================
+
+/// Client thread → host.
+enum ServerEvent {
+    Command(ClientId, CommandMessage, mpsc::Sender<Reply>),
+    Disconnected(ClientId),
+}
+
+/// Host → client thread: serialized notification.
+enum Outbound {
+    Event(String),
+    Snapshot(String),
+}
+
+type Subscribers = Arc<Mutex<HashMap<ClientId, mpsc::Sender<Outbound>>>>;
+
+/// State shared between the server handle and its threads.
+struct Shared {
+    shutdown: AtomicBool,
+    clients: AtomicUsize,
+    subscribers: Subscribers,
+    waker: Box<dyn Fn() + Send + Sync>,
+}
+
+// ============================================================
+// Server
+// ============================================================
+
+pub struct RemoteServer {
+    config: RemoteServerConfig,
+    local_addr: SocketAddr,
+    shared: Arc<Shared>,
+    events: Option<mpsc::Receiver<ServerEvent>>,
+    accept_thread: Option<JoinHandle<()>>,
+    /// Client currently steering the gaze, and when it last did.
+    gaze_owner: Option<(ClientId, f32)>,
+    /// Client currently steering the head.
+    head_owner: Option<ClientId>,
+}
+
+impl RemoteServer {
+    /// Bind and start accepting clients.
+    ///
+    /// `waker` is called from network threads whenever a command is queued or a
+    /// client disconnects, so event-driven hosts can schedule a
+    /// [`process`](Self::process) call (e.g. via an event loop proxy). Hosts that
+    /// process every frame can pass `|| {}`.
+    pub fn start(
+        config: RemoteServerConfig,
+        waker: impl Fn() + Send + Sync + 'static,
+    ) -> io::Result<Self> {
+        let addr = config
+            .addr
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "no address to bind"))?;
+        let listener = TcpListener::bind(addr)?;
+        listener.set_nonblocking(true)?;
+        let local_addr = listener.local_addr()?;
+
+        let shared = Arc::new(Shared {
+            shutdown: AtomicBool::new(false),
+            clients: AtomicUsize::new(0),
+            subscribers: Subscribers::default(),
+            waker: Box::new(waker),
+        });
+        let (events_tx, events_rx) = mpsc::channel();
+
+        let accept_thread = {
+            let shared = shared.clone();
+            let config = config.clone();
+            std::thread::Builder::new()
+                .name("eye-remote-accept".into())
+                .spawn(move || accept_loop(listener, config, shared, events_tx))?
+        };
+
+        Ok(Self {
+            config,
+            local_addr,
+            shared,
+            events: Some(events_rx),
+            accept_thread: Some(accept_thread),
+            gaze_owner: None,
+            head_owner: None,
+        })
+    }
+
+    /// The bound address (useful with port 0).
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Number of currently connected clients.
+    pub fn client_count(&self) -> usize {
+        self.shared.clients.load(Ordering::Relaxed)
+    }
+
+    /// Apply queued commands to `controller`, answer them, and release remote
+    /// gaze when its client disconnected or went quiet for `gaze_timeout`.
+    /// A head target is released when its client disconnects.
+    ///
+    /// `time` is the same clock passed to [`EyeController::update`].
+    pub fn process(&mut self, controller: &mut EyeController, time: f32) {
+        let Some(events) = &self.events else {
+            return;
+        };
+
+        while let Ok(event) = events.try_recv() {
+            match event {
+                ServerEvent::Command(client, msg, reply_tx) => {
+                    match msg.command {
+                        Command::Gaze { .. } => self.gaze_owner = Some((client, time)),
+                        Command::ReleaseGaze => self.gaze_owner = None,
+                        Command::Head { .. } => self.head_owner = Some(client),
+                        Command::ReleaseHead => self.head_owner = None,
+                        _ => {}
+                    }
+                    let _ = reply_tx.send(controller.handle_message(&msg, time));
+                }
+                ServerEvent::Disconnected(client) => {
+                    // Only the client steering the gaze/head gives it up
+                    if self.gaze_owner.is_some_and(|(owner, _)| owner == client) {
+                        self.gaze_owner = None;
+                        controller.remote_gaze = None;
+                    }
+                    if self.head_owner == Some(client) {
+                        self.head_owner = None;
+                        controller.head_target = None;
+                    }
+                }
+            }
+        }
+
+        if let Some((_, last)) = self.gaze_owner {
+            if time - last > self.config.gaze_timeout {
+                self.gaze_owner = None;
+                controller.remote_gaze = None;
+            }
+        }
+    }
+
+    /// Push the events of the last [`EyeController::update`] and a snapshot to
+    /// subscribed clients. Each client filters by its own subscription.
+    pub fn broadcast(&self, controller: &EyeController) {
+        let Ok(mut subs) = self.shared.subscribers.lock() else {
+            return;
+        };
+        if subs.is_empty() {
+            return;
+        }
+
+        let events: Vec<String> = controller
+            .event_notifications()
+            .iter()
+            .filter_map(|n| n.to_json().ok())
+            .collect();
+        let snapshot = controller.snapshot_notification().to_json().ok();
+        subs.retain(|_, tx| {
+            for json in &events {
+                if tx.send(Outbound::Event(json.clone())).is_err() {
+                    return false;
+                }
+            }
+            match &snapshot {
+                Some(json) => tx.send(Outbound::Snapshot(json.clone())).is_ok(),
+                None => true,
+            }
+        });
+    }
+
+    /// Close every connection and stop the server threads.
+    pub fn shutdown(mut self) {
+        self.stop();
+    }
+
+    fn stop(&mut self) {
+        self.shared.shutdown.store(true, Ordering::SeqCst);
+        // Dropping the queue unblocks clients waiting for a reply
+        self.events = None;
+        if let Some(handle) = self.accept_thread.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for RemoteServer {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+// ============================================================
+// Network threads
+// ============================================================
+
+fn accept_loop(
+    listener: TcpListener,
+    config: RemoteServerConfig,
+    shared: Arc<Shared>,
+    events: mpsc::Sender<ServerEvent>,
+) {
+    let mut next_id: ClientId = 0;
+    let mut handles: Vec<JoinHandle<()>> = Vec::new();
+
+    while !shared.shutdown.load(Ordering::SeqCst) {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                handles.retain(|h| !h.is_finished());
+                if shared.clients.load(Ordering::SeqCst) >= config.max_clients {
+                    drop(stream);
+                    continue;
+                }
+                next_id += 1;
+                let id = next_id;
+                let events = events.clone();
+                let poll_interval = config.poll_interval;
+                let handshake_timeout = config.handshake_timeout;
+                shared.clients.fetch_add(1, Ordering::SeqCst);
+                let spawned = std::thread::Builder::new()
+                    .name(format!("eye-remote-client-{id}"))
+                    .spawn({
+                        let shared = shared.clone();
+                        move || {
+                            client_loop(
+                                id,
+                                stream,
+                                poll_interval,
+                                handshake_timeout,
+                                &shared,
+                                &events,
+                            );
+                            shared.clients.fetch_sub(1, Ordering::SeqCst);
+                        }
+                    });
+                match spawned {
+                    Ok(handle) => handles.push(handle),
+                    Err(_) => {
+                        shared.clients.fetch_sub(1, Ordering::SeqCst);
+                    }
+                }
+            }
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                std::thread::sleep(config.poll_interval);
+            }
+            Err(_) => std::thread::sleep(config.poll_interval),
+        }
+    }
+
+    for handle in handles {
+        let _ = handle.join();
+    }
+}
+
+fn client_loop(
+    id: ClientId,
+    stream: TcpStream,
+    poll_interval: Duration,
+    handshake_timeout: Duration,
+    shared: &Shared,
+    events: &mpsc::Sender<ServerEvent>,
+) {
+    // Poll reads so shutdown and pushed notifications are handled in between,
+    // including while waiting for the upgrade request
+    if stream.set_nonblocking(false).is_err()
+        || stream.set_read_timeout(Some(poll_interval)).is_err()
+        || stream.set_write_timeout(Some(handshake_timeout)).is_err()
+    {
+        return;
+    }
+    let Some(mut ws) = handshake(stream, handshake_timeout, shared) else {
+        return;
+    };
+
+    let (out_tx, out_rx) = mpsc::channel();
+    if let Ok(mut subs) = shared.subscribers.lock() {
+        subs.insert(id, out_tx);
+    }
+    let mut push_events = false;
+    let mut snapshot_interval: Option<Duration> = None;
+    let mut last_snapshot: Option<Instant> = None;
+
+    'client: loop {
+        if shared.shutdown.load(Ordering::SeqCst) {
+            let _ = ws.close(None);
+            let _ = ws.flush();
+            break;
+        }
+
+        match ws.read() {
+            Ok(msg) if msg.is_text() => {
+                let text = msg.into_text().unwrap_or_default();
+                let reply = match CommandMessage::from_json(&text) {
+                    Ok(msg) => match msg.command {
+                        // Subscriptions are per connection, handled here
+                        Command::Subscribe {
+                            events,
+                            snapshot_interval: interval,
+                        } => match interval.map(snapshot_period).transpose() {
+                            Ok(interval) => {
+                                push_events = events;
+                                snapshot_interval = interval;
+                                Reply::new(msg.id, ReplyBody::Ack)
+                            }
+                            Err(message) => Reply::error(msg.id, message),
+                        },
+                        Command::Unsubscribe => {
+                            push_events = false;
+                            snapshot_interval = None;
+                            Reply::new(msg.id, ReplyBody::Ack)
+                        }
+                        // Everything else is applied on the host thread
+                        _ => {
+                            let (reply_tx, reply_rx) = mpsc::channel();
+                            if events.send(ServerEvent::Command(id, msg, reply_tx)).is_err() {
+                                break;
+                            }
+                            (shared.waker)();
+                            match reply_rx.recv() {
+                                Ok(reply) => reply,
+                                Err(_) => break,
+                            }
+                        }
+                    },
+                    Err(e) => e.to_reply(),
+                };
+                if reply.should_send() {
+                    if let Ok(json) = reply.to_json() {
+                        if ws.send(tungstenite::Message::text(json)).is_err() {
+                            break;
+                        }
+                    }
+                }
+            }
+            Ok(msg) if msg.is_close() => break,
+            Err(tungstenite::Error::Io(e))
+                if matches!(e.kind(), io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut) => {}
+            Err(_) => break,
+            _ => {}
+        }
+
+        while let Ok(out) = out_rx.try_recv() {
+            let json = match out {
+                Outbound::Event(json) if push_events => json,
+                Outbound::Snapshot(json) => {
+                    let Some(interval) = snapshot_interval else {
+                        continue;
+                    };
+                    if last_snapshot.is_some_and(|t| t.elapsed() < interval) {
+                        continue;
+                    }
+                    last_snapshot = Some(Instant::now());
+                    json
+                }
+                _ => continue,
+            };
+            if ws.send(tungstenite::Message::text(json)).is_err() {
+                break 'client;
+            }
+        }
+    }
+
+    if let Ok(mut subs) = shared.subscribers.lock() {
+        subs.remove(&id);
+    }
+    let _ = events.send(ServerEvent::Disconnected(id));
+    (shared.waker)();
+}
+
+/// Client-supplied snapshot interval in seconds, clamped to
+/// [`MAX_SNAPSHOT_INTERVAL`].
+fn snapshot_period(seconds: f32) -> Result<Duration, String> {
+    Duration::try_from_secs_f32(seconds.max(0.0))
+        .map(|d| d.min(MAX_SNAPSHOT_INTERVAL))
+        .map_err(|_| format!("invalid snapshot_interval `{seconds:?}`"))
+}
+
+/// Complete the WebSocket upgrade, giving up on shutdown or after `timeout`.
+fn handshake(
+    stream: TcpStream,
+    timeout: Duration,
+    shared: &Shared,
+) -> Option<tungstenite::WebSocket<TcpStream>> {
+    let deadline = Instant::now() + timeout;
+    let mut result = tungstenite::accept(stream);
+    loop {
+        match result {
+            Ok(ws) => return Some(ws),
+            Err(tungstenite::HandshakeError::Interrupted(mid)) => {
+                if shared.shutdown.load(Ordering::SeqCst) || Instant::now() >= deadline {
+                    return None;
+                }
+                result = mid.handshake();
+            }
+            Err(tungstenite::HandshakeError::Failure(_)) => return None,
+        }
+    }
+}
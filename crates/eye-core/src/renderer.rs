@@ -1,4 +1,4 @@
-use crate::outline::{BezierOutline, EyebrowOutline};
+use crate::outline::{BezierOutline, EyebrowOutline, Highlight, OutlineUniform};
 
 /// GPU uniform structure for a single canonical eye.
 /// The shader mirrors the X coordinate to render two eyes.
@@ -15,10 +15,9 @@ pub struct EyeUniforms {
     pub sclera_color: [f32; 3],      // offset 0   | vec3f
     pub squash_stretch: f32,         // offset 12  | >0 = squash, <0 = stretch
 
-    // -- Highlight -- (16 bytes, offset 16)
-    pub highlight_offset: [f32; 2],  // offset 16  | vec2f
-    pub highlight_radius: f32,       // offset 24
-    pub highlight_intensity: f32,    // offset 28
+    // -- Highlights -- (16 bytes, offset 16)
+    pub highlight_count: u32,        // offset 16  | entries used in `highlights`
+    pub _pad_highlight: [u32; 3],    // offset 20
 
     // -- Global -- (32 bytes, offset 32)
     pub bg_color: [f32; 3],          // offset 32  | vec3f
@@ -50,44 +49,193 @@ pub struct EyeUniforms {
 
     // -- Head orientation -- (16 bytes, offset 128)
     pub head_pitch: f32,             // offset 128 | [-1, 1] head vertical orientation
-    pub highlight_blur: f32,         // offset 132 | highlight edge softness (0 = sharp)
-    pub _pad_head: [f32; 2],         // offset 136 | padding to 16-byte boundary
-
-    // -- Bezier outline open -- (128 bytes, offset 144)
-    // 4 segments x 2 vec4f each. Each vec4f packs 2 vec2f control points.
+    pub _pad_head: f32,              // offset 132
+    pub bg_alpha: f32,               // offset 136 | background opacity (0 = transparent, 1 = opaque)
+    pub visibility: u32,             // offset 140 | feature bitmask, see EyeUniforms::SHOW_*
+
+    // -- Outline segment counts -- (16 bytes, offset 144)
+    pub outline_segments: u32,           // offset 144 | segments in outline_open/closed
+    pub upper_segments: u32,             // offset 148 | leading segments forming the upper lid
+    pub iris_segments: u32,              // offset 152 | segments in iris_outline
+    pub pupil_segments: u32,             // offset 156 | segments in pupil_outline
+
+    // -- Lids -- (16 bytes, offset 160)
+    // Each lid closes by 1 - (1 - eyelid_close) * (1 - own value), so
+    // eyelid_close drives both and the per-lid values add on top.
+    pub upper_lid_close: f32,            // offset 160 | 0.0 = open, 1.0 = upper lid at outline_closed
+    pub lower_lid_raise: f32,            // offset 164 | 0.0 = open, 1.0 = lower lid at outline_lower_closed
+    pub crease_thickness: f32,           // offset 168 | crease stroke thickness
+    pub crease_offset: f32,              // offset 172 | crease height above the open upper lid
+
+    // -- Lid skin -- (32 bytes, offset 176)
+    // Drawn when SHOW_LID_FILL / SHOW_CREASE are set.
+    pub lid_color: [f32; 3],             // offset 176 | vec3f - skin between open outline and lid edge
+    pub _pad_lid_color: f32,             // offset 188
+    pub crease_color: [f32; 3],          // offset 192 | vec3f
+    pub _pad_crease: f32,                // offset 204
+
+    // -- Bezier outline open -- (384 bytes, offset 208)
+    // Up to MAX_ANCHORS segments x 2 vec4f each. Each vec4f packs 2 vec2f control points.
     // seg[i*2]   = (P0.xy, P1.xy) = (anchor, anchor+handle_out)
     // seg[i*2+1] = (P2.xy, P3.xy) = (next_anchor+handle_in, next_anchor)
-    pub outline_open: [[f32; 4]; 8],
+    pub outline_open: OutlineUniform,
+
+    // -- Bezier outline closed -- (384 bytes, offset 592)
+    // Closed target of the upper lid. Same segment count as outline_open,
+    // so the two blend anchor by anchor.
+    pub outline_closed: OutlineUniform,
 
-    // -- Bezier outline closed -- (128 bytes, offset 256)
-    pub outline_closed: [[f32; 4]; 8],
+    // -- Bezier outline lower closed -- (384 bytes, offset 976)
+    // Closed target of the lower lid, same segment count.
+    pub outline_lower_closed: OutlineUniform,
 
-    // -- Eyebrow -- (224 bytes, offset 384)
-    pub eyebrow_color: [f32; 3],         // offset 384 | vec3f
-    pub eyebrow_base_y: f32,             // offset 396 | base Y position above eye
-    pub eyebrow_follow: f32,             // offset 400 | eyelid follow rate
-    pub _pad_eyebrow: [f32; 3],          // offset 404 | padding to 16-byte boundary
-    pub eyebrow_outline: [[f32; 4]; 12], // offset 416 | 6-segment Bezier control points
+    // -- Eyebrow -- (224 bytes, offset 1360)
+    pub eyebrow_color: [f32; 3],         // offset 1360 | vec3f
+    pub eyebrow_base_y: f32,             // offset 1372 | base Y position above eye
+    pub eyebrow_follow: f32,             // offset 1376 | eyelid follow rate
+    pub _pad_eyebrow: [f32; 3],          // offset 1380 | padding to 16-byte boundary
+    pub eyebrow_outline: [[f32; 4]; 12], // offset 1392 | 6-segment Bezier control points
 
-    // -- Eyelash -- (16 bytes, offset 608)
+    // -- Eyelash -- (16 bytes, offset 1584)
     // Rendered as a stroke on the upper eye outline (no separate shape).
-    pub eyelash_color: [f32; 3],         // offset 608 | vec3f
-    pub eyelash_thickness: f32,          // offset 620 | stroke thickness
+    pub eyelash_color: [f32; 3],         // offset 1584 | vec3f
+    pub eyelash_thickness: f32,          // offset 1596 | stroke thickness
+
+    // -- Pupil -- (16 bytes, offset 1600)
+    pub pupil_color: [f32; 3],           // offset 1600 | vec3f
+    pub pupil_radius: f32,              // offset 1612 | pupil circle radius (used for reset)
 
-    // -- Pupil -- (16 bytes, offset 624)
-    pub pupil_color: [f32; 3],           // offset 624 | vec3f
-    pub pupil_radius: f32,              // offset 636 | pupil circle radius (used for reset)
+    // -- Iris Bezier outline -- (384 bytes, offset 1616)
+    // Same layout as outline_open/closed.
+    pub iris_outline: OutlineUniform,
+
+    // -- Pupil Bezier outline -- (384 bytes, offset 2000)
+    pub pupil_outline: OutlineUniform,
+
+    // -- Highlights -- (MAX_HIGHLIGHTS x 448 bytes, offset 2384)
+    pub highlights: [HighlightUniform; EyeUniforms::MAX_HIGHLIGHTS],
+}
+// Total: 4176 bytes (= 16 * 261)
+
+const _: () = assert!(std::mem::size_of::<EyeUniforms>() == 4176);
+
+/// GPU layout of one [`Highlight`].
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct HighlightUniform {
+    pub offset: [f32; 2],            // offset 0   | vec2f
+    pub radius: f32,                 // offset 8
+    pub aspect: f32,                 // offset 12
+    pub color: [f32; 3],             // offset 16  | vec3f
+    pub intensity: f32,              // offset 28
+    pub blur: f32,                   // offset 32
+    pub parallax: f32,               // offset 36
+    pub rotation: f32,               // offset 40
+    pub shape: u32,                  // offset 44  | HighlightShape::to_uniform
+    pub segments: u32,               // offset 48  | segments in outline
+    pub _pad: [u32; 3],              // offset 52
+    pub outline: OutlineUniform,     // offset 64  | same layout as outline_open
+}
+// Total: 448 bytes (= 16 * 28)
 
-    // -- Iris Bezier outline -- (128 bytes, offset 640)
-    // 4 segments x 2 vec4f each. Same layout as outline_open/closed.
-    pub iris_outline: [[f32; 4]; 8],
+const _: () = assert!(std::mem::size_of::<HighlightUniform>() == 448);
 
-    // -- Pupil Bezier outline -- (128 bytes, offset 768)
-    pub pupil_outline: [[f32; 4]; 8],
+impl From<&Highlight> for HighlightUniform {
+    fn from(h: &Highlight) -> Self {
+        Self {
+            offset: h.offset,
+            radius: h.radius,
+            aspect: h.aspect,
+            color: h.color,
+            intensity: h.intensity,
+            blur: h.blur,
+            parallax: h.parallax,
+            rotation: h.rotation,
+            shape: h.shape.to_uniform(),
+            segments: h.outline.segment_count(),
+            _pad: [0; 3],
+            outline: h.outline.to_uniform_array(),
+        }
+    }
 }
-// Total: 912 bytes (= 16 * 57)
 
-const _: () = assert!(std::mem::size_of::<EyeUniforms>() == 912);
+impl EyeUniforms {
+    /// Most highlights the uniform layout holds; extra ones are not drawn.
+    pub const MAX_HIGHLIGHTS: usize = 4;
+
+    pub const SHOW_EYEBROW: u32 = 1 << 0;
+    pub const SHOW_EYELASH: u32 = 1 << 1;
+    pub const SHOW_HIGHLIGHT: u32 = 1 << 2;
+    pub const SHOW_IRIS: u32 = 1 << 3;
+    pub const SHOW_PUPIL: u32 = 1 << 4;
+    pub const SHOW_LID_FILL: u32 = 1 << 5;
+    pub const SHOW_CREASE: u32 = 1 << 6;
+    pub const SHOW_ALL: u32 = Self::SHOW_EYEBROW
+        | Self::SHOW_EYELASH
+        | Self::SHOW_HIGHLIGHT
+        | Self::SHOW_IRIS
+        | Self::SHOW_PUPIL
+        | Self::SHOW_LID_FILL
+        | Self::SHOW_CREASE;
+    /// Features with a per-eye and a global `show_*` toggle in the controller.
+    pub const SHOW_FEATURES: u32 = Self::SHOW_EYEBROW
+        | Self::SHOW_EYELASH
+        | Self::SHOW_HIGHLIGHT
+        | Self::SHOW_IRIS
+        | Self::SHOW_PUPIL;
+
+    /// Whether every feature in `features` (`SHOW_*` bits) is drawn.
+    pub fn is_visible(&self, features: u32) -> bool {
+        self.visibility & features == features
+    }
+
+    /// Show or hide the features in `features` (`SHOW_*` bits).
+    pub fn set_visible(&mut self, features: u32, visible: bool) {
+        if visible {
+            self.visibility |= features;
+        } else {
+            self.visibility &= !features;
+        }
+    }
+
+    /// Upload the open eye outline and the closed targets of the upper and
+    /// lower lids. The closed outlines are resampled to the open one's
+    /// layout (see [`BezierOutline::resampled_like`]), so the shader can
+    /// blend them segment by segment and both split at the same corner.
+    pub fn set_eye_outline(
+        &mut self,
+        open: &BezierOutline,
+        closed: &BezierOutline,
+        lower_closed: &BezierOutline,
+    ) {
+        self.outline_open = open.to_uniform_array();
+        self.outline_closed = closed.resampled_like(open).to_uniform_array();
+        self.outline_lower_closed = lower_closed.resampled_like(open).to_uniform_array();
+        self.outline_segments = open.segment_count();
+        self.upper_segments = open.upper_segment_count();
+    }
+
+    /// Upload the iris outline.
+    pub fn set_iris_outline(&mut self, outline: &BezierOutline) {
+        self.iris_outline = outline.to_uniform_array();
+        self.iris_segments = outline.segment_count();
+    }
+
+    /// Upload the pupil outline.
+    pub fn set_pupil_outline(&mut self, outline: &BezierOutline) {
+        self.pupil_outline = outline.to_uniform_array();
+        self.pupil_segments = outline.segment_count();
+    }
+
+    /// Upload the highlights, up to `MAX_HIGHLIGHTS`.
+    pub fn set_highlights(&mut self, highlights: &[Highlight]) {
+        let count = highlights.len().min(Self::MAX_HIGHLIGHTS);
+        for (slot, h) in self.highlights.iter_mut().zip(&highlights[..count]) {
+            *slot = HighlightUniform::from(h);
+        }
+        self.highlight_count = count as u32;
+    }
+}
 
 /// Paired uniform structure: one set per eye.
 /// The shader reads `pair.left` for the left eye and `pair.right` for the right eye.
@@ -99,21 +247,20 @@ pub struct EyePairUniforms {
     pub left: EyeUniforms,
     pub right: EyeUniforms,
 }
-// Total: 1120 bytes (= 560 * 2)
+// Total: 8352 bytes (= 4176 * 2)
 
-const _: () = assert!(std::mem::size_of::<EyePairUniforms>() == 1824);
+const _: () = assert!(std::mem::size_of::<EyePairUniforms>() == 8352);
 
 impl Default for EyeUniforms {
     fn default() -> Self {
-        Self {
+        let mut u = Self {
             // Sclera
             sclera_color: [0.95, 0.95, 0.95],
             squash_stretch: 0.0,
 
-            // Highlight
-            highlight_offset: [-0.04, 0.06],
-            highlight_radius: 0.03,
-            highlight_intensity: 0.9,
+            // Highlights are set below
+            highlight_count: 0,
+            _pad_highlight: [0; 3],
 
             // Global
             bg_color: [0.045, 0.097, 0.199],
@@ -145,12 +292,28 @@ impl Default for EyeUniforms {
 
             // Head orientation
             head_pitch: 0.0,
-            highlight_blur: 0.0,
-            _pad_head: [0.0, 0.0],
-
-            // Bezier outline
-            outline_open: BezierOutline::ellipse(0.28, 0.35).to_uniform_array(),
-            outline_closed: BezierOutline::closed_slit_asymmetric(0.20, -0.20, -0.015).to_uniform_array(),
+            _pad_head: 0.0,
+            bg_alpha: 1.0,
+            // Lid skin and crease are opt-in (see EyelidShape)
+            visibility: EyeUniforms::SHOW_ALL
+                & !(EyeUniforms::SHOW_LID_FILL | EyeUniforms::SHOW_CREASE),
+
+            // Outlines and segment counts are set below
+            outline_segments: 0,
+            upper_segments: 0,
+            iris_segments: 0,
+            pupil_segments: 0,
+            upper_lid_close: 0.0,
+            lower_lid_raise: 0.0,
+            crease_thickness: 0.008,
+            crease_offset: 0.06,
+            lid_color: [0.80, 0.52, 0.40],
+            _pad_lid_color: 0.0,
+            crease_color: [0.30, 0.15, 0.12],
+            _pad_crease: 0.0,
+            outline_open: [[0.0; 4]; 2 * BezierOutline::MAX_ANCHORS],
+            outline_closed: [[0.0; 4]; 2 * BezierOutline::MAX_ANCHORS],
+            outline_lower_closed: [[0.0; 4]; 2 * BezierOutline::MAX_ANCHORS],
 
             // Eyebrow
             eyebrow_color: [0.009, 0.009, 0.035],
@@ -167,19 +330,43 @@ impl Default for EyeUniforms {
             pupil_color: [0.013, 0.013, 0.030],
             pupil_radius: 0.05,
 
-            // Iris outline
-            iris_outline: BezierOutline::circle(0.15).to_uniform_array(),
-
-            // Pupil outline
-            pupil_outline: BezierOutline::circle(0.08).to_uniform_array(),
-        }
+            iris_outline: [[0.0; 4]; 2 * BezierOutline::MAX_ANCHORS],
+            pupil_outline: [[0.0; 4]; 2 * BezierOutline::MAX_ANCHORS],
+
+            highlights: [bytemuck::Zeroable::zeroed(); EyeUniforms::MAX_HIGHLIGHTS],
+        };
+        let closed = BezierOutline::closed_slit_asymmetric(0.20, -0.20, -0.015);
+        u.set_eye_outline(&BezierOutline::ellipse(0.28, 0.35), &closed, &closed);
+        u.set_iris_outline(&BezierOutline::circle(0.15));
+        u.set_pupil_outline(&BezierOutline::circle(0.08));
+        u.set_highlights(&[Highlight::default()]);
+        u
     }
 }
 
+/// How the render pass treats the existing contents of the target.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LoadMode {
+    /// Clear to transparent black before drawing.
+    #[default]
+    Clear,
+    /// Keep the target's contents and composite the eyes over them.
+    /// Combine with `bg_alpha = 0` so only the eyes cover the existing frame.
+    Load,
+}
+
+/// Renders the eye pair with a single fullscreen triangle.
+///
+/// The fragment shader outputs premultiplied alpha and the pipeline blends
+/// with `PREMULTIPLIED_ALPHA_BLENDING`. With an opaque background
+/// (`bg_alpha = 1`) every pixel has alpha 1, so the output is the same as a
+/// plain replace.
 pub struct EyeRenderer {
     pipeline: wgpu::RenderPipeline,
     uniform_buffer: wgpu::Buffer,
     bind_group: wgpu::BindGroup,
+    target_format: wgpu::TextureFormat,
+    load_mode: LoadMode,
 }
 
 impl EyeRenderer {
@@ -240,7 +427,7 @@ impl EyeRenderer {
                 entry_point: Some("fs_main"),
                 targets: &[Some(wgpu::ColorTargetState {
                     format: target_format,
-                    blend: Some(wgpu::BlendState::REPLACE),
+                    blend: Some(wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING),
                     write_mask: wgpu::ColorWrites::ALL,
                 })],
                 compilation_options: Default::default(),
@@ -259,6 +446,8 @@ impl EyeRenderer {
             pipeline,
             uniform_buffer,
             bind_group,
+            target_format,
+            load_mode: LoadMode::default(),
         }
     }
 
@@ -274,6 +463,19 @@ impl EyeRenderer {
         &self.uniform_buffer
     }
 
+    /// Color format the pipeline was built for.
+    pub fn target_format(&self) -> wgpu::TextureFormat {
+        self.target_format
+    }
+
+    pub fn load_mode(&self) -> LoadMode {
+        self.load_mode
+    }
+
+    pub fn set_load_mode(&mut self, mode: LoadMode) {
+        self.load_mode = mode;
+    }
+
     pub fn render(
         &self,
         encoder: &mut wgpu::CommandEncoder,
@@ -283,13 +485,18 @@ impl EyeRenderer {
     ) {
         queue.write_buffer(&self.uniform_buffer, 0, bytemuck::bytes_of(params));
 
+        let load = match self.load_mode {
+            LoadMode::Clear => wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+            LoadMode::Load => wgpu::LoadOp::Load,
+        };
+
         let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
             label: Some("eye_render_pass"),
             color_attachments: &[Some(wgpu::RenderPassColorAttachment {
                 view: target,
                 resolve_target: None,
                 ops: wgpu::Operations {
-                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    load,
                     store: wgpu::StoreOp::Store,
                 },
             })],
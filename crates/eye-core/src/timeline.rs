@@ -1,16 +1,19 @@
 use serde::{Deserialize, Serialize};
 
-use crate::animation::{apply_easing, Easing};
+use crate::animation::{apply_easing_for, Easing, WinkEye, WINK_HOLD};
 use crate::config::{
-    BezierAnchorConfig, BezierOutlineConfig, EyeShapeConfig, EyeSideConfig, EyebrowOutlineConfig,
-    EyebrowShapeConfig, EyelashShapeConfig,
+    BezierAnchorConfig, BezierOutlineConfig, EyeConfig, EyeSideConfig, GlobalConfig,
+    HighlightConfig, NodConfig, LEGACY_HIGHLIGHT_FIELDS,
 };
+use crate::controller::{EyeController, EyeSideState};
 
 // ============================================================
 // Timeline easing (serde-compatible wrapper for animation::Easing)
 // ============================================================
 
-#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+/// Unit variants serialize as plain strings (`"BackOut"`), parametric ones
+/// as objects (`{"CubicBezier": {"x1": 0.25, "y1": 0.1, "x2": 0.25, "y2": 1.0}}`).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub enum TimelineEasing {
     Linear,
     EaseIn,
@@ -18,7 +21,18 @@ pub enum TimelineEasing {
     EaseInOut,
     BounceOut,
     ElasticOut,
+    #[default]
     BackOut,
+    /// CSS-style `cubic-bezier(x1, y1, x2, y2)`.
+    CubicBezier { x1: f32, y1: f32, x2: f32, y2: f32 },
+    /// `count` equal jumps, at the start or the end of each interval.
+    Steps {
+        count: u32,
+        #[serde(default)]
+        jump_start: bool,
+    },
+    /// Damped spring (unit mass), timed in seconds over the transition.
+    Spring { stiffness: f32, damping: f32 },
 }
 
 impl TimelineEasing {
@@ -31,10 +45,19 @@ impl TimelineEasing {
             Self::BounceOut => Easing::BounceOut,
             Self::ElasticOut => Easing::ElasticOut,
             Self::BackOut => Easing::BackOut,
+            Self::CubicBezier { x1, y1, x2, y2 } => Easing::CubicBezier { x1, y1, x2, y2 },
+            Self::Steps { count, jump_start } => Easing::Steps { count, jump_start },
+            Self::Spring { stiffness, damping } => Easing::Spring { stiffness, damping },
         }
     }
 
-    pub const ALL: [Self; 7] = [
+    /// Eased progress for a transition of `duration` seconds.
+    pub fn apply(self, t: f32, duration: f32) -> f32 {
+        apply_easing_for(t, self.to_easing(), duration)
+    }
+
+    /// One of each kind; parametric kinds carry their default parameters.
+    pub const ALL: [Self; 10] = [
         Self::Linear,
         Self::EaseIn,
         Self::EaseOut,
@@ -42,8 +65,27 @@ impl TimelineEasing {
         Self::BounceOut,
         Self::ElasticOut,
         Self::BackOut,
+        Self::CubicBezier {
+            x1: 0.25,
+            y1: 0.1,
+            x2: 0.25,
+            y2: 1.0,
+        },
+        Self::Steps {
+            count: 4,
+            jump_start: false,
+        },
+        Self::Spring {
+            stiffness: 170.0,
+            damping: 26.0,
+        },
     ];
 
+    /// Whether `other` is the same kind of curve, ignoring parameters.
+    pub fn same_kind(self, other: Self) -> bool {
+        std::mem::discriminant(&self) == std::mem::discriminant(&other)
+    }
+
     pub fn label(self) -> &'static str {
         match self {
             Self::Linear => "Linear",
@@ -53,16 +95,13 @@ impl TimelineEasing {
             Self::BounceOut => "Bounce Out",
             Self::ElasticOut => "Elastic Out",
             Self::BackOut => "Back Out",
+            Self::CubicBezier { .. } => "Cubic Bezier",
+            Self::Steps { .. } => "Steps",
+            Self::Spring { .. } => "Spring",
         }
     }
 }
 
-impl Default for TimelineEasing {
-    fn default() -> Self {
-        Self::BackOut
-    }
-}
-
 // ============================================================
 // Global config subset (interpolatable fields only)
 // ============================================================
@@ -76,59 +115,717 @@ pub struct TimelineGlobalConfig {
     pub focus_distance: f32,
 }
 
+impl From<&GlobalConfig> for TimelineGlobalConfig {
+    fn from(g: &GlobalConfig) -> Self {
+        Self {
+            bg_color: g.bg_color,
+            eye_separation: g.eye_separation,
+            max_angle: g.max_angle,
+            eye_angle: g.eye_angle,
+            focus_distance: g.focus_distance,
+        }
+    }
+}
+
 // ============================================================
-// Keyframe and Timeline
+// Pose: everything a timeline can animate
 // ============================================================
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct TimelineKeyframe {
-    pub label: String,
-    /// Time in seconds from timeline start when this keyframe is fully reached.
-    pub fire_time: f32,
-    /// Duration of transition FROM the previous keyframe TO this one.
-    pub transition_duration: f32,
-    pub easing: TimelineEasing,
-    #[serde(default)]
-    pub blink: bool,
+/// Both eyes plus the animatable globals. Tracks are evaluated on top of a
+/// pose taken from the current (base) config, so un-animated properties keep
+/// whatever the config says.
+#[derive(Clone, Debug)]
+pub struct TimelinePose {
     pub left: EyeSideConfig,
     pub right: EyeSideConfig,
     pub global: TimelineGlobalConfig,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct Timeline {
-    pub keyframes: Vec<TimelineKeyframe>,
+impl From<&EyeConfig> for TimelinePose {
+    fn from(c: &EyeConfig) -> Self {
+        Self {
+            left: c.left.clone(),
+            right: c.right.clone(),
+            global: TimelineGlobalConfig::from(&c.global),
+        }
+    }
 }
 
-impl Timeline {
-    pub fn new() -> Self {
+impl Default for TimelinePose {
+    /// Pose of a newly created controller.
+    fn default() -> Self {
+        let c = EyeController::default();
+        Self::from_state(&c.left, &c.right, c.focus_distance)
+    }
+}
+
+impl TimelinePose {
+    pub fn from_state(left: &EyeSideState, right: &EyeSideState, focus_distance: f32) -> Self {
         Self {
-            keyframes: Vec::new(),
+            left: EyeSideConfig::from(left),
+            right: EyeSideConfig::from(right),
+            global: TimelineGlobalConfig {
+                bg_color: left.uniforms.bg_color,
+                eye_separation: left.uniforms.eye_separation,
+                max_angle: left.uniforms.max_angle,
+                eye_angle: left.uniforms.eye_angle,
+                focus_distance,
+            },
         }
     }
 
-    pub fn total_duration(&self) -> f32 {
-        self.keyframes.last().map(|kf| kf.fire_time).unwrap_or(0.0)
+    pub fn apply_to_state(
+        &self,
+        left: &mut EyeSideState,
+        right: &mut EyeSideState,
+        focus_distance: &mut f32,
+    ) {
+        self.left.apply_to(left);
+        self.right.apply_to(right);
+        for side in [left, right] {
+            side.uniforms.bg_color = self.global.bg_color;
+            side.uniforms.eye_separation = self.global.eye_separation;
+            side.uniforms.max_angle = self.global.max_angle;
+            side.uniforms.eye_angle = self.global.eye_angle;
+        }
+        *focus_distance = self.global.focus_distance;
     }
 
-    pub fn sort(&mut self) {
-        self.keyframes
-            .sort_by(|a, b| a.fire_time.partial_cmp(&b.fire_time).unwrap());
+    /// Write the pose back into a full config (links and non-animatable
+    /// globals are left untouched).
+    pub fn apply_to_config(&self, c: &mut EyeConfig) {
+        c.left = self.left.clone();
+        c.right = self.right.clone();
+        c.global.bg_color = self.global.bg_color;
+        c.global.eye_separation = self.global.eye_separation;
+        c.global.max_angle = self.global.max_angle;
+        c.global.eye_angle = self.global.eye_angle;
+        c.global.focus_distance = self.global.focus_distance;
     }
+}
 
-    pub fn to_json(&self) -> Result<String, serde_json::Error> {
-        serde_json::to_string_pretty(self)
+// ============================================================
+// Animatable properties
+// ============================================================
+
+/// A single animatable property. Per-eye properties address an
+/// [`EyeSideConfig`] field; the global ones a [`TimelineGlobalConfig`] field.
+/// Outline anchors are addressed by index and animate position and both
+/// handles together. Eye, iris and pupil outlines vary in size, so their
+/// tracks remember the anchor count they were keyed on and the outline is
+/// resampled to it before the keys apply (see [`TimelineTrack::anchor_count`]).
+/// Highlights are addressed by index too;
+/// tracks for a highlight the eye does not have are ignored, and a
+/// highlight's shape and outline are not animated.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrackProperty {
+    // Per-eye scalars
+    #[default]
+    EyelidClose,
+    UpperLidClose,
+    LowerLidRaise,
+    IrisRadius,
+    IrisFollow,
+    IrisOffsetY,
+    PupilRadius,
+    LookX,
+    LookY,
+    CloseArch,
+    EyebrowBaseY,
+    EyebrowFollow,
+    EyelashThickness,
+    CreaseThickness,
+    CreaseOffset,
+    // Per-eye vectors
+    ScleraColor,
+    IrisColor,
+    PupilColor,
+    EyebrowColor,
+    EyebrowThickness,
+    EyelashColor,
+    LidColor,
+    CreaseColor,
+    // Per-eye highlights
+    HighlightOffset(usize),
+    HighlightRadius(usize),
+    HighlightAspect(usize),
+    HighlightRotation(usize),
+    HighlightIntensity(usize),
+    HighlightBlur(usize),
+    HighlightParallax(usize),
+    HighlightColor(usize),
+    // Per-eye outline anchors
+    EyeOpenAnchor(usize),
+    EyeClosedAnchor(usize),
+    EyebrowAnchor(usize),
+    IrisAnchor(usize),
+    PupilAnchor(usize),
+    // Globals
+    BgColor,
+    EyeSeparation,
+    MaxAngle,
+    EyeAngle,
+    FocusDistance,
+}
+
+impl TrackProperty {
+    /// Every property without an index, in display order.
+    pub const FIXED: [Self; 28] = [
+        Self::EyelidClose,
+        Self::UpperLidClose,
+        Self::LowerLidRaise,
+        Self::LookX,
+        Self::LookY,
+        Self::IrisRadius,
+        Self::IrisFollow,
+        Self::IrisOffsetY,
+        Self::PupilRadius,
+        Self::CloseArch,
+        Self::EyebrowBaseY,
+        Self::EyebrowFollow,
+        Self::EyebrowThickness,
+        Self::EyelashThickness,
+        Self::CreaseThickness,
+        Self::CreaseOffset,
+        Self::ScleraColor,
+        Self::IrisColor,
+        Self::PupilColor,
+        Self::EyebrowColor,
+        Self::EyelashColor,
+        Self::LidColor,
+        Self::CreaseColor,
+        Self::BgColor,
+        Self::EyeSeparation,
+        Self::MaxAngle,
+        Self::EyeAngle,
+        Self::FocusDistance,
+    ];
+
+    /// Highlight properties (at index 0), in display order.
+    pub const HIGHLIGHTS: [Self; 8] = [
+        Self::HighlightOffset(0),
+        Self::HighlightRadius(0),
+        Self::HighlightAspect(0),
+        Self::HighlightRotation(0),
+        Self::HighlightIntensity(0),
+        Self::HighlightBlur(0),
+        Self::HighlightParallax(0),
+        Self::HighlightColor(0),
+    ];
+
+    /// Anchor properties (at index 0), in display order.
+    pub const ANCHORS: [Self; 5] = [
+        Self::EyeOpenAnchor(0),
+        Self::EyeClosedAnchor(0),
+        Self::EyebrowAnchor(0),
+        Self::IrisAnchor(0),
+        Self::PupilAnchor(0),
+    ];
+
+    /// Every property of `side`, highlights and anchors included, followed
+    /// by the globals.
+    pub fn all_for(side: &EyeSideConfig) -> Vec<Self> {
+        let mut out: Vec<Self> = Self::FIXED
+            .iter()
+            .copied()
+            .filter(|p| !p.is_global())
+            .collect();
+        for i in 0..side.highlights.len() {
+            out.extend(Self::HIGHLIGHTS.iter().map(|p| p.with_index(i)));
+        }
+        for i in 0..side.eye_shape.open.anchors.len() {
+            out.push(Self::EyeOpenAnchor(i));
+        }
+        for i in 0..side.eye_shape.closed.anchors.len() {
+            out.push(Self::EyeClosedAnchor(i));
+        }
+        for i in 0..side.eyebrow_shape.outline.anchors.len() {
+            out.push(Self::EyebrowAnchor(i));
+        }
+        for i in 0..side.iris_shape.anchors.len() {
+            out.push(Self::IrisAnchor(i));
+        }
+        for i in 0..side.pupil_shape.anchors.len() {
+            out.push(Self::PupilAnchor(i));
+        }
+        out.extend(Self::FIXED.iter().copied().filter(|p| p.is_global()));
+        out
     }
 
-    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
-        serde_json::from_str(json)
+    pub fn is_global(self) -> bool {
+        matches!(
+            self,
+            Self::BgColor
+                | Self::EyeSeparation
+                | Self::MaxAngle
+                | Self::EyeAngle
+                | Self::FocusDistance
+        )
+    }
+
+    /// Channel a layered timeline claims to drive this property.
+    pub fn channel(self) -> AnimationChannel {
+        match self {
+            Self::LookX | Self::LookY | Self::FocusDistance => AnimationChannel::Gaze,
+            Self::EyelidClose
+            | Self::UpperLidClose
+            | Self::LowerLidRaise
+            | Self::CloseArch
+            | Self::EyelashThickness
+            | Self::EyelashColor
+            | Self::LidColor
+            | Self::CreaseColor
+            | Self::CreaseThickness
+            | Self::CreaseOffset
+            | Self::EyeOpenAnchor(_)
+            | Self::EyeClosedAnchor(_) => AnimationChannel::Eyelids,
+            Self::EyebrowBaseY
+            | Self::EyebrowFollow
+            | Self::EyebrowColor
+            | Self::EyebrowThickness
+            | Self::EyebrowAnchor(_) => AnimationChannel::Brows,
+            Self::IrisRadius
+            | Self::IrisFollow
+            | Self::IrisOffsetY
+            | Self::PupilRadius
+            | Self::HighlightOffset(_)
+            | Self::HighlightRadius(_)
+            | Self::HighlightAspect(_)
+            | Self::HighlightRotation(_)
+            | Self::HighlightIntensity(_)
+            | Self::HighlightBlur(_)
+            | Self::HighlightParallax(_)
+            | Self::HighlightColor(_)
+            | Self::ScleraColor
+            | Self::IrisColor
+            | Self::PupilColor
+            | Self::IrisAnchor(_)
+            | Self::PupilAnchor(_) => AnimationChannel::Eyes,
+            Self::BgColor | Self::EyeSeparation | Self::MaxAngle | Self::EyeAngle => {
+                AnimationChannel::Scene
+            }
+        }
+    }
+
+    /// Anchor or highlight index, for indexed properties.
+    pub fn index(self) -> Option<usize> {
+        match self {
+            Self::HighlightOffset(i)
+            | Self::HighlightRadius(i)
+            | Self::HighlightAspect(i)
+            | Self::HighlightRotation(i)
+            | Self::HighlightIntensity(i)
+            | Self::HighlightBlur(i)
+            | Self::HighlightParallax(i)
+            | Self::HighlightColor(i)
+            | Self::EyeOpenAnchor(i)
+            | Self::EyeClosedAnchor(i)
+            | Self::EyebrowAnchor(i)
+            | Self::IrisAnchor(i)
+            | Self::PupilAnchor(i) => Some(i),
+            _ => None,
+        }
+    }
+
+    /// Whether this addresses a highlight rather than an outline anchor.
+    pub fn is_highlight(self) -> bool {
+        Self::HIGHLIGHTS.contains(&self.with_index(0))
+    }
+
+    /// Anchor index of eye, iris and pupil anchor properties.
+    pub fn outline_index(self) -> Option<usize> {
+        match self {
+            Self::EyeOpenAnchor(i)
+            | Self::EyeClosedAnchor(i)
+            | Self::IrisAnchor(i)
+            | Self::PupilAnchor(i) => Some(i),
+            _ => None,
+        }
+    }
+
+    /// The eye, iris or pupil outline an anchor property indexes in `s`.
+    /// The eyebrow outline has a fixed size and is not included.
+    pub fn outline(self, s: &EyeSideConfig) -> Option<&BezierOutlineConfig> {
+        match self {
+            Self::EyeOpenAnchor(_) => Some(&s.eye_shape.open),
+            Self::EyeClosedAnchor(_) => Some(&s.eye_shape.closed),
+            Self::IrisAnchor(_) => Some(&s.iris_shape),
+            Self::PupilAnchor(_) => Some(&s.pupil_shape),
+            _ => None,
+        }
+    }
+
+    fn outline_mut(self, s: &mut EyeSideConfig) -> Option<&mut BezierOutlineConfig> {
+        match self {
+            Self::EyeOpenAnchor(_) => Some(&mut s.eye_shape.open),
+            Self::EyeClosedAnchor(_) => Some(&mut s.eye_shape.closed),
+            Self::IrisAnchor(_) => Some(&mut s.iris_shape),
+            Self::PupilAnchor(_) => Some(&mut s.pupil_shape),
+            _ => None,
+        }
+    }
+
+    /// Resample the outline this property indexes for `eye` in `pose` to
+    /// `count` anchors, so anchor indices keyed on such an outline match.
+    pub fn fit_outline(self, pose: &mut TimelinePose, eye: TrackEye, count: usize) {
+        if eye != TrackEye::Right {
+            if let Some(outline) = self.outline_mut(&mut pose.left) {
+                outline.resample(count);
+            }
+        }
+        if eye != TrackEye::Left {
+            if let Some(outline) = self.outline_mut(&mut pose.right) {
+                outline.resample(count);
+            }
+        }
+    }
+
+    /// Same property kind at another index (no-op for unindexed properties).
+    pub fn with_index(self, index: usize) -> Self {
+        match self {
+            Self::HighlightOffset(_) => Self::HighlightOffset(index),
+            Self::HighlightRadius(_) => Self::HighlightRadius(index),
+            Self::HighlightAspect(_) => Self::HighlightAspect(index),
+            Self::HighlightRotation(_) => Self::HighlightRotation(index),
+            Self::HighlightIntensity(_) => Self::HighlightIntensity(index),
+            Self::HighlightBlur(_) => Self::HighlightBlur(index),
+            Self::HighlightParallax(_) => Self::HighlightParallax(index),
+            Self::HighlightColor(_) => Self::HighlightColor(index),
+            Self::EyeOpenAnchor(_) => Self::EyeOpenAnchor(index),
+            Self::EyeClosedAnchor(_) => Self::EyeClosedAnchor(index),
+            Self::EyebrowAnchor(_) => Self::EyebrowAnchor(index),
+            Self::IrisAnchor(_) => Self::IrisAnchor(index),
+            Self::PupilAnchor(_) => Self::PupilAnchor(index),
+            other => other,
+        }
+    }
+
+    /// Iris and pupil properties lock onto their target instead of overshooting.
+    fn locks_on(self) -> bool {
+        matches!(
+            self,
+            Self::IrisColor
+                | Self::PupilColor
+                | Self::IrisRadius
+                | Self::IrisFollow
+                | Self::IrisOffsetY
+                | Self::PupilRadius
+                | Self::LookX
+                | Self::LookY
+                | Self::IrisAnchor(_)
+                | Self::PupilAnchor(_)
+        )
+    }
+
+    pub fn label(self) -> String {
+        let name = match self {
+            Self::EyelidClose => "Eyelid Close",
+            Self::UpperLidClose => "Upper Lid Close",
+            Self::LowerLidRaise => "Lower Lid Raise",
+            Self::IrisRadius => "Iris Radius",
+            Self::IrisFollow => "Iris Follow",
+            Self::IrisOffsetY => "Iris Offset Y",
+            Self::PupilRadius => "Pupil Radius",
+            Self::LookX => "Look X",
+            Self::LookY => "Look Y",
+            Self::CloseArch => "Close Arch",
+            Self::EyebrowBaseY => "Eyebrow Base Y",
+            Self::EyebrowFollow => "Eyebrow Follow",
+            Self::EyelashThickness => "Eyelash Thickness",
+            Self::CreaseThickness => "Crease Thickness",
+            Self::CreaseOffset => "Crease Offset",
+            Self::ScleraColor => "Sclera Color",
+            Self::IrisColor => "Iris Color",
+            Self::PupilColor => "Pupil Color",
+            Self::EyebrowColor => "Eyebrow Color",
+            Self::EyebrowThickness => "Eyebrow Thickness",
+            Self::EyelashColor => "Eyelash Color",
+            Self::LidColor => "Lid Color",
+            Self::CreaseColor => "Crease Color",
+            Self::HighlightOffset(_) => "Highlight Offset",
+            Self::HighlightRadius(_) => "Highlight Radius",
+            Self::HighlightAspect(_) => "Highlight Aspect",
+            Self::HighlightRotation(_) => "Highlight Rotation",
+            Self::HighlightIntensity(_) => "Highlight Intensity",
+            Self::HighlightBlur(_) => "Highlight Blur",
+            Self::HighlightParallax(_) => "Highlight Parallax",
+            Self::HighlightColor(_) => "Highlight Color",
+            Self::EyeOpenAnchor(_) => "Eye Open Anchor",
+            Self::EyeClosedAnchor(_) => "Eye Closed Anchor",
+            Self::EyebrowAnchor(_) => "Eyebrow Anchor",
+            Self::IrisAnchor(_) => "Iris Anchor",
+            Self::PupilAnchor(_) => "Pupil Anchor",
+            Self::BgColor => "Background Color",
+            Self::EyeSeparation => "Eye Separation",
+            Self::MaxAngle => "Max Angle",
+            Self::EyeAngle => "Eye Angle",
+            Self::FocusDistance => "Focus Distance",
+        };
+        match self.index() {
+            Some(i) => format!("{name} {i}"),
+            None => name.to_string(),
+        }
+    }
+
+    /// Current value in `side`, or `None` for globals and missing anchors
+    /// or highlights.
+    pub fn side_value(self, s: &EyeSideConfig) -> Option<TrackValue> {
+        use TrackValue::{Anchor, Scalar, Vec2, Vec3};
+        Some(match self {
+            Self::EyelidClose => Scalar(s.eyelid_close),
+            Self::UpperLidClose => Scalar(s.upper_lid_close),
+            Self::LowerLidRaise => Scalar(s.lower_lid_raise),
+            Self::IrisRadius => Scalar(s.iris_radius),
+            Self::IrisFollow => Scalar(s.iris_follow),
+            Self::IrisOffsetY => Scalar(s.iris_offset_y),
+            Self::PupilRadius => Scalar(s.pupil_radius),
+            Self::LookX => Scalar(s.look_x),
+            Self::LookY => Scalar(s.look_y),
+            Self::CloseArch => Scalar(s.eye_shape.close_arch),
+            Self::EyebrowBaseY => Scalar(s.eyebrow_shape.base_y),
+            Self::EyebrowFollow => Scalar(s.eyebrow_shape.follow),
+            Self::EyelashThickness => Scalar(s.eyelash_shape.thickness),
+            Self::CreaseThickness => Scalar(s.eyelid_shape.crease_thickness),
+            Self::CreaseOffset => Scalar(s.eyelid_shape.crease_offset),
+            Self::ScleraColor => Vec3(s.sclera_color),
+            Self::IrisColor => Vec3(s.iris_color),
+            Self::PupilColor => Vec3(s.pupil_color),
+            Self::EyebrowColor => Vec3(s.eyebrow_shape.color),
+            Self::EyebrowThickness => Vec3(s.eyebrow_shape.thickness),
+            Self::EyelashColor => Vec3(s.eyelash_shape.color),
+            Self::LidColor => Vec3(s.eyelid_shape.color),
+            Self::CreaseColor => Vec3(s.eyelid_shape.crease_color),
+            Self::HighlightOffset(i) => Vec2(s.highlights.get(i)?.offset),
+            Self::HighlightRadius(i) => Scalar(s.highlights.get(i)?.radius),
+            Self::HighlightAspect(i) => Scalar(s.highlights.get(i)?.aspect),
+            Self::HighlightRotation(i) => Scalar(s.highlights.get(i)?.rotation),
+            Self::HighlightIntensity(i) => Scalar(s.highlights.get(i)?.intensity),
+            Self::HighlightBlur(i) => Scalar(s.highlights.get(i)?.blur),
+            Self::HighlightParallax(i) => Scalar(s.highlights.get(i)?.parallax),
+            Self::HighlightColor(i) => Vec3(s.highlights.get(i)?.color),
+            Self::EyeOpenAnchor(i) => Anchor(s.eye_shape.open.anchors.get(i)?.clone()),
+            Self::EyeClosedAnchor(i) => Anchor(s.eye_shape.closed.anchors.get(i)?.clone()),
+            Self::EyebrowAnchor(i) => Anchor(s.eyebrow_shape.outline.anchors.get(i)?.clone()),
+            Self::IrisAnchor(i) => Anchor(s.iris_shape.anchors.get(i)?.clone()),
+            Self::PupilAnchor(i) => Anchor(s.pupil_shape.anchors.get(i)?.clone()),
+            Self::BgColor
+            | Self::EyeSeparation
+            | Self::MaxAngle
+            | Self::EyeAngle
+            | Self::FocusDistance => return None,
+        })
+    }
+
+    /// Write `value` into `side`. Mismatched value kinds are ignored.
+    pub fn set_side(self, s: &mut EyeSideConfig, value: &TrackValue) {
+        match self {
+            Self::EyelidClose => value.store_scalar(&mut s.eyelid_close),
+            Self::UpperLidClose => value.store_scalar(&mut s.upper_lid_close),
+            Self::LowerLidRaise => value.store_scalar(&mut s.lower_lid_raise),
+            Self::IrisRadius => value.store_scalar(&mut s.iris_radius),
+            Self::IrisFollow => value.store_scalar(&mut s.iris_follow),
+            Self::IrisOffsetY => value.store_scalar(&mut s.iris_offset_y),
+            Self::PupilRadius => value.store_scalar(&mut s.pupil_radius),
+            Self::LookX => value.store_scalar(&mut s.look_x),
+            Self::LookY => value.store_scalar(&mut s.look_y),
+            Self::CloseArch => value.store_scalar(&mut s.eye_shape.close_arch),
+            Self::EyebrowBaseY => value.store_scalar(&mut s.eyebrow_shape.base_y),
+            Self::EyebrowFollow => value.store_scalar(&mut s.eyebrow_shape.follow),
+            Self::EyelashThickness => value.store_scalar(&mut s.eyelash_shape.thickness),
+            Self::CreaseThickness => value.store_scalar(&mut s.eyelid_shape.crease_thickness),
+            Self::CreaseOffset => value.store_scalar(&mut s.eyelid_shape.crease_offset),
+            Self::ScleraColor => value.store_vec3(&mut s.sclera_color),
+            Self::IrisColor => value.store_vec3(&mut s.iris_color),
+            Self::PupilColor => value.store_vec3(&mut s.pupil_color),
+            Self::EyebrowColor => value.store_vec3(&mut s.eyebrow_shape.color),
+            Self::EyebrowThickness => value.store_vec3(&mut s.eyebrow_shape.thickness),
+            Self::EyelashColor => value.store_vec3(&mut s.eyelash_shape.color),
+            Self::LidColor => value.store_vec3(&mut s.eyelid_shape.color),
+            Self::CreaseColor => value.store_vec3(&mut s.eyelid_shape.crease_color),
+            Self::HighlightOffset(i)
+            | Self::HighlightRadius(i)
+            | Self::HighlightAspect(i)
+            | Self::HighlightRotation(i)
+            | Self::HighlightIntensity(i)
+            | Self::HighlightBlur(i)
+            | Self::HighlightParallax(i)
+            | Self::HighlightColor(i) => {
+                if let Some(h) = s.highlights.get_mut(i) {
+                    self.set_highlight(h, value);
+                }
+            }
+            Self::EyeOpenAnchor(i) => {
+                s.eye_shape.open.upsample(i + 1);
+                value.store_anchor(s.eye_shape.open.anchors.get_mut(i))
+            }
+            Self::EyeClosedAnchor(i) => {
+                s.eye_shape.closed.upsample(i + 1);
+                value.store_anchor(s.eye_shape.closed.anchors.get_mut(i))
+            }
+            Self::EyebrowAnchor(i) => {
+                value.store_anchor(s.eyebrow_shape.outline.anchors.get_mut(i))
+            }
+            Self::IrisAnchor(i) => {
+                s.iris_shape.upsample(i + 1);
+                value.store_anchor(s.iris_shape.anchors.get_mut(i))
+            }
+            Self::PupilAnchor(i) => {
+                s.pupil_shape.upsample(i + 1);
+                value.store_anchor(s.pupil_shape.anchors.get_mut(i))
+            }
+            Self::BgColor
+            | Self::EyeSeparation
+            | Self::MaxAngle
+            | Self::EyeAngle
+            | Self::FocusDistance => {}
+        }
+    }
+
+    fn set_highlight(self, h: &mut HighlightConfig, value: &TrackValue) {
+        match self {
+            Self::HighlightOffset(_) => value.store_vec2(&mut h.offset),
+            Self::HighlightRadius(_) => value.store_scalar(&mut h.radius),
+            Self::HighlightAspect(_) => value.store_scalar(&mut h.aspect),
+            Self::HighlightRotation(_) => value.store_scalar(&mut h.rotation),
+            Self::HighlightIntensity(_) => value.store_scalar(&mut h.intensity),
+            Self::HighlightBlur(_) => value.store_scalar(&mut h.blur),
+            Self::HighlightParallax(_) => value.store_scalar(&mut h.parallax),
+            Self::HighlightColor(_) => value.store_vec3(&mut h.color),
+            _ => {}
+        }
+    }
+
+    /// Current value in `global`, or `None` for per-eye properties.
+    pub fn global_value(self, g: &TimelineGlobalConfig) -> Option<TrackValue> {
+        match self {
+            Self::BgColor => Some(TrackValue::Vec3(g.bg_color)),
+            Self::EyeSeparation => Some(TrackValue::Scalar(g.eye_separation)),
+            Self::MaxAngle => Some(TrackValue::Scalar(g.max_angle)),
+            Self::EyeAngle => Some(TrackValue::Scalar(g.eye_angle)),
+            Self::FocusDistance => Some(TrackValue::Scalar(g.focus_distance)),
+            _ => None,
+        }
+    }
+
+    pub fn set_global(self, g: &mut TimelineGlobalConfig, value: &TrackValue) {
+        match self {
+            Self::BgColor => value.store_vec3(&mut g.bg_color),
+            Self::EyeSeparation => value.store_scalar(&mut g.eye_separation),
+            Self::MaxAngle => value.store_scalar(&mut g.max_angle),
+            Self::EyeAngle => value.store_scalar(&mut g.eye_angle),
+            Self::FocusDistance => value.store_scalar(&mut g.focus_distance),
+            _ => {}
+        }
+    }
+
+    /// Current value of this property for `eye` in `pose` (`Both` reads the left eye).
+    pub fn value_in(self, pose: &TimelinePose, eye: TrackEye) -> Option<TrackValue> {
+        if self.is_global() {
+            return self.global_value(&pose.global);
+        }
+        match eye {
+            TrackEye::Both | TrackEye::Left => self.side_value(&pose.left),
+            TrackEye::Right => self.side_value(&pose.right),
+        }
+    }
+
+    /// Write `value` for `eye` into `pose`.
+    pub fn set_in(self, pose: &mut TimelinePose, eye: TrackEye, value: &TrackValue) {
+        if self.is_global() {
+            self.set_global(&mut pose.global, value);
+            return;
+        }
+        if eye != TrackEye::Right {
+            self.set_side(&mut pose.left, value);
+        }
+        if eye != TrackEye::Left {
+            self.set_side(&mut pose.right, value);
+        }
     }
 }
 
 // ============================================================
-// Interpolation: primitives
+// Track values
 // ============================================================
 
+/// Value of a [`TrackProperty`] at one key. Serialized as a bare number,
+/// array or anchor object.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum TrackValue {
+    Scalar(f32),
+    Vec2([f32; 2]),
+    Vec3([f32; 3]),
+    Anchor(BezierAnchorConfig),
+}
+
+impl TrackValue {
+    /// Component-wise interpolation. Mismatched kinds snap at `t = 1`.
+    pub fn lerp(&self, b: &Self, t: f32) -> Self {
+        match (self, b) {
+            (Self::Scalar(a), Self::Scalar(b)) => Self::Scalar(lerp_f32(*a, *b, t)),
+            (Self::Vec2(a), Self::Vec2(b)) => Self::Vec2(lerp_f32_2(*a, *b, t)),
+            (Self::Vec3(a), Self::Vec3(b)) => Self::Vec3(lerp_f32_3(*a, *b, t)),
+            (Self::Anchor(a), Self::Anchor(b)) => Self::Anchor(lerp_anchor(a, b, t)),
+            _ if t >= 1.0 => b.clone(),
+            _ => self.clone(),
+        }
+    }
+
+    /// Component-wise `self - from`, or `None` for mismatched kinds.
+    pub fn delta(&self, from: &Self) -> Option<Self> {
+        self.zip_with(from, |a, b| a - b)
+    }
+
+    /// Component-wise `self + delta * weight`. Mismatched kinds leave `self`.
+    pub fn add_scaled(&self, delta: &Self, weight: f32) -> Self {
+        self.zip_with(delta, |a, d| a + d * weight)
+            .unwrap_or_else(|| self.clone())
+    }
+
+    fn zip_with(&self, b: &Self, f: impl Fn(f32, f32) -> f32) -> Option<Self> {
+        let zip2 = |a: [f32; 2], b: [f32; 2]| [f(a[0], b[0]), f(a[1], b[1])];
+        Some(match (self, b) {
+            (Self::Scalar(a), Self::Scalar(b)) => Self::Scalar(f(*a, *b)),
+            (Self::Vec2(a), Self::Vec2(b)) => Self::Vec2(zip2(*a, *b)),
+            (Self::Vec3(a), Self::Vec3(b)) => {
+                Self::Vec3([f(a[0], b[0]), f(a[1], b[1]), f(a[2], b[2])])
+            }
+            (Self::Anchor(a), Self::Anchor(b)) => Self::Anchor(BezierAnchorConfig {
+                position: zip2(a.position, b.position),
+                handle_in: zip2(a.handle_in, b.handle_in),
+                handle_out: zip2(a.handle_out, b.handle_out),
+            }),
+            _ => return None,
+        })
+    }
+
+    fn store_scalar(&self, out: &mut f32) {
+        if let Self::Scalar(v) = self {
+            *out = *v;
+        }
+    }
+
+    fn store_vec2(&self, out: &mut [f32; 2]) {
+        if let Self::Vec2(v) = self {
+            *out = *v;
+        }
+    }
+
+    fn store_vec3(&self, out: &mut [f32; 3]) {
+        if let Self::Vec3(v) = self {
+            *out = *v;
+        }
+    }
+
+    fn store_anchor(&self, out: Option<&mut BezierAnchorConfig>) {
+        if let (Self::Anchor(v), Some(out)) = (self, out) {
+            *out = v.clone();
+        }
+    }
+}
+
 fn lerp_f32(a: f32, b: f32, t: f32) -> f32 {
     a + (b - a) * t
 }
@@ -145,130 +842,886 @@ fn lerp_f32_3(a: [f32; 3], b: [f32; 3], t: f32) -> [f32; 3] {
     ]
 }
 
-fn snap_bool_2(a: [bool; 2], b: [bool; 2], t: f32) -> [bool; 2] {
-    if t >= 1.0 {
-        b
-    } else {
-        a
+fn lerp_anchor(a: &BezierAnchorConfig, b: &BezierAnchorConfig, t: f32) -> BezierAnchorConfig {
+    BezierAnchorConfig {
+        position: lerp_f32_2(a.position, b.position, t),
+        handle_in: lerp_f32_2(a.handle_in, b.handle_in, t),
+        handle_out: lerp_f32_2(a.handle_out, b.handle_out, t),
     }
 }
 
 // ============================================================
-// Interpolation: compound types
+// Tracks and events
 // ============================================================
 
-fn lerp_anchor(a: &BezierAnchorConfig, b: &BezierAnchorConfig, t: f32) -> BezierAnchorConfig {
-    BezierAnchorConfig {
-        position: lerp_f32_2(a.position, b.position, t),
-        handle_in: lerp_f32_2(a.handle_in, b.handle_in, t),
-        handle_out: lerp_f32_2(a.handle_out, b.handle_out, t),
+/// Which eye a per-eye track drives. Ignored for global properties.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrackEye {
+    #[default]
+    Both,
+    Left,
+    Right,
+}
+
+impl TrackEye {
+    pub const ALL: [Self; 3] = [Self::Both, Self::Left, Self::Right];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Both => "Both",
+            Self::Left => "Left",
+            Self::Right => "Right",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TrackKey {
+    /// Seconds from timeline start.
+    pub time: f32,
+    pub value: TrackValue,
+    /// Easing of the segment arriving at this key from the previous one.
+    #[serde(default)]
+    pub easing: TimelineEasing,
+}
+
+/// Keyframes of a single property. Before the first key the first value is
+/// held, after the last key the last one.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TimelineTrack {
+    pub property: TrackProperty,
+    #[serde(default)]
+    pub eye: TrackEye,
+    /// Anchor count of the eye, iris or pupil outline the keys were taken
+    /// from. Outlines of another size are resampled to it before the track
+    /// applies. Tracks saved without one were keyed on four anchors.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anchor_count: Option<usize>,
+    pub keys: Vec<TrackKey>,
+}
+
+/// Outline size of anchor tracks saved before [`TimelineTrack::anchor_count`].
+const LEGACY_ANCHOR_COUNT: usize = 4;
+
+impl TimelineTrack {
+    pub fn new(property: TrackProperty, eye: TrackEye) -> Self {
+        Self {
+            property,
+            eye: if property.is_global() {
+                TrackEye::Both
+            } else {
+                eye
+            },
+            anchor_count: None,
+            keys: Vec::new(),
+        }
+    }
+
+    /// Anchor count the keys' index refers to, for eye, iris and pupil
+    /// anchor tracks.
+    pub fn outline_len(&self) -> Option<usize> {
+        let index = self.property.outline_index()?;
+        Some(
+            self.anchor_count
+                .unwrap_or(LEGACY_ANCHOR_COUNT)
+                .max(index + 1),
+        )
+    }
+
+    /// Key the current value of the property in `pose` at `time`. Anchors
+    /// are read from the outline resampled to the track's anchor count,
+    /// which the first key of a new track records. Returns `false` if
+    /// `pose` has no such value.
+    pub fn key_pose(&mut self, time: f32, pose: &TimelinePose, easing: TimelineEasing) -> bool {
+        let side = match self.eye {
+            TrackEye::Both | TrackEye::Left => &pose.left,
+            TrackEye::Right => &pose.right,
+        };
+        let outline = self.property.outline(side);
+        if self.keys.is_empty() {
+            self.anchor_count = outline.map(|o| o.anchors.len());
+        }
+        let value = match (outline, self.outline_len()) {
+            (Some(outline), Some(count)) if outline.anchors.len() != count => {
+                let mut fitted = outline.clone();
+                fitted.resample(count);
+                self.property
+                    .index()
+                    .and_then(|i| fitted.anchors.get(i))
+                    .map(|a| TrackValue::Anchor(a.clone()))
+            }
+            _ => self.property.value_in(pose, self.eye),
+        };
+        let Some(value) = value else {
+            return false;
+        };
+        self.set_key(time, value, easing);
+        true
+    }
+
+    /// Write `value` into `pose`, resampling outlines to the track's anchor
+    /// count first.
+    pub fn set_in(&self, pose: &mut TimelinePose, value: &TrackValue) {
+        if let Some(count) = self.outline_len() {
+            self.property.fit_outline(pose, self.eye, count);
+        }
+        self.property.set_in(pose, self.eye, value);
     }
+
+    pub fn label(&self) -> String {
+        if self.property.is_global() {
+            self.property.label()
+        } else {
+            format!("{} ({})", self.property.label(), self.eye.label())
+        }
+    }
+
+    pub fn sort(&mut self) {
+        self.keys.sort_by(|a, b| a.time.total_cmp(&b.time));
+    }
+
+    /// Insert a key, replacing any existing key at the same time.
+    pub fn set_key(&mut self, time: f32, value: TrackValue, easing: TimelineEasing) {
+        let key = TrackKey {
+            time,
+            value,
+            easing,
+        };
+        match self.keys.iter_mut().find(|k| (k.time - time).abs() < 1e-4) {
+            Some(existing) => *existing = key,
+            None => {
+                self.keys.push(key);
+                self.sort();
+            }
+        }
+    }
+
+    /// Interpolated value at `t` (keys must be sorted).
+    pub fn sample(&self, t: f32) -> Option<TrackValue> {
+        let first = self.keys.first()?;
+        if t <= first.time {
+            return Some(first.value.clone());
+        }
+        for pair in self.keys.windows(2) {
+            let (prev, curr) = (&pair[0], &pair[1]);
+            if t <= curr.time {
+                let span = curr.time - prev.time;
+                let raw_t = if span > 0.0 {
+                    ((t - prev.time) / span).clamp(0.0, 1.0)
+                } else {
+                    1.0
+                };
+                let eased_t = curr.easing.apply(raw_t, span);
+                return Some(prev.value.lerp(&curr.value, eased_t));
+            }
+        }
+        self.keys.last().map(|k| k.value.clone())
+    }
+}
+
+/// What a [`TimelineEvent`] does when the playhead reaches it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum TimelineAction {
+    /// Named marker, reported to the host as
+    /// [`EyeEvent::TimelineMarkerReached`](crate::controller::EyeEvent).
+    Marker { label: String },
+    /// Blink. `None` durations use the natural randomised timing.
+    Blink {
+        #[serde(default)]
+        close_duration: Option<f32>,
+        /// Seconds held fully closed.
+        #[serde(default)]
+        hold_duration: f32,
+        #[serde(default)]
+        open_duration: Option<f32>,
+    },
+    /// Wink: closes only `eye`, with the same timing options as `Blink`.
+    Wink {
+        eye: WinkEye,
+        #[serde(default)]
+        close_duration: Option<f32>,
+        #[serde(default = "default_wink_hold")]
+        hold_duration: f32,
+        #[serde(default)]
+        open_duration: Option<f32>,
+    },
+    /// Nod, optionally with its own parameters instead of the current ones.
+    Nod {
+        #[serde(default)]
+        overrides: Option<NodConfig>,
+    },
+    Microsaccade,
 }
 
-fn lerp_outline(a: &BezierOutlineConfig, b: &BezierOutlineConfig, t: f32) -> BezierOutlineConfig {
-    BezierOutlineConfig {
-        anchors: core::array::from_fn(|i| lerp_anchor(&a.anchors[i], &b.anchors[i], t)),
+impl TimelineAction {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Marker { .. } => "Marker",
+            Self::Blink { .. } => "Blink",
+            Self::Wink { .. } => "Wink",
+            Self::Nod { .. } => "Nod",
+            Self::Microsaccade => "Microsaccade",
+        }
     }
+
+    pub fn blink() -> Self {
+        Self::Blink {
+            close_duration: None,
+            hold_duration: 0.0,
+            open_duration: None,
+        }
+    }
+
+    pub fn wink(eye: WinkEye) -> Self {
+        Self::Wink {
+            eye,
+            close_duration: None,
+            hold_duration: WINK_HOLD,
+            open_duration: None,
+        }
+    }
+}
+
+fn default_wink_hold() -> f32 {
+    WINK_HOLD
+}
+
+/// Entry of the event track, fired once when the playhead reaches `time`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TimelineEvent {
+    pub time: f32,
+    #[serde(flatten)]
+    pub action: TimelineAction,
+}
+
+// ============================================================
+// Layers
+// ============================================================
+
+/// Group of properties that timeline layers and realtime animation
+/// (gaze input, auto-blink, listening nods) contend for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnimationChannel {
+    /// Look direction and focus; realtime: mouse/remote gaze, head follow,
+    /// idle microsaccades and listening nods.
+    Gaze,
+    /// Lid closure, lid outlines and eyelashes; realtime: auto-blink and the
+    /// remote eyelid override.
+    Eyelids,
+    Brows,
+    /// Iris, pupil, highlight and sclera.
+    Eyes,
+    /// Background, eye separation, max angle and eye angle.
+    Scene,
 }
 
-fn lerp_eyebrow_outline(
-    a: &EyebrowOutlineConfig,
-    b: &EyebrowOutlineConfig,
-    t: f32,
-) -> EyebrowOutlineConfig {
-    EyebrowOutlineConfig {
-        anchors: core::array::from_fn(|i| lerp_anchor(&a.anchors[i], &b.anchors[i], t)),
+impl AnimationChannel {
+    pub const ALL: [Self; 5] = [
+        Self::Gaze,
+        Self::Eyelids,
+        Self::Brows,
+        Self::Eyes,
+        Self::Scene,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Gaze => "Gaze",
+            Self::Eyelids => "Eyelids",
+            Self::Brows => "Brows",
+            Self::Eyes => "Eyes",
+            Self::Scene => "Scene",
+        }
     }
 }
 
-fn lerp_eye_shape(a: &EyeShapeConfig, b: &EyeShapeConfig, t: f32) -> EyeShapeConfig {
-    EyeShapeConfig {
-        open: lerp_outline(&a.open, &b.open, t),
-        closed: lerp_outline(&a.closed, &b.closed, t),
-        close_arch: lerp_f32(a.close_arch, b.close_arch, t),
+/// How a layer's tracks combine with what lies beneath them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LayerBlend {
+    /// Crossfade from the underlying value to the track value by `weight`.
+    #[default]
+    Override,
+    /// Add the track's change since its first key, scaled by `weight`.
+    Additive,
+}
+
+impl LayerBlend {
+    pub const ALL: [Self; 2] = [Self::Override, Self::Additive];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Override => "Override",
+            Self::Additive => "Additive",
+        }
     }
 }
 
-fn lerp_eyebrow_shape(
-    a: &EyebrowShapeConfig,
-    b: &EyebrowShapeConfig,
-    t: f32,
-) -> EyebrowShapeConfig {
-    EyebrowShapeConfig {
-        outline: lerp_eyebrow_outline(&a.outline, &b.outline, t),
-        thickness: lerp_f32_3(a.thickness, b.thickness, t),
-        tip_round: snap_bool_2(a.tip_round, b.tip_round, t),
-        base_y: lerp_f32(a.base_y, b.base_y, t),
-        follow: lerp_f32(a.follow, b.follow, t),
-        color: lerp_f32_3(a.color, b.color, t),
+/// Channels a timeline takes over, and how strongly.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TimelineLayer {
+    pub name: String,
+    pub channels: Vec<AnimationChannel>,
+    pub blend: LayerBlend,
+    /// 0.0 = no effect, 1.0 = full.
+    pub weight: f32,
+}
+
+impl Default for TimelineLayer {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            channels: Vec::new(),
+            blend: LayerBlend::Override,
+            weight: 1.0,
+        }
     }
 }
 
-fn lerp_eyelash_shape(
-    a: &EyelashShapeConfig,
-    b: &EyelashShapeConfig,
-    t: f32,
-) -> EyelashShapeConfig {
-    EyelashShapeConfig {
-        color: lerp_f32_3(a.color, b.color, t),
-        thickness: lerp_f32(a.thickness, b.thickness, t),
+impl TimelineLayer {
+    /// Whether realtime animation on this layer's channels is hidden entirely.
+    pub fn replaces_realtime(&self) -> bool {
+        self.blend == LayerBlend::Override && self.weight >= 1.0
     }
 }
 
 // ============================================================
-// Interpolation: full config
+// Timeline
 // ============================================================
 
-/// Interpolate eye-side config with two t values:
-/// - `t`: general easing (may overshoot, e.g. BackOut)
-/// - `t_precise`: non-overshooting easing for iris/pupil (biologically they lock on target)
-pub fn lerp_eye_side(a: &EyeSideConfig, b: &EyeSideConfig, t: f32, t_precise: f32) -> EyeSideConfig {
-    EyeSideConfig {
-        sclera_color: lerp_f32_3(a.sclera_color, b.sclera_color, t),
-        iris_color: lerp_f32_3(a.iris_color, b.iris_color, t_precise),
-        pupil_color: lerp_f32_3(a.pupil_color, b.pupil_color, t_precise),
-        eyelid_close: lerp_f32(a.eyelid_close, b.eyelid_close, t),
-        iris_radius: lerp_f32(a.iris_radius, b.iris_radius, t_precise),
-        iris_follow: lerp_f32(a.iris_follow, b.iris_follow, t_precise),
-        iris_offset_y: lerp_f32(a.iris_offset_y, b.iris_offset_y, t_precise),
-        pupil_radius: lerp_f32(a.pupil_radius, b.pupil_radius, t_precise),
-        highlight_offset: lerp_f32_2(a.highlight_offset, b.highlight_offset, t),
-        highlight_radius: lerp_f32(a.highlight_radius, b.highlight_radius, t),
-        highlight_intensity: lerp_f32(a.highlight_intensity, b.highlight_intensity, t),
-        highlight_blur: lerp_f32(a.highlight_blur, b.highlight_blur, t),
-        look_x: lerp_f32(a.look_x, b.look_x, t_precise),
-        look_y: lerp_f32(a.look_y, b.look_y, t_precise),
-        eye_shape: lerp_eye_shape(&a.eye_shape, &b.eye_shape, t),
-        eyebrow_shape: lerp_eyebrow_shape(&a.eyebrow_shape, &b.eyebrow_shape, t),
-        eyelash_shape: lerp_eyelash_shape(&a.eyelash_shape, &b.eyelash_shape, t),
-        iris_shape: lerp_outline(&a.iris_shape, &b.iris_shape, t_precise),
-        pupil_shape: lerp_outline(&a.pupil_shape, &b.pupil_shape, t_precise),
-    }
-}
-
-pub fn lerp_timeline_global(
-    a: &TimelineGlobalConfig,
-    b: &TimelineGlobalConfig,
-    t: f32,
-) -> TimelineGlobalConfig {
-    TimelineGlobalConfig {
-        bg_color: lerp_f32_3(a.bg_color, b.bg_color, t),
-        eye_separation: lerp_f32(a.eye_separation, b.eye_separation, t),
-        max_angle: lerp_f32(a.max_angle, b.max_angle, t),
-        eye_angle: lerp_f32(a.eye_angle, b.eye_angle, t),
-        focus_distance: lerp_f32(a.focus_distance, b.focus_distance, t),
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct Timeline {
+    pub tracks: Vec<TimelineTrack>,
+    /// Event track (gestures and markers), kept sorted by time.
+    pub events: Vec<TimelineEvent>,
+    /// Channel layers. Without any, the timeline drives every channel and
+    /// realtime animation pauses during playback; with some, a track only
+    /// plays through the first layer claiming its channel, and unclaimed
+    /// channels keep their realtime animation.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub layers: Vec<TimelineLayer>,
+}
+
+/// Serialized form of the track format; [`Timeline`] deserializes through
+/// this after checking for the legacy keyframe format.
+#[derive(Deserialize)]
+struct TrackTimeline {
+    #[serde(default)]
+    tracks: Vec<TimelineTrack>,
+    #[serde(default)]
+    events: Vec<TimelineEvent>,
+    #[serde(default)]
+    layers: Vec<TimelineLayer>,
+}
+
+/// Pre-track format: full snapshots of both eyes per keyframe.
+#[derive(Deserialize)]
+struct LegacyTimeline {
+    keyframes: Vec<TimelineKeyframe>,
+}
+
+impl<'de> Deserialize<'de> for Timeline {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let mut value = serde_json::Value::deserialize(deserializer)?;
+        if value.get("keyframes").is_some() {
+            let legacy: LegacyTimeline = serde_json::from_value(value).map_err(D::Error::custom)?;
+            return Ok(Self::from_keyframes(&legacy.keyframes));
+        }
+        if let Some(tracks) = value.get_mut("tracks").and_then(|t| t.as_array_mut()) {
+            tracks.iter_mut().for_each(migrate_highlight_property);
+        }
+        let data: TrackTimeline = serde_json::from_value(value).map_err(D::Error::custom)?;
+        let mut timeline = Self {
+            tracks: data.tracks,
+            events: data.events,
+            layers: data.layers,
+        };
+        timeline.sort();
+        Ok(timeline)
+    }
+}
+
+/// Highlight properties were unindexed while eyes had a single highlight;
+/// such tracks now drive highlight 0.
+fn migrate_highlight_property(track: &mut serde_json::Value) {
+    let Some(property) = track.get_mut("property") else {
+        return;
+    };
+    if let Some(name) = property
+        .as_str()
+        .filter(|name| LEGACY_HIGHLIGHT_FIELDS.contains(name))
+    {
+        *property = serde_json::json!({ name: 0 });
+    }
+}
+
+impl Timeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether there is nothing to play (no keys and no events).
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty() && self.tracks.iter().all(|t| t.keys.is_empty())
+    }
+
+    /// Time of the last key or event.
+    pub fn total_duration(&self) -> f32 {
+        let keys = self
+            .tracks
+            .iter()
+            .filter_map(|t| t.keys.last())
+            .map(|k| k.time);
+        let events = self.events.iter().map(|e| e.time);
+        keys.chain(events).fold(0.0, f32::max)
+    }
+
+    pub fn sort(&mut self) {
+        for track in &mut self.tracks {
+            track.sort();
+        }
+        self.events.sort_by(|a, b| a.time.total_cmp(&b.time));
+    }
+
+    /// Whether tracks play through channel layers rather than driving everything.
+    pub fn is_layered(&self) -> bool {
+        !self.layers.is_empty()
+    }
+
+    /// First layer claiming `channel`.
+    pub fn layer_for(&self, channel: AnimationChannel) -> Option<&TimelineLayer> {
+        self.layers.iter().find(|l| l.channels.contains(&channel))
+    }
+
+    /// Whether playback replaces realtime animation on `channel`.
+    pub fn drives(&self, channel: AnimationChannel) -> bool {
+        !self.is_layered()
+            || self
+                .layer_for(channel)
+                .is_some_and(TimelineLayer::replaces_realtime)
+    }
+
+    /// The track animating `property` for `eye`, created if missing.
+    pub fn track_mut(&mut self, property: TrackProperty, eye: TrackEye) -> &mut TimelineTrack {
+        let eye = if property.is_global() {
+            TrackEye::Both
+        } else {
+            eye
+        };
+        let index = match self
+            .tracks
+            .iter()
+            .position(|t| t.property == property && t.eye == eye)
+        {
+            Some(i) => i,
+            None => {
+                self.tracks.push(TimelineTrack::new(property, eye));
+                self.tracks.len() - 1
+            }
+        };
+        &mut self.tracks[index]
+    }
+
+    /// Key every property whose value in `to` differs from `from` at `time`.
+    /// Properties equal on both eyes get a single `Both` track.
+    pub fn key_changes(
+        &mut self,
+        time: f32,
+        easing: TimelineEasing,
+        from: &TimelinePose,
+        to: &TimelinePose,
+    ) {
+        for property in TrackProperty::all_for(&to.left) {
+            if property.is_global() {
+                let value = property.global_value(&to.global);
+                if value.is_some() && value != property.global_value(&from.global) {
+                    self.track_mut(property, TrackEye::Both)
+                        .key_pose(time, to, easing);
+                }
+                continue;
+            }
+            let left = property.side_value(&to.left);
+            let right = property.side_value(&to.right);
+            let left_changed = left.is_some() && left != property.side_value(&from.left);
+            let right_changed = right.is_some() && right != property.side_value(&from.right);
+            if left_changed && right_changed && left == right {
+                self.track_mut(property, TrackEye::Both)
+                    .key_pose(time, to, easing);
+                continue;
+            }
+            if left_changed {
+                self.track_mut(property, TrackEye::Left)
+                    .key_pose(time, to, easing);
+            }
+            if right_changed {
+                self.track_mut(property, TrackEye::Right)
+                    .key_pose(time, to, easing);
+            }
+        }
+    }
+
+    /// Sampled values of every keyed track at `t`, with their layer's blend.
+    /// Tracks on channels no layer claims are left out of layered timelines.
+    pub fn sample(&self, t: f32) -> TimelineFrame {
+        TimelineFrame {
+            values: self
+                .tracks
+                .iter()
+                .filter_map(|track| {
+                    let (blend, weight) = if self.is_layered() {
+                        let layer = self.layer_for(track.property.channel())?;
+                        (layer.blend, layer.weight)
+                    } else {
+                        (LayerBlend::Override, 1.0)
+                    };
+                    let mut value = track.sample(t)?;
+                    if blend == LayerBlend::Additive {
+                        value = value.delta(&track.keys[0].value)?;
+                    }
+                    Some(TrackSample {
+                        property: track.property,
+                        eye: track.eye,
+                        value,
+                        blend,
+                        weight,
+                        anchor_count: track.outline_len(),
+                    })
+                })
+                .collect(),
+            fired_events: Vec::new(),
+        }
+    }
+
+    /// `base` with every track applied at `t`.
+    pub fn evaluate_config(&self, t: f32, base: &EyeConfig) -> EyeConfig {
+        let mut pose = TimelinePose::from(base);
+        self.sample(t).apply_to(&mut pose);
+        let mut config = base.clone();
+        pose.apply_to_config(&mut config);
+        config
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Parse a timeline; the legacy keyframe format is migrated on load.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
     }
 }
 
 // ============================================================
-// Timeline output frame
+// Legacy keyframe format (migration only)
 // ============================================================
 
-pub struct TimelineFrame {
+/// Full-snapshot keyframe of the pre-track timeline format
+/// (`{"keyframes": [...]}`), kept so old files can be migrated.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TimelineKeyframe {
+    pub label: String,
+    /// Time in seconds from timeline start when this keyframe is fully reached.
+    pub fire_time: f32,
+    /// Duration of transition FROM the previous keyframe TO this one.
+    pub transition_duration: f32,
+    pub easing: TimelineEasing,
+    #[serde(default)]
+    pub blink: bool,
     pub left: EyeSideConfig,
     pub right: EyeSideConfig,
     pub global: TimelineGlobalConfig,
-    pub trigger_blink: bool,
+}
+
+impl Timeline {
+    /// Convert snapshot keyframes into tracks.
+    ///
+    /// Properties that change between keyframes get a track, and so do those
+    /// holding a value other than the default pose's (as a single key);
+    /// everything else is left to the base config. Each transition becomes a hold key at
+    /// its start plus a target key carrying the keyframe's easing; iris,
+    /// pupil and look properties land in the first quarter of the transition
+    /// with ease-out, as the snapshot player did. Keyframe labels become
+    /// marker events, and blink keyframes a blink event at the transition start.
+    pub fn from_keyframes(keyframes: &[TimelineKeyframe]) -> Self {
+        let mut kfs = keyframes.to_vec();
+        kfs.sort_by(|a, b| a.fire_time.total_cmp(&b.fire_time));
+        for kf in &mut kfs {
+            kf.left.migrate_legacy_highlight();
+            kf.right.migrate_legacy_highlight();
+        }
+        match_anchor_counts(&mut kfs);
+        let mut timeline = Self::new();
+        let Some(first) = kfs.first() else {
+            return timeline;
+        };
+        let defaults = TimelinePose::default();
+
+        for kf in &kfs {
+            if kf.blink {
+                timeline.events.push(TimelineEvent {
+                    time: (kf.fire_time - kf.transition_duration).max(0.0),
+                    action: TimelineAction::blink(),
+                });
+            }
+            timeline.events.push(TimelineEvent {
+                time: kf.fire_time,
+                action: TimelineAction::Marker {
+                    label: kf.label.clone(),
+                },
+            });
+        }
+
+        for property in TrackProperty::all_for(&first.left) {
+            if property.is_global() {
+                let values: Vec<_> = kfs
+                    .iter()
+                    .map(|kf| property.global_value(&kf.global))
+                    .collect();
+                let default = property.global_value(&defaults.global);
+                if let Some(track) =
+                    migrate_track(&kfs, property, TrackEye::Both, &values, default.as_ref())
+                {
+                    timeline.tracks.push(track);
+                }
+                continue;
+            }
+            let default_left = default_side_value(property, &defaults.left, &first.left);
+            let default_right = default_side_value(property, &defaults.right, &first.right);
+            let left: Vec<_> = kfs.iter().map(|kf| property.side_value(&kf.left)).collect();
+            let right: Vec<_> = kfs
+                .iter()
+                .map(|kf| property.side_value(&kf.right))
+                .collect();
+            if left == right && default_left == default_right {
+                timeline.tracks.extend(migrate_track(
+                    &kfs,
+                    property,
+                    TrackEye::Both,
+                    &left,
+                    default_left.as_ref(),
+                ));
+            } else {
+                timeline.tracks.extend(migrate_track(
+                    &kfs,
+                    property,
+                    TrackEye::Left,
+                    &left,
+                    default_left.as_ref(),
+                ));
+                timeline.tracks.extend(migrate_track(
+                    &kfs,
+                    property,
+                    TrackEye::Right,
+                    &right,
+                    default_right.as_ref(),
+                ));
+            }
+        }
+
+        // Anchor indices refer to the matched outline sizes
+        for track in &mut timeline.tracks {
+            track.anchor_count = track.property.outline(&first.left).map(|o| o.anchors.len());
+        }
+        timeline.sort();
+        timeline
+    }
+}
+
+/// Subdivide every keyframe's eye, iris and pupil outlines to the largest
+/// anchor count among them, so anchor `i` means the same point throughout.
+fn match_anchor_counts(kfs: &mut [TimelineKeyframe]) {
+    let mut counts = [0usize; 4];
+    for kf in kfs.iter() {
+        for side in [&kf.left, &kf.right] {
+            let lens = [
+                side.eye_shape.open.anchors.len(),
+                side.eye_shape.closed.anchors.len(),
+                side.iris_shape.anchors.len(),
+                side.pupil_shape.anchors.len(),
+            ];
+            for (c, n) in counts.iter_mut().zip(lens) {
+                *c = (*c).max(n);
+            }
+        }
+    }
+    for kf in kfs.iter_mut() {
+        for side in [&mut kf.left, &mut kf.right] {
+            side.eye_shape.open.upsample(counts[0]);
+            side.eye_shape.closed.upsample(counts[1]);
+            side.iris_shape.upsample(counts[2]);
+            side.pupil_shape.upsample(counts[3]);
+        }
+    }
+}
+
+/// Value of `property` in the default pose's `side`, or `None` where the
+/// keyframes' `keyed` side has no comparable value (another outline size).
+fn default_side_value(
+    property: TrackProperty,
+    side: &EyeSideConfig,
+    keyed: &EyeSideConfig,
+) -> Option<TrackValue> {
+    let len = |s| property.outline(s).map(|o| o.anchors.len());
+    if len(side) != len(keyed) {
+        return None;
+    }
+    property.side_value(side)
+}
+
+/// Track for one property's per-keyframe `values`, or `None` if it holds
+/// `default` throughout.
+fn migrate_track(
+    kfs: &[TimelineKeyframe],
+    property: TrackProperty,
+    eye: TrackEye,
+    values: &[Option<TrackValue>],
+    default: Option<&TrackValue>,
+) -> Option<TimelineTrack> {
+    let first = values.first()?.as_ref()?;
+    if values.iter().all(|v| v.as_ref() == Some(first)) && default == Some(first) {
+        return None;
+    }
+
+    let mut track = TimelineTrack::new(property, eye);
+    track.keys.push(TrackKey {
+        time: kfs[0].fire_time,
+        value: first.clone(),
+        easing: TimelineEasing::Linear,
+    });
+    let mut held = first.clone();
+    for (i, kf) in kfs.iter().enumerate().skip(1) {
+        let Some(value) = values[i].clone() else {
+            continue;
+        };
+        if value == held {
+            continue;
+        }
+        let start = (kf.fire_time - kf.transition_duration).max(kfs[i - 1].fire_time);
+        if track.keys.last().is_some_and(|k| start > k.time) {
+            track.keys.push(TrackKey {
+                time: start,
+                value: held.clone(),
+                easing: TimelineEasing::Linear,
+            });
+        }
+        let (time, easing) = if property.locks_on() {
+            (
+                start + (kf.fire_time - start) * 0.25,
+                TimelineEasing::EaseOut,
+            )
+        } else {
+            (kf.fire_time, kf.easing)
+        };
+        track.keys.push(TrackKey {
+            time,
+            value: value.clone(),
+            easing,
+        });
+        held = value;
+    }
+    Some(track)
+}
+
+// ============================================================
+// Timeline output frame
+// ============================================================
+
+#[derive(Clone, Debug)]
+pub struct TrackSample {
+    pub property: TrackProperty,
+    pub eye: TrackEye,
+    /// Track value, or its change since the first key for additive layers.
+    pub value: TrackValue,
+    pub blend: LayerBlend,
+    pub weight: f32,
+    /// Outline size `value` was keyed on, see [`TimelineTrack::anchor_count`].
+    pub anchor_count: Option<usize>,
+}
+
+impl TrackSample {
+    /// Eyes to blend separately, since both may start from different values.
+    fn sides(&self) -> &'static [TrackEye] {
+        if self.property.is_global() {
+            return &[TrackEye::Both];
+        }
+        match self.eye {
+            TrackEye::Both => &[TrackEye::Left, TrackEye::Right],
+            TrackEye::Left => &[TrackEye::Left],
+            TrackEye::Right => &[TrackEye::Right],
+        }
+    }
+}
+
+pub struct TimelineFrame {
+    /// Value of every keyed track; un-animated properties are absent.
+    pub values: Vec<TrackSample>,
+    /// Indices of events reached since the previous evaluate, in playback order.
+    pub fired_events: Vec<usize>,
+}
+
+impl TimelineFrame {
+    /// Whether a track drives `property` for the given side.
+    pub fn animates(&self, property: TrackProperty, right_eye: bool) -> bool {
+        let side = if right_eye {
+            TrackEye::Right
+        } else {
+            TrackEye::Left
+        };
+        self.values
+            .iter()
+            .any(|s| s.property == property && (s.eye == TrackEye::Both || s.eye == side))
+    }
+
+    /// Blend the animated properties into `pose` in track order.
+    pub fn apply_to(&self, pose: &mut TimelinePose) {
+        for sample in &self.values {
+            if let Some(count) = sample.anchor_count {
+                sample.property.fit_outline(pose, sample.eye, count);
+            }
+            if sample.blend == LayerBlend::Override && sample.weight >= 1.0 {
+                sample.property.set_in(pose, sample.eye, &sample.value);
+                continue;
+            }
+            for &eye in sample.sides() {
+                let Some(under) = sample.property.value_in(pose, eye) else {
+                    continue;
+                };
+                let value = match sample.blend {
+                    LayerBlend::Override => under.lerp(&sample.value, sample.weight),
+                    LayerBlend::Additive => under.add_scaled(&sample.value, sample.weight),
+                };
+                sample.property.set_in(pose, eye, &value);
+            }
+        }
+    }
+
+    /// Reset the animated properties of `pose` to their values in `base`,
+    /// so partial layers blend over a steady value instead of last frame's.
+    pub fn restore(&self, pose: &mut TimelinePose, base: &TimelinePose) {
+        for sample in &self.values {
+            for &eye in sample.sides() {
+                // Resampled outlines go back to the base size as a whole
+                let (from, to) = match eye {
+                    TrackEye::Right => (&base.right, &mut pose.right),
+                    _ => (&base.left, &mut pose.left),
+                };
+                if let (Some(from), Some(to)) = (
+                    sample.property.outline(from),
+                    sample.property.outline_mut(to),
+                ) {
+                    *to = from.clone();
+                } else if let Some(value) = sample.property.value_in(base, eye) {
+                    sample.property.set_in(pose, eye, &value);
+                }
+            }
+        }
+    }
 }
 
 // ============================================================
@@ -279,12 +1732,29 @@ pub struct TimelinePlayer {
     pub timeline: Timeline,
     pub playing: bool,
     pub looping: bool,
+    /// When looping, alternate forward and backward passes instead of wrapping.
+    pub ping_pong: bool,
+    /// Held at `elapsed` while `playing`; the timeline keeps driving the eyes.
+    paused: bool,
+    /// Playback rate multiplier (1.0 = real time).
+    speed: f32,
+    /// +1.0 forward, -1.0 during the backward half of a ping-pong loop.
+    direction: f32,
     elapsed: f32,
     play_start_wall: f32,
     elapsed_at_pause: f32,
-    pub selected_keyframe: Option<usize>,
-    /// Tracks the elapsed time up to which blink keyframes have been checked.
-    blink_cursor: f32,
+    /// Track being edited in the GUI.
+    pub selected_track: Option<usize>,
+    /// Playhead up to which events have been fired.
+    cursor: f32,
+    /// Whether an event exactly at `cursor` is still pending (after start, seek or wrap).
+    cursor_inclusive: bool,
+}
+
+impl Default for TimelinePlayer {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl TimelinePlayer {
@@ -293,29 +1763,100 @@ impl TimelinePlayer {
             timeline: Timeline::new(),
             playing: false,
             looping: false,
+            ping_pong: false,
+            paused: false,
+            speed: 1.0,
+            direction: 1.0,
             elapsed: 0.0,
             play_start_wall: 0.0,
             elapsed_at_pause: 0.0,
-            selected_keyframe: None,
-            blink_cursor: 0.0,
+            selected_track: None,
+            cursor: 0.0,
+            cursor_inclusive: true,
         }
     }
 
+    /// Start playback from the current position, or resume if paused.
+    /// A timeline that played to its end rewinds, so `play` replays it
+    /// from the start with all events firing again.
     pub fn play(&mut self, wall_time: f32) {
         if !self.playing {
             self.play_start_wall = wall_time;
             self.playing = true;
+            self.paused = false;
+        } else if self.paused {
+            self.resume(wall_time);
         }
     }
 
     pub fn stop(&mut self) {
         self.playing = false;
+        self.paused = false;
+        self.direction = 1.0;
         self.elapsed = 0.0;
         self.elapsed_at_pause = 0.0;
-        self.blink_cursor = 0.0;
+        self.set_cursor(0.0);
+    }
+
+    /// Hold the current frame. The timeline keeps driving the eyes until
+    /// [`resume`](Self::resume) or [`stop`](Self::stop).
+    pub fn pause(&mut self) {
+        if self.playing && !self.paused {
+            self.paused = true;
+            self.elapsed_at_pause = self.elapsed;
+        }
+    }
+
+    pub fn resume(&mut self, wall_time: f32) {
+        if self.playing && self.paused {
+            self.paused = false;
+            self.elapsed_at_pause = self.elapsed;
+            self.play_start_wall = wall_time;
+        }
+    }
+
+    /// Jump to `time` seconds into the timeline (clamped to its duration).
+    /// Playback continues from there if playing, or starts there on the next `play`.
+    /// Events at or after `time` fire as the playhead reaches them.
+    pub fn seek(&mut self, time: f32, wall_time: f32) {
+        let t = time.clamp(0.0, self.timeline.total_duration());
+        self.elapsed = t;
+        self.elapsed_at_pause = t;
+        self.play_start_wall = wall_time;
+        self.set_cursor(t);
+    }
+
+    /// Show the frame at `time` without playing (pauses if needed).
+    pub fn scrub(&mut self, time: f32, wall_time: f32) {
+        self.seek(time, wall_time);
+        self.playing = true;
+        self.paused = true;
+    }
+
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    /// Change the playback rate without jumping.
+    pub fn set_speed(&mut self, speed: f32, wall_time: f32) {
+        if self.playing && !self.paused {
+            self.elapsed_at_pause = self.position_at(wall_time);
+            self.play_start_wall = wall_time;
+        }
+        self.speed = speed.max(0.0);
     }
 
+    /// Whether the playhead is advancing.
     pub fn is_playing(&self) -> bool {
+        self.playing && !self.paused
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.playing && self.paused
+    }
+
+    /// Whether the timeline drives the eyes (playing or paused).
+    pub fn is_active(&self) -> bool {
         self.playing
     }
 
@@ -323,125 +1864,331 @@ impl TimelinePlayer {
         self.elapsed
     }
 
+    /// Interpolated frame at `time` seconds, independent of playback state.
+    pub fn sample(&self, time: f32) -> TimelineFrame {
+        self.timeline
+            .sample(time.clamp(0.0, self.timeline.total_duration()))
+    }
+
+    fn set_cursor(&mut self, t: f32) {
+        self.cursor = t;
+        self.cursor_inclusive = true;
+    }
+
+    /// Unwrapped playhead at `wall_time` for the current pass.
+    fn position_at(&self, wall_time: f32) -> f32 {
+        self.elapsed_at_pause + self.direction * (wall_time - self.play_start_wall) * self.speed
+    }
+
     pub fn evaluate(&mut self, wall_time: f32) -> Option<TimelineFrame> {
-        if !self.playing || self.timeline.keyframes.is_empty() {
+        if !self.playing || self.timeline.is_empty() {
             return None;
         }
 
-        let prev_cursor = self.blink_cursor;
-        self.elapsed = self.elapsed_at_pause + (wall_time - self.play_start_wall);
+        if self.paused {
+            return Some(self.timeline.sample(self.elapsed));
+        }
 
         let total = self.timeline.total_duration();
         if total <= 0.0 {
-            let kf = &self.timeline.keyframes[0];
-            return Some(TimelineFrame {
-                left: kf.left.clone(),
-                right: kf.right.clone(),
-                global: kf.global.clone(),
-                trigger_blink: false,
-            });
+            let mut frame = self.timeline.sample(0.0);
+            frame.fired_events = self.crossed_events(0.0);
+            self.cursor = 0.0;
+            self.cursor_inclusive = false;
+            return Some(frame);
         }
 
-        if self.elapsed > total {
+        let mut position = self.position_at(wall_time);
+        let mut fired_events = Vec::new();
+
+        if !(0.0..=total).contains(&position) {
+            // Report the tail of the pass that just ended
+            let boundary = if self.direction > 0.0 { total } else { 0.0 };
+            fired_events = self.crossed_events(boundary);
+
             if self.looping {
-                self.elapsed %= total;
+                let overshoot = (position - boundary).abs() % total;
+                if self.ping_pong {
+                    self.direction = -self.direction;
+                    position = boundary + self.direction * overshoot;
+                    // Events at the boundary were just fired
+                    self.cursor = boundary;
+                    self.cursor_inclusive = false;
+                } else {
+                    position = overshoot;
+                    self.set_cursor(0.0);
+                }
                 self.play_start_wall = wall_time;
-                self.elapsed_at_pause = self.elapsed;
-                self.blink_cursor = 0.0;
+                self.elapsed_at_pause = position;
             } else {
+                // Rewind so the next `play` replays from the start
                 self.playing = false;
-                let kf = self.timeline.keyframes.last().unwrap();
-                return Some(TimelineFrame {
-                    left: kf.left.clone(),
-                    right: kf.right.clone(),
-                    global: kf.global.clone(),
-                    trigger_blink: false,
-                });
+                self.direction = 1.0;
+                self.elapsed = 0.0;
+                self.elapsed_at_pause = 0.0;
+                self.set_cursor(0.0);
+                let mut frame = self.timeline.sample(boundary);
+                frame.fired_events = fired_events;
+                return Some(frame);
             }
         }
 
-        // Check if any blink keyframe's transition_start was crossed this frame
-        let mut trigger_blink = false;
-        for kf in &self.timeline.keyframes {
-            if kf.blink {
-                let trigger_time = (kf.fire_time - kf.transition_duration).max(0.0);
-                if trigger_time > prev_cursor && trigger_time <= self.elapsed {
-                    trigger_blink = true;
-                }
-            }
-        }
-        self.blink_cursor = self.elapsed;
+        self.elapsed = position;
+        fired_events.extend(self.crossed_events(position));
+        self.cursor = position;
+        self.cursor_inclusive = false;
 
-        let mut frame = self.interpolate_at(self.elapsed)?;
-        frame.trigger_blink = trigger_blink;
+        let mut frame = self.timeline.sample(position);
+        frame.fired_events = fired_events;
         Some(frame)
     }
 
-    fn interpolate_at(&self, t: f32) -> Option<TimelineFrame> {
-        let kfs = &self.timeline.keyframes;
-        if kfs.is_empty() {
-            return None;
+    /// Whether `t` lies between the cursor and `to` in the playback direction.
+    fn crossed(&self, t: f32, to: f32) -> bool {
+        let from = self.cursor;
+        let past_cursor = if self.direction > 0.0 {
+            t > from
+        } else {
+            t < from
+        };
+        let reached = if self.direction > 0.0 {
+            t <= to
+        } else {
+            t >= to
+        };
+        (past_cursor || (self.cursor_inclusive && t == from)) && reached
+    }
+
+    /// Events whose time lies between the cursor and `to`, in playback order.
+    fn crossed_events(&self, to: f32) -> Vec<usize> {
+        let mut fired: Vec<usize> = self
+            .timeline
+            .events
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| self.crossed(e.time, to))
+            .map(|(i, _)| i)
+            .collect();
+        if self.direction < 0.0 {
+            fired.reverse();
         }
+        fired
+    }
+}
 
-        // Before or at first keyframe: hold first keyframe
-        if t <= kfs[0].fire_time {
-            return Some(TimelineFrame {
-                left: kfs[0].left.clone(),
-                right: kfs[0].right.clone(),
-                global: kfs[0].global.clone(),
-                trigger_blink: false,
-            });
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Player over markers at 0, 1 and 2 seconds.
+    fn marker_player() -> TimelinePlayer {
+        let mut player = TimelinePlayer::new();
+        player.timeline.events = [0.0, 1.0, 2.0]
+            .into_iter()
+            .map(|time| TimelineEvent {
+                time,
+                action: TimelineAction::Marker {
+                    label: format!("{time}"),
+                },
+            })
+            .collect();
+        player
+    }
+
+    fn fired(player: &mut TimelinePlayer, wall_time: f32) -> Vec<usize> {
+        player.evaluate(wall_time).expect("playing").fired_events
+    }
+
+    #[test]
+    fn events_fire_once_forward() {
+        let mut player = marker_player();
+        player.play(0.0);
+        assert_eq!(fired(&mut player, 0.0), [0]);
+        assert!(fired(&mut player, 0.0).is_empty());
+        assert!(fired(&mut player, 0.5).is_empty());
+        assert_eq!(fired(&mut player, 1.0), [1]);
+        assert!(fired(&mut player, 1.5).is_empty());
+        // Frames skipping past an event still report it
+        let mut player = marker_player();
+        player.play(0.0);
+        assert_eq!(fired(&mut player, 1.5), [0, 1]);
+    }
+
+    #[test]
+    fn finishing_fires_the_tail_and_rewinds() {
+        let mut player = marker_player();
+        player.play(0.0);
+        assert_eq!(fired(&mut player, 1.5), [0, 1]);
+        assert_eq!(fired(&mut player, 2.5), [2]);
+        assert!(!player.is_active());
+        assert_eq!(player.current_time(), 0.0);
+        assert!(player.evaluate(3.0).is_none());
+
+        // Replays from the start with every event firing again
+        player.play(10.0);
+        assert_eq!(fired(&mut player, 10.0), [0]);
+        assert_eq!(fired(&mut player, 12.5), [1, 2]);
+    }
+
+    #[test]
+    fn events_fire_once_per_loop() {
+        let mut player = marker_player();
+        player.looping = true;
+        player.play(0.0);
+        assert_eq!(fired(&mut player, 0.0), [0]);
+        assert_eq!(fired(&mut player, 2.5), [1, 2, 0]);
+        assert_eq!(player.current_time(), 0.5);
+        assert_eq!(fired(&mut player, 4.0), [1, 2]);
+        assert!(fired(&mut player, 4.0).is_empty());
+    }
+
+    #[test]
+    fn events_fire_once_per_ping_pong_pass() {
+        let mut player = marker_player();
+        player.looping = true;
+        player.ping_pong = true;
+        player.play(0.0);
+        assert_eq!(fired(&mut player, 0.0), [0]);
+        // Turning at the end fires its event once, then runs backward
+        assert_eq!(fired(&mut player, 2.5), [1, 2]);
+        assert_eq!(player.current_time(), 1.5);
+        assert_eq!(fired(&mut player, 3.0), [1]);
+        assert_eq!(fired(&mut player, 4.5), [0]);
+        assert_eq!(player.current_time(), 0.5);
+        assert_eq!(fired(&mut player, 6.0), [1, 2]);
+        assert!(fired(&mut player, 6.5).is_empty());
+        assert_eq!(player.current_time(), 1.5);
+    }
+
+    #[test]
+    fn seek_refires_events_from_the_new_position() {
+        let mut player = marker_player();
+        player.play(0.0);
+        assert_eq!(fired(&mut player, 1.5), [0, 1]);
+
+        // Back before an event fires it again
+        player.seek(0.5, 1.5);
+        assert_eq!(fired(&mut player, 2.0), [1]);
+
+        // Onto an event fires it, once
+        player.seek(1.0, 2.0);
+        assert_eq!(fired(&mut player, 2.0), [1]);
+        assert!(fired(&mut player, 2.0).is_empty());
+
+        // Past an event skips it
+        player.seek(1.5, 2.0);
+        assert!(fired(&mut player, 2.25).is_empty());
+        assert_eq!(fired(&mut player, 2.5), [2]);
+
+        // Seeking while stopped sets where the next play starts
+        player.seek(1.0, 5.0);
+        player.play(5.0);
+        assert_eq!(fired(&mut player, 5.0), [1]);
+    }
+
+    #[test]
+    fn paused_and_scrubbed_frames_fire_nothing() {
+        let mut player = marker_player();
+        player.play(0.0);
+        assert_eq!(fired(&mut player, 0.5), [0]);
+        player.pause();
+        assert!(fired(&mut player, 5.0).is_empty());
+        player.scrub(1.0, 5.0);
+        assert!(fired(&mut player, 6.0).is_empty());
+        assert_eq!(player.current_time(), 1.0);
+        // Resuming plays on from the scrubbed position, event included
+        player.resume(6.0);
+        assert_eq!(fired(&mut player, 6.5), [1]);
+    }
+
+    fn keyframe(
+        fire_time: f32,
+        transition_duration: f32,
+        side: &EyeSideConfig,
+    ) -> TimelineKeyframe {
+        TimelineKeyframe {
+            label: format!("{fire_time}"),
+            fire_time,
+            transition_duration,
+            easing: TimelineEasing::Linear,
+            blink: false,
+            left: side.clone(),
+            right: side.clone(),
+            global: TimelinePose::default().global,
         }
+    }
 
-        // Find the segment: kfs[i-1] ... kfs[i]
-        for i in 1..kfs.len() {
-            if t <= kfs[i].fire_time {
-                let prev = &kfs[i - 1];
-                let curr = &kfs[i];
+    /// Largest component difference between two values of the same kind.
+    fn value_error(a: &TrackValue, b: &TrackValue) -> f32 {
+        let max_abs = |v: &[f32]| v.iter().fold(0.0f32, |m, d| m.max(d.abs()));
+        match a.delta(b).expect("same kind") {
+            TrackValue::Scalar(d) => d.abs(),
+            TrackValue::Vec2(d) => max_abs(&d),
+            TrackValue::Vec3(d) => max_abs(&d),
+            TrackValue::Anchor(d) => max_abs(&[d.position, d.handle_in, d.handle_out].concat()),
+        }
+    }
 
-                let transition_start =
-                    (curr.fire_time - curr.transition_duration).max(prev.fire_time);
+    #[test]
+    fn legacy_keyframes_migrate_to_the_same_poses() {
+        let defaults = TimelinePose::default();
+        let mut side = defaults.left.clone();
+        side.iris_color = [0.1, 0.5, 0.2];
+        side.eyelid_close = 0.0;
+        let start = keyframe(0.0, 0.0, &side);
+        side.eyelid_close = 1.0;
+        let end = keyframe(1.0, 1.0, &side);
+        let timeline = Timeline::from_keyframes(&[start.clone(), end.clone()]);
 
-                if t < transition_start {
-                    // Hold at previous keyframe (gap before transition)
-                    return Some(TimelineFrame {
-                        left: prev.left.clone(),
-                        right: prev.right.clone(),
-                        global: prev.global.clone(),
-                        trigger_blink: false,
-                    });
-                }
+        // Constant but not the default: held by a single key
+        let iris = timeline
+            .tracks
+            .iter()
+            .find(|t| t.property == TrackProperty::IrisColor)
+            .expect("iris color track");
+        assert_eq!(iris.keys.len(), 1);
+        // Constant at the default: left to the base
+        assert!(!timeline
+            .tracks
+            .iter()
+            .any(|t| t.property == TrackProperty::PupilColor));
 
-                // Within transition
-                let transition_elapsed = t - transition_start;
-                let transition_total = curr.fire_time - transition_start;
-                let raw_t = if transition_total > 0.0 {
-                    (transition_elapsed / transition_total).clamp(0.0, 1.0)
+        // The snapshot player showed each keyframe in full at its fire time
+        for kf in [&start, &end] {
+            let mut pose = defaults.clone();
+            timeline.sample(kf.fire_time).apply_to(&mut pose);
+            for property in TrackProperty::all_for(&kf.left) {
+                let pairs = if property.is_global() {
+                    vec![(
+                        property.global_value(&pose.global),
+                        property.global_value(&kf.global),
+                    )]
                 } else {
-                    1.0
+                    vec![
+                        (
+                            property.side_value(&pose.left),
+                            property.side_value(&kf.left),
+                        ),
+                        (
+                            property.side_value(&pose.right),
+                            property.side_value(&kf.right),
+                        ),
+                    ]
                 };
-
-                let eased_t = apply_easing(raw_t, curr.easing.to_easing());
-                // Iris/pupil complete in 1/3 of the transition time, then hold at target
-                let fast_t = (raw_t * 4.0).clamp(0.0, 1.0);
-                let precise_t = apply_easing(fast_t, Easing::EaseOut);
-
-                return Some(TimelineFrame {
-                    left: lerp_eye_side(&prev.left, &curr.left, eased_t, precise_t),
-                    right: lerp_eye_side(&prev.right, &curr.right, eased_t, precise_t),
-                    global: lerp_timeline_global(&prev.global, &curr.global, eased_t),
-                    trigger_blink: false,
-                });
+                for (got, expected) in pairs {
+                    let (Some(got), Some(expected)) = (got, expected) else {
+                        continue;
+                    };
+                    let error = value_error(&got, &expected);
+                    assert!(error < 1e-5, "{property:?} at {}: {error}", kf.fire_time);
+                }
             }
         }
 
-        // Past last keyframe: hold at last
-        let last = kfs.last().unwrap();
-        Some(TimelineFrame {
-            left: last.left.clone(),
-            right: last.right.clone(),
-            global: last.global.clone(),
-            trigger_blink: false,
-        })
+        // and eased linearly into it over the transition
+        let mut pose = defaults.clone();
+        timeline.sample(0.5).apply_to(&mut pose);
+        assert!((pose.left.eyelid_close - 0.5).abs() < 1e-5);
+        assert_eq!(pose.right.iris_color, [0.1, 0.5, 0.2]);
     }
 }
@@ -1,112 +1,18 @@
-use std::net::TcpListener;
 use std::sync::{Arc, Mutex};
 use std::time::Instant;
 
-use eye::gui::{eye_control_panel, EyeSideState, GuiActions, SectionLink};
-use eye::{BlinkAnimation, EyeConfig, EyePairUniforms, EyeRenderer, ListeningNod, MicrosaccadeAnimation, NodAnimation, Timeline, TimelinePlayer};
+use eye::controller::pointer_to_look;
+use eye::gui::{eye_control_panel, GuiActions, SectionLink};
+use eye::{
+    EyeConfig, EyeController, EyeInputs, EyeRenderer, RemoteServer, RemoteServerConfig, Timeline, TimelineEasing,
+    TimelinePose, WinkEye,
+};
 use winit::application::ApplicationHandler;
 use winit::event::{ElementState, KeyEvent, WindowEvent};
-use winit::event_loop::{ActiveEventLoop, EventLoop};
+use winit::event_loop::{ActiveEventLoop, EventLoop, EventLoopProxy};
 use winit::keyboard::{Key, NamedKey};
 use winit::window::{Window, WindowId};
 
-// ============================================================
-// WebSocket gaze state
-// ============================================================
-
-/// Gaze parameters received from an external WebSocket client.
-#[derive(Clone, Debug, serde::Deserialize)]
-struct WsGazeMessage {
-    look_x: Option<f32>,
-    look_y: Option<f32>,
-    focus_distance: Option<f32>,
-    eyelid_close: Option<f32>,
-}
-
-struct WsGazeState {
-    look_x: f32,
-    look_y: f32,
-    focus_distance: f32,
-    eyelid_close: Option<f32>,
-    active: bool,
-}
-
-impl Default for WsGazeState {
-    fn default() -> Self {
-        Self {
-            look_x: 0.0,
-            look_y: 0.0,
-            focus_distance: 1.5,
-            eyelid_close: None,
-            active: false,
-        }
-    }
-}
-
-fn start_ws_server(shared: Arc<Mutex<WsGazeState>>) {
-    std::thread::Builder::new()
-        .name("ws-server".into())
-        .spawn(move || {
-            let listener = match TcpListener::bind("127.0.0.1:8765") {
-                Ok(l) => l,
-                Err(e) => {
-                    eprintln!("WebSocket server failed to bind: {e}");
-                    return;
-                }
-            };
-            log::info!("WebSocket server listening on ws://127.0.0.1:8765");
-
-            for stream in listener.incoming().flatten() {
-                let shared = shared.clone();
-                std::thread::spawn(move || {
-                    let mut ws = match tungstenite::accept(stream) {
-                        Ok(ws) => ws,
-                        Err(e) => {
-                            eprintln!("WebSocket accept error: {e}");
-                            return;
-                        }
-                    };
-                    log::info!("WebSocket client connected");
-                    if let Ok(mut state) = shared.lock() {
-                        state.active = true;
-                    }
-
-                    loop {
-                        match ws.read() {
-                            Ok(msg) if msg.is_text() => {
-                                let text = msg.into_text().unwrap_or_default();
-                                if let Ok(gaze) = serde_json::from_str::<WsGazeMessage>(&text) {
-                                    if let Ok(mut state) = shared.lock() {
-                                        if let Some(v) = gaze.look_x {
-                                            state.look_x = v.clamp(-1.0, 1.0);
-                                        }
-                                        if let Some(v) = gaze.look_y {
-                                            state.look_y = v.clamp(-1.0, 1.0);
-                                        }
-                                        if let Some(v) = gaze.focus_distance {
-                                            state.focus_distance = v.clamp(0.5, 20.0);
-                                        }
-                                        state.eyelid_close =
-                                            gaze.eyelid_close.map(|v| v.clamp(0.0, 1.0));
-                                    }
-                                }
-                            }
-                            Ok(msg) if msg.is_close() => break,
-                            Err(_) => break,
-                            _ => {}
-                        }
-                    }
-
-                    log::info!("WebSocket client disconnected");
-                    if let Ok(mut state) = shared.lock() {
-                        state.active = false;
-                    }
-                });
-            }
-        })
-        .expect("Failed to spawn WebSocket server thread");
-}
-
 // ============================================================
 // Audio capture state
 // ============================================================
@@ -204,6 +110,7 @@ fn start_audio_capture(shared: Arc<Mutex<AudioState>>) {
 struct App {
     state: Option<AppState>,
     config_path: Option<String>,
+    proxy: EventLoopProxy<()>,
 }
 
 struct AppState {
@@ -214,9 +121,8 @@ struct AppState {
     surface_config: wgpu::SurfaceConfiguration,
     renderer: EyeRenderer,
 
-    // Per-eye state
-    left: EyeSideState,
-    right: EyeSideState,
+    // Per-eye state + animations
+    controller: EyeController,
 
     // Section link state
     link_shape: SectionLink,
@@ -225,30 +131,15 @@ struct AppState {
     link_eyelash: SectionLink,
     link_highlight: SectionLink,
 
-    blink_animation: BlinkAnimation,
-    nod_animation: NodAnimation,
-    microsaccade_animation: MicrosaccadeAnimation,
-    auto_blink: bool,
-    follow_mouse: bool,
-    show_highlight: bool,
-    show_eyebrow: bool,
-    show_eyelash: bool,
     show_sidebar: bool,
-    pause_animation: bool,
-    focus_distance: f32,
     mouse_position: Option<winit::dpi::PhysicalPosition<f64>>,
     start_time: Instant,
 
     // WebSocket
-    ws_gaze: Arc<Mutex<WsGazeState>>,
+    remote: Option<RemoteServer>,
 
-    // Audio / Listening
+    // Audio
     audio_state: Arc<Mutex<AudioState>>,
-    listening_nod: ListeningNod,
-
-    // Timeline
-    timeline_player: TimelinePlayer,
-    prev_timeline_eyelid: f32,
 
     // egui
     egui_ctx: egui::Context,
@@ -337,31 +228,17 @@ impl ApplicationHandler for App {
                 surface,
                 surface_config,
                 renderer,
-                left: EyeSideState::default(),
-                right: EyeSideState::default(),
+                controller: EyeController::new(),
                 link_shape: SectionLink::default(),
                 link_iris: SectionLink::default(),
                 link_eyebrow: SectionLink::default(),
                 link_eyelash: SectionLink::default(),
                 link_highlight: SectionLink::default(),
-                blink_animation: BlinkAnimation::sample(),
-                nod_animation: NodAnimation::default(),
-                microsaccade_animation: MicrosaccadeAnimation::new(7),
-                auto_blink: true,
-                follow_mouse: true,
-                show_highlight: true,
-                show_eyebrow: true,
-                show_eyelash: true,
                 show_sidebar: true,
-                pause_animation: false,
-                focus_distance: 1.5,
                 mouse_position: None,
                 start_time: Instant::now(),
-                ws_gaze: Arc::new(Mutex::new(WsGazeState::default())),
+                remote: None,
                 audio_state: Arc::new(Mutex::new(AudioState::default())),
-                listening_nod: ListeningNod::default(),
-                timeline_player: TimelinePlayer::new(),
-                prev_timeline_eyelid: 0.0,
                 egui_ctx,
                 egui_state,
                 egui_renderer,
@@ -369,7 +246,20 @@ impl ApplicationHandler for App {
         });
 
         // Start WebSocket server
-        start_ws_server(state.ws_gaze.clone());
+        let remote_config = RemoteServerConfig {
+            addr: std::env::var("EYE_REMOTE_ADDR").unwrap_or_else(|_| "127.0.0.1:8765".into()),
+            ..Default::default()
+        };
+        let proxy = self.proxy.clone();
+        match RemoteServer::start(remote_config, move || {
+            let _ = proxy.send_event(());
+        }) {
+            Ok(server) => {
+                log::info!("WebSocket server listening on ws://{}", server.local_addr());
+                state.remote = Some(server);
+            }
+            Err(e) => eprintln!("WebSocket server failed to start: {e}"),
+        }
 
         // Start audio capture
         start_audio_capture(state.audio_state.clone());
@@ -379,21 +269,29 @@ impl ApplicationHandler for App {
             match std::fs::read_to_string(path) {
                 Ok(json) => match EyeConfig::from_json(&json) {
                     Ok(config) => {
+                        let c = &mut state.controller;
                         config.apply_to_state(
-                            &mut state.left,
-                            &mut state.right,
+                            &mut c.left,
+                            &mut c.right,
                             &mut state.link_shape,
                             &mut state.link_iris,
                             &mut state.link_eyebrow,
                             &mut state.link_eyelash,
                             &mut state.link_highlight,
-                            &mut state.auto_blink,
-                            &mut state.follow_mouse,
-                            &mut state.show_highlight,
-                            &mut state.show_eyebrow,
-                            &mut state.show_eyelash,
-                            &mut state.focus_distance,
-                            &mut state.nod_animation,
+                            &mut c.auto_blink,
+                            &mut c.follow_mouse,
+                            &mut c.show_highlight,
+                            &mut c.show_eyebrow,
+                            &mut c.show_eyelash,
+                            &mut c.show_iris,
+                            &mut c.show_pupil,
+                            &mut c.focus_distance,
+                            &mut c.nod_animation,
+                            &mut c.auto_microsaccade,
+                            &mut c.microsaccade_animation,
+                            &mut c.blink_animation,
+                            &mut c.listening_nod,
+                            &mut c.pupil_dynamics,
                         );
                     }
                     Err(e) => eprintln!("Invalid config JSON: {e}"),
@@ -405,6 +303,19 @@ impl ApplicationHandler for App {
         self.state = Some(state);
     }
 
+    fn user_event(&mut self, _event_loop: &ActiveEventLoop, _event: ()) {
+        let Some(state) = &mut self.state else {
+            return;
+        };
+
+        // Apply queued WebSocket commands and answer them
+        let time = state.start_time.elapsed().as_secs_f32();
+        if let Some(remote) = &mut state.remote {
+            remote.process(&mut state.controller, time);
+        }
+        state.window.request_redraw();
+    }
+
     fn window_event(
         &mut self,
         event_loop: &ActiveEventLoop,
@@ -452,30 +363,34 @@ impl ApplicationHandler for App {
             match logical_key {
                 Key::Character(c) if c.as_str() == "b" => {
                     let time = state.start_time.elapsed().as_secs_f32();
-                    state.blink_animation.trigger(time);
+                    state.controller.trigger_blink(time);
+                    state.window.request_redraw();
+                    return;
+                }
+                Key::Character(c) if c.as_str() == "[" || c.as_str() == "]" => {
+                    let time = state.start_time.elapsed().as_secs_f32();
+                    let eye = if c.as_str() == "[" { WinkEye::Left } else { WinkEye::Right };
+                    state.controller.trigger_wink(time, eye);
                     state.window.request_redraw();
                     return;
                 }
                 Key::Character(c) if c.as_str() == "n" => {
                     let time = state.start_time.elapsed().as_secs_f32();
-                    let current_eyelid = state.left.uniforms.eyelid_close;
-                    state.nod_animation.trigger(time, current_eyelid);
+                    state.controller.trigger_nod(time);
                     state.window.request_redraw();
                     return;
                 }
                 Key::Character(c) if c.as_str() == "s" => {
                     let time = state.start_time.elapsed().as_secs_f32();
-                    let look_x = state.left.uniforms.look_x;
-                    let look_y = state.left.uniforms.look_y;
-                    state.microsaccade_animation.trigger(time, look_x, look_y);
+                    state.controller.trigger_microsaccade(time);
                     state.window.request_redraw();
                     return;
                 }
                 Key::Character(c) if c.as_str() == "l" => {
-                    state.listening_nod.toggle();
+                    state.controller.listening_nod.toggle();
                     log::info!(
                         "Listening nod: {}",
-                        if state.listening_nod.enabled { "ON" } else { "OFF" }
+                        if state.controller.listening_nod.enabled { "ON" } else { "OFF" }
                     );
                     state.window.request_redraw();
                     return;
@@ -509,7 +424,7 @@ impl ApplicationHandler for App {
             }
             WindowEvent::CursorMoved { position, .. } => {
                 state.mouse_position = Some(position);
-                if state.follow_mouse {
+                if state.controller.follow_mouse {
                     state.window.request_redraw();
                 }
             }
@@ -528,247 +443,39 @@ impl ApplicationHandler for App {
                     }
                 };
 
-                // Update dynamic uniforms (global: sync to both eyes)
+                // Gather host inputs and run the animation pipeline
                 let aspect =
                     state.surface_config.width as f32 / state.surface_config.height as f32;
                 let time = state.start_time.elapsed().as_secs_f32();
-                state.left.uniforms.aspect_ratio = aspect;
-                state.left.uniforms.time = time;
-                state.right.uniforms.aspect_ratio = aspect;
-                state.right.uniforms.time = time;
-
-                // --- Timeline playback (takes priority over all other animations) ---
-                let timeline_active = state.timeline_player.is_playing();
-                if let Some(frame) = state.timeline_player.evaluate(time) {
-                    // Trigger blink if a blink keyframe was crossed
-                    if frame.trigger_blink {
-                        state.blink_animation.trigger(time);
-                    }
-
-                    frame.left.apply_to(&mut state.left);
-                    frame.right.apply_to(&mut state.right);
-                    // Apply global params
-                    state.left.uniforms.bg_color = frame.global.bg_color;
-                    state.left.uniforms.eye_separation = frame.global.eye_separation;
-                    state.left.uniforms.max_angle = frame.global.max_angle;
-                    state.left.uniforms.eye_angle = frame.global.eye_angle;
-                    state.right.uniforms.bg_color = frame.global.bg_color;
-                    state.right.uniforms.eye_separation = frame.global.eye_separation;
-                    state.right.uniforms.max_angle = frame.global.max_angle;
-                    state.right.uniforms.eye_angle = frame.global.eye_angle;
-                    state.focus_distance = frame.global.focus_distance;
-                    // Restore runtime-only fields
-                    state.left.uniforms.aspect_ratio = aspect;
-                    state.left.uniforms.time = time;
-                    state.right.uniforms.aspect_ratio = aspect;
-                    state.right.uniforms.time = time;
-
-                    // Overlay blink on top of timeline eyelid_close
-                    if state.blink_animation.is_blinking(time) {
-                        let blink_value = state.blink_animation.peek_value(time);
-                        let eyelid = f32::max(state.left.uniforms.eyelid_close, blink_value);
-                        state.left.uniforms.eyelid_close = eyelid;
-                        state.right.uniforms.eyelid_close = eyelid;
-                    }
-                }
-
-                // When timeline is active, skip all other animations
-                let ws_active;
-                if timeline_active {
-                    // Squash & stretch driven by eyelid velocity during timeline playback
-                    let eyelid_now = state.left.uniforms.eyelid_close;
-                    let dt = 1.0 / 60.0_f32;
-                    let velocity = (eyelid_now - state.prev_timeline_eyelid) / dt;
-                    const SQUASH_STRENGTH: f32 = 0.08;
-                    const MAX_SQUASH: f32 = 0.045;
-                    let squash = (velocity * SQUASH_STRENGTH).clamp(-MAX_SQUASH, MAX_SQUASH);
-                    state.left.uniforms.squash_stretch = squash;
-                    state.right.uniforms.squash_stretch = squash;
-                    state.prev_timeline_eyelid = eyelid_now;
-                    ws_active = false;
-                } else {
-
-                // Auto-blink: applies to both eyes (skip when paused)
-                if state.auto_blink && !state.pause_animation {
-                    let eyelid_now = state.blink_animation.evaluate(time);
-
-                    // Squash & stretch driven by eyelid velocity
-                    let dt = 1.0 / 60.0_f32;
-                    let eyelid_prev = state.blink_animation.peek_value(time - dt);
-                    let velocity = (eyelid_now - eyelid_prev) / dt;
-                    const SQUASH_STRENGTH: f32 = 0.08;
-                    const MAX_SQUASH: f32 = 0.045;
-                    let squash =
-                        (velocity * SQUASH_STRENGTH).clamp(-MAX_SQUASH, MAX_SQUASH);
-
-                    state.left.uniforms.squash_stretch = squash;
-                    state.right.uniforms.squash_stretch = squash;
-                    state.left.uniforms.eyelid_close = eyelid_now;
-                    state.right.uniforms.eyelid_close = eyelid_now;
-                } else {
-                    state.left.uniforms.squash_stretch = 0.0;
-                    state.right.uniforms.squash_stretch = 0.0;
-                }
-
-                // Gaze input: WebSocket takes priority over mouse follow
-                ws_active = state
-                    .ws_gaze
-                    .lock()
-                    .map(|ws| {
-                        if ws.active {
-                            state.left.uniforms.look_x = ws.look_x;
-                            state.left.uniforms.look_y = ws.look_y;
-                            state.right.uniforms.look_x = ws.look_x;
-                            state.right.uniforms.look_y = ws.look_y;
-                            // Sync head orientation with gaze
-                            state.left.uniforms.head_yaw = ws.look_x;
-                            state.left.uniforms.head_pitch = ws.look_y;
-                            state.right.uniforms.head_yaw = ws.look_x;
-                            state.right.uniforms.head_pitch = ws.look_y;
-                            state.focus_distance = ws.focus_distance;
-                            if let Some(ec) = ws.eyelid_close {
-                                state.left.uniforms.eyelid_close = ec;
-                                state.right.uniforms.eyelid_close = ec;
-                            }
-                            true
-                        } else {
-                            false
-                        }
-                    })
-                    .unwrap_or(false);
-
-                if !ws_active && state.follow_mouse {
-                    if let Some(pos) = state.mouse_position {
-                        let cx = state.surface_config.width as f64 / 2.0;
-                        let cy = state.surface_config.height as f64 / 2.0;
-                        let look_x =
-                            ((pos.x - cx) / cx).clamp(-1.0, 1.0) as f32;
-                        let look_y =
-                            -((pos.y - cy) / cy).clamp(-1.0, 1.0) as f32;
-                        state.left.uniforms.look_x = look_x;
-                        state.left.uniforms.look_y = look_y;
-                        state.right.uniforms.look_x = look_x;
-                        state.right.uniforms.look_y = look_y;
-                        // Sync head orientation with gaze
-                        state.left.uniforms.head_yaw = look_x;
-                        state.left.uniforms.head_pitch = look_y;
-                        state.right.uniforms.head_yaw = look_x;
-                        state.right.uniforms.head_pitch = look_y;
-                    }
-                }
-
-                // Microsaccade: iris-only offset (both eyes same direction)
-                if !state.pause_animation {
-                    let (ms_x, ms_y) = state.microsaccade_animation.evaluate(time);
-                    state.left.uniforms.microsaccade_x = ms_x;
-                    state.right.uniforms.microsaccade_x = ms_x;
-                    state.left.uniforms.microsaccade_y = ms_y;
-                    state.right.uniforms.microsaccade_y = ms_y;
-                }
-
-                // Focus distance → convergence offset (global)
-                // Physical eye separation on screen scales with window size.
-                // Model: each eye rotates inward to converge at the viewer.
                 let scale_factor = state.window.scale_factor() as f32;
-                let logical_height =
-                    state.surface_config.height as f32 / scale_factor;
-                let half_ipd_lp = state.left.uniforms.eye_separation
-                    * logical_height
-                    * 0.25;
-                let viewer_dist_lp = state.focus_distance * 800.0;
-                let conv_angle = (half_ipd_lp / viewer_dist_lp).atan();
-                let iris_follow = state.left.uniforms.iris_follow;
-                let max_angle = state.left.uniforms.max_angle;
-                let convergence = if max_angle > 0.001 {
-                    (conv_angle * iris_follow / max_angle)
-                        .clamp(0.0, iris_follow * 0.8)
-                } else {
-                    0.0
+                let mouse_look = state.mouse_position.map(|pos| {
+                    pointer_to_look(
+                        pos.x,
+                        pos.y,
+                        state.surface_config.width as f64,
+                        state.surface_config.height as f64,
+                    )
+                });
+                let audio_rms = state
+                    .audio_state
+                    .lock()
+                    .map(|a| a.rms)
+                    .unwrap_or(0.0);
+                let inputs = EyeInputs {
+                    aspect_ratio: aspect,
+                    logical_height: state.surface_config.height as f32 / scale_factor,
+                    mouse_look,
+                    remote_gaze: None,
+                    audio_rms,
                 };
-                state.left.uniforms.convergence = convergence;
-                state.right.uniforms.convergence = convergence;
-
-                if !state.pause_animation {
-                    // Listening nod: trigger nod on detected speech pauses
-                    if state.listening_nod.enabled {
-                        let rms = state
-                            .audio_state
-                            .lock()
-                            .map(|a| a.rms)
-                            .unwrap_or(0.0);
-                        if state.listening_nod.update(time, rms)
-                            && !state.nod_animation.is_active()
-                        {
-                            let current_eyelid = state.left.uniforms.eyelid_close;
-                            state.nod_animation.trigger(time, current_eyelid);
-                        }
-                    }
-
-                    // Nod animation: sets nod_pitch uniform and overrides eyelid_close
-                    if let Some(nod_out) = state.nod_animation.evaluate(time) {
-                        state.left.uniforms.nod_pitch = nod_out.nod_pitch;
-                        state.right.uniforms.nod_pitch = nod_out.nod_pitch;
-                        state.left.uniforms.nod_pivot_y = state.nod_animation.pivot_y;
-                        state.right.uniforms.nod_pivot_y = state.nod_animation.pivot_y;
-                        state.left.uniforms.nod_sink = nod_out.nod_sink;
-                        state.right.uniforms.nod_sink = nod_out.nod_sink;
-                        state.left.uniforms.eyelid_close = nod_out.eyelid_close;
-                        state.right.uniforms.eyelid_close = nod_out.eyelid_close;
-                    } else {
-                        state.left.uniforms.nod_pitch = 0.0;
-                        state.right.uniforms.nod_pitch = 0.0;
-                        state.left.uniforms.nod_sink = 0.0;
-                        state.right.uniforms.nod_sink = 0.0;
-                    }
+                if let Some(remote) = &mut state.remote {
+                    remote.process(&mut state.controller, time);
                 }
-
-                } // end of !timeline_active block
-
-                // Sync shapes into respective uniforms
-                state.left.uniforms.outline_open =
-                    state.left.eye_shape.open.to_uniform_array();
-                state.left.uniforms.outline_closed =
-                    state.left.eye_shape.closed.to_uniform_array();
-                state.right.uniforms.outline_open =
-                    state.right.eye_shape.open.to_uniform_array();
-                state.right.uniforms.outline_closed =
-                    state.right.eye_shape.closed.to_uniform_array();
-
-                // Sync eyebrow shapes into uniforms
-                state.left.uniforms.eyebrow_color = state.left.eyebrow_shape.color;
-                state.left.uniforms.eyebrow_base_y = state.left.eyebrow_shape.base_y;
-                state.left.uniforms.eyebrow_follow = state.left.eyebrow_shape.follow;
-                state.left.uniforms.eyebrow_outline =
-                    state.left.eyebrow_shape.outline.to_uniform_array();
-                state.right.uniforms.eyebrow_color = state.right.eyebrow_shape.color;
-                state.right.uniforms.eyebrow_base_y = state.right.eyebrow_shape.base_y;
-                state.right.uniforms.eyebrow_follow = state.right.eyebrow_shape.follow;
-                state.right.uniforms.eyebrow_outline =
-                    state.right.eyebrow_shape.outline.to_uniform_array();
-
-                // Sync eyelash shapes into uniforms
-                state.left.uniforms.eyelash_color = state.left.eyelash_shape.color;
-                state.left.uniforms.eyelash_thickness = state.left.eyelash_shape.thickness;
-                state.right.uniforms.eyelash_color = state.right.eyelash_shape.color;
-                state.right.uniforms.eyelash_thickness = state.right.eyelash_shape.thickness;
-
-                // Sync iris/pupil shapes into uniforms
-                state.left.uniforms.iris_outline =
-                    state.left.iris_shape.outline.to_uniform_array();
-                state.right.uniforms.iris_outline =
-                    state.right.iris_shape.outline.to_uniform_array();
-                state.left.uniforms.pupil_outline =
-                    state.left.pupil_shape.outline.to_uniform_array();
-                state.right.uniforms.pupil_outline =
-                    state.right.pupil_shape.outline.to_uniform_array();
-
-                // Sync global params left → right
-                state.right.uniforms.bg_color = state.left.uniforms.bg_color;
-                state.right.uniforms.eye_separation = state.left.uniforms.eye_separation;
-                state.right.uniforms.max_angle = state.left.uniforms.max_angle;
-                state.right.uniforms.eye_angle = state.left.uniforms.eye_angle;
-                state.right.uniforms.head_yaw = state.left.uniforms.head_yaw;
-                state.right.uniforms.head_pitch = state.left.uniforms.head_pitch;
+                state.controller.update(time, &inputs);
+                if let Some(remote) = &state.remote {
+                    remote.broadcast(&state.controller);
+                }
+                let ws_active = state.controller.remote_gaze_active();
 
                 // --- egui frame ---
                 let raw_input = state.egui_state.take_egui_input(&state.window);
@@ -776,58 +483,68 @@ impl ApplicationHandler for App {
                 let mut gui_actions = GuiActions::default();
                 let full_output = state.egui_ctx.run(raw_input, |ctx| {
                     if show_sidebar {
-                        let audio_rms = state
-                            .audio_state
-                            .lock()
-                            .map(|a| a.rms)
-                            .unwrap_or(0.0);
+                        let c = &mut state.controller;
                         gui_actions = eye_control_panel(
                             ctx,
-                            &mut state.left,
-                            &mut state.right,
+                            &mut c.left,
+                            &mut c.right,
                             &mut state.link_shape,
                             &mut state.link_iris,
                             &mut state.link_eyebrow,
                             &mut state.link_eyelash,
                             &mut state.link_highlight,
-                            &mut state.auto_blink,
-                            &mut state.follow_mouse,
-                            &mut state.show_highlight,
-                            &mut state.show_eyebrow,
-                            &mut state.show_eyelash,
-                            &mut state.pause_animation,
-                            &mut state.focus_distance,
-                            &mut state.nod_animation,
-                            &mut state.listening_nod,
+                            &mut c.auto_blink,
+                            &mut c.auto_microsaccade,
+                            &mut c.follow_mouse,
+                            &mut c.show_highlight,
+                            &mut c.show_eyebrow,
+                            &mut c.show_eyelash,
+                            &mut c.show_iris,
+                            &mut c.show_pupil,
+                            &mut c.pause_animation,
+                            &mut c.focus_distance,
+                            &mut c.nod_animation,
+                            &mut c.blink_animation,
+                            &mut c.microsaccade_animation,
+                            &mut c.listening_nod,
+                            &mut c.pupil_dynamics,
                             audio_rms,
                             ws_active,
-                            &mut state.timeline_player,
+                            &mut c.timeline_player,
+                            &mut c.expressions,
                         );
                     }
                 });
 
                 // Handle GUI actions
                 if gui_actions.nod_triggered {
-                    let current_eyelid = state.left.uniforms.eyelid_close;
-                    state.nod_animation.trigger(time, current_eyelid);
+                    state.controller.trigger_nod(time);
                 }
 
                 if gui_actions.export_requested {
+                    let c = &state.controller;
                     let config = EyeConfig::from_state(
-                        &state.left,
-                        &state.right,
+                        &c.left,
+                        &c.right,
                         &state.link_shape,
                         &state.link_iris,
                         &state.link_eyebrow,
                         &state.link_eyelash,
                         &state.link_highlight,
-                        state.auto_blink,
-                        state.follow_mouse,
-                        state.show_highlight,
-                        state.show_eyebrow,
-                        state.show_eyelash,
-                        state.focus_distance,
-                        &state.nod_animation,
+                        c.auto_blink,
+                        c.follow_mouse,
+                        c.show_highlight,
+                        c.show_eyebrow,
+                        c.show_eyelash,
+                        c.show_iris,
+                        c.show_pupil,
+                        c.focus_distance,
+                        &c.nod_animation,
+                        c.auto_microsaccade,
+                        &c.microsaccade_animation,
+                        &c.blink_animation,
+                        &c.listening_nod,
+                        &c.pupil_dynamics,
                     );
                     if let Ok(json) = config.to_json() {
                         let file = rfd::FileDialog::new()
@@ -852,21 +569,29 @@ impl ApplicationHandler for App {
                         match std::fs::read_to_string(&path) {
                             Ok(json) => match EyeConfig::from_json(&json) {
                                 Ok(config) => {
+                                    let c = &mut state.controller;
                                     config.apply_to_state(
-                                        &mut state.left,
-                                        &mut state.right,
+                                        &mut c.left,
+                                        &mut c.right,
                                         &mut state.link_shape,
                                         &mut state.link_iris,
                                         &mut state.link_eyebrow,
                                         &mut state.link_eyelash,
                                         &mut state.link_highlight,
-                                        &mut state.auto_blink,
-                                        &mut state.follow_mouse,
-                                        &mut state.show_highlight,
-                                        &mut state.show_eyebrow,
-                                        &mut state.show_eyelash,
-                                        &mut state.focus_distance,
-                                        &mut state.nod_animation,
+                                        &mut c.auto_blink,
+                                        &mut c.follow_mouse,
+                                        &mut c.show_highlight,
+                                        &mut c.show_eyebrow,
+                                        &mut c.show_eyelash,
+                                        &mut c.show_iris,
+                                        &mut c.show_pupil,
+                                        &mut c.focus_distance,
+                                        &mut c.nod_animation,
+                                        &mut c.auto_microsaccade,
+                                        &mut c.microsaccade_animation,
+                                        &mut c.blink_animation,
+                                        &mut c.listening_nod,
+                                        &mut c.pupil_dynamics,
                                     );
                                 }
                                 Err(e) => eprintln!("Invalid config JSON: {e}"),
@@ -878,7 +603,7 @@ impl ApplicationHandler for App {
 
                 // Timeline export/import
                 if gui_actions.timeline_export_requested {
-                    if let Ok(json) = state.timeline_player.timeline.to_json() {
+                    if let Ok(json) = state.controller.timeline_player.timeline.to_json() {
                         let file = rfd::FileDialog::new()
                             .set_title("Export Timeline")
                             .add_filter("JSON", &["json"])
@@ -900,10 +625,7 @@ impl ApplicationHandler for App {
                     if let Some(path) = file {
                         match std::fs::read_to_string(&path) {
                             Ok(json) => match Timeline::from_json(&json) {
-                                Ok(timeline) => {
-                                    state.timeline_player.timeline = timeline;
-                                    state.timeline_player.selected_keyframe = None;
-                                }
+                                Ok(timeline) => state.controller.load_timeline(timeline),
                                 Err(e) => eprintln!("Invalid timeline JSON: {e}"),
                             },
                             Err(e) => eprintln!("Failed to read timeline file: {e}"),
@@ -911,32 +633,29 @@ impl ApplicationHandler for App {
                     }
                 }
 
-                // Load config file into a specific keyframe
-                if let Some(kf_idx) = gui_actions.timeline_load_file_into_keyframe {
-                    if kf_idx < state.timeline_player.timeline.keyframes.len() {
-                        let file = rfd::FileDialog::new()
-                            .set_title("Load Config into Keyframe")
-                            .add_filter("JSON", &["json"])
-                            .pick_file();
-                        if let Some(path) = file {
-                            match std::fs::read_to_string(&path) {
-                                Ok(json) => match EyeConfig::from_json(&json) {
-                                    Ok(config) => {
-                                        let kf = &mut state.timeline_player.timeline.keyframes[kf_idx];
-                                        kf.left = config.left;
-                                        kf.right = config.right;
-                                        kf.global = eye::TimelineGlobalConfig {
-                                            bg_color: config.global.bg_color,
-                                            eye_separation: config.global.eye_separation,
-                                            max_angle: config.global.max_angle,
-                                            eye_angle: config.global.eye_angle,
-                                            focus_distance: config.global.focus_distance,
-                                        };
-                                    }
-                                    Err(e) => eprintln!("Invalid config JSON: {e}"),
-                                },
-                                Err(e) => eprintln!("Failed to read config file: {e}"),
-                            }
+                // Key the differences of a config file at the playhead
+                if gui_actions.timeline_key_config_file {
+                    let file = rfd::FileDialog::new()
+                        .set_title("Key Config File")
+                        .add_filter("JSON", &["json"])
+                        .pick_file();
+                    if let Some(path) = file {
+                        match std::fs::read_to_string(&path) {
+                            Ok(json) => match EyeConfig::from_json(&json) {
+                                Ok(config) => {
+                                    let c = &mut state.controller;
+                                    let current = TimelinePose::from_state(&c.left, &c.right, c.focus_distance);
+                                    let time = c.timeline_player.current_time();
+                                    c.timeline_player.timeline.key_changes(
+                                        time,
+                                        TimelineEasing::default(),
+                                        &current,
+                                        &TimelinePose::from(&config),
+                                    );
+                                }
+                                Err(e) => eprintln!("Invalid config JSON: {e}"),
+                            },
+                            Err(e) => eprintln!("Failed to read config file: {e}"),
                         }
                     }
                 }
@@ -1002,27 +721,8 @@ impl ApplicationHandler for App {
                         occlusion_query_set: None,
                     });
 
-                    // Build paired uniforms with visibility overrides
-                    let mut left_u = state.left.uniforms;
-                    let mut right_u = state.right.uniforms;
-
-                    if !state.show_highlight {
-                        left_u.highlight_intensity = 0.0;
-                        right_u.highlight_intensity = 0.0;
-                    }
-                    if !state.show_eyebrow {
-                        left_u.eyebrow_base_y = 100.0;
-                        right_u.eyebrow_base_y = 100.0;
-                    }
-                    if !state.show_eyelash {
-                        left_u.eyelash_thickness = 0.0;
-                        right_u.eyelash_thickness = 0.0;
-                    }
-
-                    let pair = EyePairUniforms {
-                        left: left_u,
-                        right: right_u,
-                    };
+                    // Rebuild after GUI edits so slider changes show this frame
+                    let pair = state.controller.uniforms();
                     state.queue.write_buffer(
                         state.renderer.uniform_buffer(),
                         0,
@@ -1051,9 +751,7 @@ impl ApplicationHandler for App {
                 output.present();
 
                 // Only request next frame when animation is running and not paused
-                if !state.pause_animation
-                    && (state.auto_blink || ws_active || state.nod_animation.is_active() || state.listening_nod.enabled || state.timeline_player.is_playing())
-                {
+                if state.controller.is_animating() {
                     state.window.request_redraw();
                 }
             }
@@ -1071,6 +769,7 @@ fn main() {
     let mut app = App {
         state: None,
         config_path,
+        proxy: event_loop.create_proxy(),
     };
     event_loop.run_app(&mut app).unwrap();
 }
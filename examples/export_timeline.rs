@@ -0,0 +1,75 @@
+//! Export a timeline JSON to numbered PNG frames or an animated PNG at a fixed frame rate.
+//!
+//! Usage: cargo run --example export_timeline --features png -- <timeline.json> <out> [fps] [width] [height] [config.json]
+//!
+//! If `<out>` ends with `.png` a single APNG is written; otherwise it is treated as
+//! a directory and receives `frame_00000.png`, `frame_00001.png`, ...
+
+use eye::export::{export_apng, export_png_sequence};
+use eye::{EyeConfig, EyeRenderer, ExportSettings, Timeline};
+
+fn main() {
+    env_logger::init();
+
+    let mut args = std::env::args().skip(1);
+    let (Some(timeline_path), Some(out)) = (args.next(), args.next()) else {
+        eprintln!("usage: export_timeline <timeline.json> <out> [fps] [width] [height] [config.json]");
+        std::process::exit(2);
+    };
+    let defaults = ExportSettings::default();
+    let settings = ExportSettings {
+        fps: args.next().and_then(|s| s.parse().ok()).unwrap_or(defaults.fps),
+        width: args.next().and_then(|s| s.parse().ok()).unwrap_or(defaults.width),
+        height: args.next().and_then(|s| s.parse().ok()).unwrap_or(defaults.height),
+        duration: None,
+    };
+    let config_path = args.next();
+
+    let json = std::fs::read_to_string(&timeline_path).expect("failed to read timeline");
+    let timeline = Timeline::from_json(&json).expect("invalid timeline JSON");
+
+    let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+    let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+        power_preference: wgpu::PowerPreference::default(),
+        compatible_surface: None,
+        force_fallback_adapter: std::env::var_os("EYE_FALLBACK_ADAPTER").is_some(),
+    }))
+    .expect("no suitable wgpu adapter");
+    let (device, queue) = pollster::block_on(adapter.request_device(
+        &wgpu::DeviceDescriptor {
+            label: Some("eye_export_device"),
+            ..Default::default()
+        },
+        None,
+    ))
+    .expect("failed to create device");
+
+    let renderer = EyeRenderer::new(&device, wgpu::TextureFormat::Rgba8UnormSrgb);
+    let config = config_path.map(|path| {
+        let json = std::fs::read_to_string(&path).expect("failed to read config");
+        EyeConfig::from_json(&json).expect("invalid config JSON")
+    });
+
+    let result = if out.ends_with(".png") {
+        export_apng(config.as_ref(), &timeline, &renderer, &device, &queue, &settings, &out)
+    } else {
+        export_png_sequence(
+            config.as_ref(),
+            &timeline,
+            &renderer,
+            &device,
+            &queue,
+            &settings,
+            &out,
+            "frame",
+        )
+    };
+
+    match result {
+        Ok(frames) => println!("Wrote {frames} frames at {} fps to {out}", settings.fps),
+        Err(e) => {
+            eprintln!("Export failed: {e}");
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,61 @@
+//! Render a single frame to a PNG without opening a window.
+//!
+//! Usage: cargo run --example render_png --features png -- [out.png] [width] [height] [config.json]
+//!
+//! Set `EYE_FALLBACK_ADAPTER=1` to force wgpu's software adapter (llvmpipe / WARP),
+//! which is what CI machines without a GPU end up using anyway.
+
+use eye::{EyeConfig, EyeController, EyeInputs, EyeRenderer};
+
+fn main() {
+    env_logger::init();
+
+    let mut args = std::env::args().skip(1);
+    let out = args.next().unwrap_or_else(|| "eye.png".to_string());
+    let width: u32 = args.next().and_then(|s| s.parse().ok()).unwrap_or(640);
+    let height: u32 = args.next().and_then(|s| s.parse().ok()).unwrap_or(360);
+    let config_path = args.next();
+
+    let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+    let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+        power_preference: wgpu::PowerPreference::default(),
+        compatible_surface: None,
+        force_fallback_adapter: std::env::var_os("EYE_FALLBACK_ADAPTER").is_some(),
+    }))
+    .expect("no suitable wgpu adapter");
+    log::info!("Using adapter: {:?}", adapter.get_info());
+
+    let (device, queue) = pollster::block_on(adapter.request_device(
+        &wgpu::DeviceDescriptor {
+            label: Some("eye_headless_device"),
+            ..Default::default()
+        },
+        None,
+    ))
+    .expect("failed to create device");
+
+    let renderer = EyeRenderer::new(&device, wgpu::TextureFormat::Rgba8UnormSrgb);
+
+    let mut controller = EyeController::new();
+    if let Some(path) = config_path {
+        let json = std::fs::read_to_string(&path).expect("failed to read config");
+        let config = EyeConfig::from_json(&json).expect("invalid config JSON");
+        config.apply_to_controller(&mut controller);
+    }
+    // A still frame: no blink in progress, eyes looking straight ahead
+    controller.auto_blink = false;
+    controller.follow_mouse = false;
+
+    let inputs = EyeInputs {
+        aspect_ratio: width as f32 / height as f32,
+        logical_height: height as f32,
+        ..Default::default()
+    };
+    let pair = controller.update(0.0, &inputs);
+
+    let image = renderer
+        .render_to_image(&device, &queue, width, height, &pair)
+        .expect("offscreen render failed");
+    image.write_png(&out).expect("failed to write PNG");
+    println!("Wrote {out} ({width}x{height})");
+}